@@ -8,7 +8,7 @@ use web_sys::KeyboardEvent;
 
 use crate::{
 	components::FerrisError,
-	contexts::{NetworkConfigCtx, RegionConfigCtx, UiConfigCtx},
+	contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx, UiConfigCtx},
 	resources::InstancesResource,
 	themes::*,
 	utils::*,
@@ -100,14 +100,15 @@ pub fn Search() -> impl IntoView {
 	};
 
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let query = RwSignal::new(String::default());
 	let set_query = move |_| query.set(search_bar.get().unwrap().value());
 
 	let suggestions = create_resource(
 		move || (query.get(), server.get(), locale.get().to_invidious_lang()),
-		|(query, server, lang)| async move {
-			Suggestions::fetch_suggestions(&query, &server, &lang).await
+		move |(query, server, lang)| async move {
+			Suggestions::fetch_suggestions(&transport.get(), &query, &server, &lang).await
 		},
 	);
 
@@ -158,7 +159,7 @@ pub fn Search() -> impl IntoView {
 }
 
 fn search(query: String) {
-	let search_args = SearchArgs::from_str(query);
+	let search_args = SearchArgs::from_query(query);
 
 	let navigate = leptos_router::use_navigate();
 	request_animation_frame(move || {
@@ -197,10 +198,7 @@ pub fn InstanceSelectDropdown() -> impl IntoView {
 								.unwrap()
 								.into_iter()
 								.map(|instance: (String, InstanceInfo)| {
-									let api = instance.1.api.unwrap_or_default();
-									let cors = instance.1.cors.unwrap_or_default();
-									let server_visible = api && cors;
-									match server_visible {
+									match instance.1.is_usable() {
 										true => {
 											view! { <InstanceDropdownListItem instance=instance/> }
 										}
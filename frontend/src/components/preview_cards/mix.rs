@@ -0,0 +1,52 @@
+use invidious::{Mix, MixVideo};
+use leptos::*;
+
+use crate::{contexts::PlayQueueCtx, utils::get_current_video_query_signal};
+
+#[component]
+pub fn MixVideoCard(video: MixVideo, mix: Mix) -> impl IntoView {
+	let queue = expect_context::<PlayQueueCtx>();
+	let current_video = get_current_video_query_signal().0;
+
+	let src = video.thumbnails.get(3).map(|thumb| thumb.url.clone());
+	let length = utils::unix_to_hours_secs_mins(video.length as f64);
+	let index = video.index + 1;
+
+	let video_id = video.id.clone();
+	let is_current = move || current_video.get().as_deref() == Some(video_id.as_str());
+
+	let video_id = video.id;
+	let open_video = move |_| {
+		queue.start(mix.clone());
+		let navigate = leptos_router::use_navigate();
+		let id = video_id.clone();
+		request_animation_frame(move || {
+			_ = navigate(&format!("/player?id={}", id), Default::default());
+		})
+	};
+
+	let img_loaded = create_rw_signal(false);
+	let image_classes = move || match img_loaded.get() {
+		true => "w-[30%] aspect-video object-center object-cover bg-neutral rounded-lg".to_string(),
+		false => "animate-pulse w-[30%] aspect-video bg-neutral rounded-lg".to_string(),
+	};
+
+	view! {
+		<div
+			on:click=open_video
+			data-current=move || is_current().to_string()
+			class="flex flex-row items-center space-x-4 p-2 rounded-lg cursor-pointer hover:bg-base-300 data-[current=true]:bg-base-300"
+		>
+			<p class="w-6 text-sm text-center">{index}</p>
+			<img on:load=move |_| img_loaded.set(true) src=src class=image_classes/>
+			<div class="flex flex-col w-[60%]">
+				<p class="text-sm line-clamp-2">{video.title}</p>
+				<div class="flex flex-row flex-wrap mt-2 space-x-1 text-sm">
+					<p>{video.author}</p>
+					<p>{"•"}</p>
+					<p>{length}</p>
+				</div>
+			</div>
+		</div>
+	}
+}
@@ -1,4 +1,5 @@
 mod channel;
+mod mix;
 mod placeholder;
 mod playlist;
 mod popular;
@@ -6,6 +7,7 @@ mod recommended;
 mod video;
 
 pub use channel::ChannelPreviewCard;
+pub use mix::MixVideoCard;
 pub use placeholder::PlaceholderCardArray;
 pub use playlist::PlaylistPreviewCard;
 pub use popular::PopularPreviewCard;
@@ -1,4 +1,4 @@
-use config::{Config, HomepageCategory, RememberPosition};
+use config::{Config, HomepageCategory, RememberPosition, SponsorBlockCategories};
 use invidious::{AudioQuality, VideoQuality};
 use leptos::*;
 use locales::RustyTubeLocale;
@@ -23,6 +23,7 @@ pub fn provide_config_context_slices(config: Config) {
 		auto_play_slice: slice!(config.player.auto_play),
 		fast_forward_interval_slice: slice!(config.player.fast_forward_interval),
 		default_video_quality_slice: slice!(config.player.default_video_quality),
+		max_video_quality_slice: slice!(config.player.max_video_quality),
 		default_audio_quality_slice: slice!(config.player.default_audio_quality),
 		remember_position_slice: slice!(config.player.remember_position),
 		volume_slice: slice!(config.player.volume),
@@ -35,11 +36,18 @@ pub fn provide_config_context_slices(config: Config) {
 
 	let privacy_ctx = PrivacyConfigCtx { keep_history_slice: slice!(config.privacy.keep_history) };
 
+	let sponsorblock_ctx = SponsorBlockConfigCtx {
+		categories_slice: slice!(config.sponsorblock.categories),
+		min_segment_length_slice: slice!(config.sponsorblock.min_segment_length),
+		hash_prefix_lookups_slice: slice!(config.sponsorblock.hash_prefix_lookups),
+	};
+
 	provide_context(server_ctx);
 	provide_context(ui_ctx);
 	provide_context(player_ctx);
 	provide_context(region_ctx);
 	provide_context(privacy_ctx);
+	provide_context(sponsorblock_ctx);
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -61,6 +69,7 @@ pub struct PlayerConfigCtx {
 	pub auto_play_slice: (Signal<bool>, SignalSetter<bool>),
 	pub fast_forward_interval_slice: (Signal<u8>, SignalSetter<u8>),
 	pub default_video_quality_slice: (Signal<VideoQuality>, SignalSetter<VideoQuality>),
+	pub max_video_quality_slice: (Signal<VideoQuality>, SignalSetter<VideoQuality>),
 	pub default_audio_quality_slice: (Signal<AudioQuality>, SignalSetter<AudioQuality>),
 	pub remember_position_slice: (Signal<RememberPosition>, SignalSetter<RememberPosition>),
 	pub volume_slice: (Signal<f64>, SignalSetter<f64>),
@@ -77,3 +86,10 @@ pub struct RegionConfigCtx {
 pub struct PrivacyConfigCtx {
 	pub keep_history_slice: (Signal<bool>, SignalSetter<bool>),
 }
+
+#[derive(Copy, Clone, PartialEq)]
+pub struct SponsorBlockConfigCtx {
+	pub categories_slice: (Signal<SponsorBlockCategories>, SignalSetter<SponsorBlockCategories>),
+	pub min_segment_length_slice: (Signal<f64>, SignalSetter<f64>),
+	pub hash_prefix_lookups_slice: (Signal<bool>, SignalSetter<bool>),
+}
@@ -0,0 +1,403 @@
+use std::{
+	cell::{Cell, RefCell},
+	rc::Rc,
+	str::FromStr,
+};
+
+use futures::channel::oneshot;
+use gloo::{console::error, events::EventListener};
+use invidious::{AudioFormat, BandwidthEstimate, ByteRange, Format, SegmentIndex, VideoFormat};
+use leptos::*;
+use reqwasm::http::Request;
+use rustytube_error::RustyTubeError;
+use web_sys::{
+	EventTarget, HtmlVideoElement, MediaSource, MediaSourceReadyState, SourceBuffer, Url,
+};
+
+/// Seconds of media kept buffered ahead of the playback position.
+const BUFFER_AHEAD: f64 = 30f64;
+/// Seconds of media kept behind the playback position before being evicted.
+const BUFFER_BEHIND: f64 = 60f64;
+/// How far ahead of the playback position a quality switch takes effect.
+const SWITCH_MARGIN: f64 = 1f64;
+/// Buffered ranges can end a few milliseconds short of the segment that was appended.
+const SEGMENT_LOOKUP_MARGIN: f64 = 0.1f64;
+
+/// Plays DASH formats through one `MediaSource` attached to the video element, appending the
+/// segments of the video and audio formats to their own `SourceBuffer`. The segments are located
+/// from each format's `init` and `index` byte ranges and fetched just ahead of the playback
+/// position, so seeking only fetches what is needed and the streams can't drift apart.
+#[derive(Clone)]
+pub struct MediaSourceEngine {
+	video: HtmlVideoElement,
+	media_source: MediaSource,
+	object_url: Rc<String>,
+	streams: Rc<Vec<Rc<Stream>>>,
+	bandwidth: Rc<RefCell<BandwidthEstimate>>,
+}
+
+/// A format's stream appended to a `SourceBuffer`.
+struct Stream {
+	buffer: SourceBuffer,
+	source: RefCell<StreamSource>,
+	/// A source of another quality to switch to once the current append has finished.
+	pending: RefCell<Option<StreamSource>>,
+	busy: Cell<bool>,
+	finished: Cell<bool>,
+}
+
+struct StreamSource {
+	url: String,
+	r#type: String,
+	init: Vec<u8>,
+	index: SegmentIndex,
+	needs_init: bool,
+	last_appended: Option<usize>,
+}
+
+impl MediaSourceEngine {
+	/// Whether the browser has Media Source Extensions at all. iOS Safari doesn't, and asking it
+	/// whether a type is supported throws.
+	pub fn is_available() -> bool {
+		window().get("MediaSource").is_some()
+	}
+
+	/// Whether the browser can play a format type such as `video/mp4; codecs="avc1.4d401f"`
+	/// through Media Source Extensions.
+	pub fn supports_type(r#type: &str) -> bool {
+		Self::is_available() && MediaSource::is_type_supported(r#type)
+	}
+
+	pub fn supports(format: &Format) -> bool {
+		match format {
+			Format::Dash(dash) => {
+				Self::supports_type(&dash.video.r#type) && Self::supports_type(&dash.audio.r#type)
+			}
+			Format::Audio(audio) => Self::supports_type(&audio.r#type),
+			Format::Legacy(_) => false,
+		}
+	}
+
+	/// Attaches a new `MediaSource` to `video` and loads the segment indexes of `format`, which
+	/// must be a DASH or audio format. Playback starts `start_time` seconds in.
+	pub async fn attach(
+		video: &HtmlVideoElement,
+		format: &Format,
+		start_time: f64,
+	) -> Result<Self, RustyTubeError> {
+		let media_source = MediaSource::new()?;
+		let object_url = Url::create_object_url_with_source(&media_source)?;
+		let source_open = next_event(&media_source, "sourceopen");
+		video.set_src(&object_url);
+		seek_on_metadata(video, start_time);
+		source_open.await;
+
+		let sources = stream_sources(format).into_iter().map(|(url, r#type, init, index, clen)| {
+			StreamSource::load(url, r#type, init, index, clen)
+		});
+		let sources = futures::future::try_join_all(sources).await?;
+
+		let duration = sources.iter().map(|source| source.index.duration()).fold(0f64, f64::max);
+		if duration > 0f64 {
+			media_source.set_duration(duration);
+		}
+
+		let streams = sources
+			.into_iter()
+			.map(|source| {
+				let buffer = media_source.add_source_buffer(&source.r#type)?;
+				Ok(Rc::new(Stream {
+					buffer,
+					source: RefCell::new(source),
+					pending: RefCell::new(None),
+					busy: Cell::new(false),
+					finished: Cell::new(false),
+				}))
+			})
+			.collect::<Result<Vec<Rc<Stream>>, RustyTubeError>>()?;
+
+		Ok(Self {
+			video: video.clone(),
+			media_source,
+			object_url: Rc::new(object_url),
+			streams: Rc::new(streams),
+			bandwidth: Rc::new(RefCell::new(BandwidthEstimate::default())),
+		})
+	}
+
+	/// Releases the `MediaSource`. Appends that are still running stop once it is closed.
+	pub fn detach(&self) {
+		let _ = Url::revoke_object_url(&self.object_url);
+	}
+
+	/// Starts appending segments to every stream that isn't already busy.
+	pub fn fill(&self) {
+		self.streams.iter().filter(|stream| !stream.busy.get()).for_each(|stream| {
+			let engine = self.clone();
+			let stream = stream.clone();
+			stream.busy.set(true);
+			spawn_local(async move {
+				let appended = engine.append_next(&stream).await;
+				stream.busy.set(false);
+				match appended {
+					Ok(true) => engine.fill(),
+					Ok(false) => (),
+					Err(err) => error!(err.description),
+				}
+			});
+		});
+	}
+
+	/// The bandwidth measured from the segments fetched so far, in bits per second.
+	pub fn bandwidth(&self) -> Option<f64> {
+		self.bandwidth.borrow().bits_per_second()
+	}
+
+	/// Seconds buffered ahead of the playback position in the stream that has the least.
+	pub fn buffered_ahead(&self) -> f64 {
+		let time = self.video.current_time();
+		self.streams
+			.iter()
+			.map(|stream| match buffered_end(&stream.buffer, time) {
+				Ok(Some(end)) => end - time,
+				_ => 0f64,
+			})
+			.fold(f64::INFINITY, f64::min)
+	}
+
+	/// Whether `format` can replace the playing format without reloading: its streams must use
+	/// the same containers and codecs, so their initialisation segments can be appended as is.
+	pub fn can_switch(&self, format: &Format) -> bool {
+		let types = stream_sources(format).into_iter().map(|(_, r#type, ..)| r#type);
+		let current = self.streams.iter().map(|stream| stream.source.borrow().r#type.clone());
+		types.len() == self.streams.len()
+			&& types.zip(current).all(|(new, current)| codec_family(&new) == codec_family(&current))
+	}
+
+	/// Switches to `format` from just ahead of the playback position, without interrupting it.
+	pub fn switch(&self, format: &Format) {
+		let engine = self.clone();
+		let sources = stream_sources(format);
+		spawn_local(async move {
+			for (stream, (url, r#type, init, index, clen)) in engine.streams.iter().zip(sources) {
+				if stream.source.borrow().url == url {
+					continue;
+				}
+				match StreamSource::load(url, r#type, init, index, clen).await {
+					Ok(source) => {
+						stream.pending.replace(Some(source));
+						stream.finished.set(false);
+					}
+					Err(err) => error!(err.description),
+				}
+			}
+			engine.fill();
+		});
+	}
+
+	/// Appends the next segment the stream needs. Returns whether anything was appended.
+	async fn append_next(&self, stream: &Stream) -> Result<bool, RustyTubeError> {
+		// An ended source reopens when appended to, after seeking back from the end.
+		if self.media_source.ready_state() == MediaSourceReadyState::Closed {
+			return Ok(false);
+		}
+		let time = self.video.current_time();
+
+		if let Some(pending) = stream.pending.take() {
+			stream.source.replace(pending);
+			let buffered_end = buffered_end(&stream.buffer, time)?.unwrap_or(time);
+			if buffered_end > time + SWITCH_MARGIN {
+				remove(&stream.buffer, time + SWITCH_MARGIN, f64::INFINITY).await?;
+			}
+		}
+
+		let init = stream.source.borrow().needs_init.then(|| stream.source.borrow().init.clone());
+		if let Some(init) = init {
+			append(&stream.buffer, init).await?;
+			stream.source.borrow_mut().needs_init = false;
+			return Ok(true);
+		}
+
+		let buffered_end = buffered_end(&stream.buffer, time)?.unwrap_or(time);
+		if buffered_end - time > BUFFER_AHEAD {
+			return Ok(false);
+		}
+		self.evict(stream, time).await?;
+
+		let (url, segment, next) = {
+			let source = stream.source.borrow();
+			let next =
+				source.index.segment_at(buffered_end + SEGMENT_LOOKUP_MARGIN).map(
+					|next| match Some(next) == source.last_appended {
+						true => next + 1,
+						false => next,
+					},
+				);
+			let segment = next.and_then(|next| source.index.segments.get(next)).copied();
+			(source.url.clone(), segment, next)
+		};
+		let Some(segment) = segment else {
+			stream.finished.set(true);
+			self.end_if_finished();
+			return Ok(false);
+		};
+
+		let started = now();
+		let bytes = fetch_range(&url, segment.bytes).await?;
+		self.bandwidth.borrow_mut().sample(bytes.len() as u64, (now() - started) / 1000f64);
+		if stream.pending.borrow().is_some() {
+			// The quality changed while fetching, the next call appends the new one instead.
+			return Ok(true);
+		}
+		append(&stream.buffer, bytes).await?;
+		stream.source.borrow_mut().last_appended = next;
+		stream.finished.set(false);
+		Ok(true)
+	}
+
+	/// Removes media far behind the playback position, so long videos fit in the buffer quota.
+	async fn evict(&self, stream: &Stream, time: f64) -> Result<(), RustyTubeError> {
+		let buffered = stream.buffer.buffered()?;
+		if buffered.length() > 0 && time - buffered.start(0)? > BUFFER_BEHIND {
+			remove(&stream.buffer, 0f64, time - BUFFER_BEHIND / 2f64).await?;
+		}
+		Ok(())
+	}
+
+	fn end_if_finished(&self) {
+		let all_finished = self.streams.iter().all(|stream| stream.finished.get());
+		let updating = self.streams.iter().any(|stream| stream.buffer.updating());
+		if all_finished
+			&& !updating
+			&& self.media_source.ready_state() == MediaSourceReadyState::Open
+		{
+			let _ = self.media_source.end_of_stream();
+		}
+	}
+}
+
+impl StreamSource {
+	/// Fetches the format's `init` and `index` ranges, which always start the file, in one request.
+	async fn load(
+		url: String,
+		r#type: String,
+		init: String,
+		index: String,
+		clen: String,
+	) -> Result<Self, RustyTubeError> {
+		let init = ByteRange::from_str(&init)?;
+		let index = ByteRange::from_str(&index)?;
+		let clen = clen.parse::<u64>().map_err(|_| RustyTubeError::format_parse())?;
+		let header_end = init.end.max(index.end);
+
+		let header = fetch_range(&url, ByteRange::new(0, header_end)).await?;
+		let segment_index = SegmentIndex::parse(&r#type, &header, index, clen)?;
+		let init = header
+			.get(init.start as usize..=init.end as usize)
+			.ok_or(RustyTubeError::segment_index_parse("the init range is missing"))?
+			.to_vec();
+
+		Ok(Self { url, r#type, init, index: segment_index, needs_init: true, last_appended: None })
+	}
+}
+
+/// The url, type, `init` range, `index` range and length of each stream of a format.
+fn stream_sources(format: &Format) -> Vec<(String, String, String, String, String)> {
+	let video = |format: &VideoFormat| {
+		(
+			format.url.clone(),
+			format.r#type.clone(),
+			format.init.clone(),
+			format.index.clone(),
+			format.clen.clone(),
+		)
+	};
+	let audio = |format: &AudioFormat| {
+		(
+			format.url.clone(),
+			format.r#type.clone(),
+			format.init.clone(),
+			format.index.clone(),
+			format.clen.clone(),
+		)
+	};
+
+	match format {
+		Format::Dash(dash) => vec![video(&dash.video), audio(&dash.audio)],
+		Format::Audio(format) => vec![audio(format)],
+		Format::Legacy(_) => vec![],
+	}
+}
+
+/// The type up to the codec's profile, e.g. `video/mp4; codecs="avc1` for
+/// `video/mp4; codecs="avc1.4d401f"`.
+fn codec_family(r#type: &str) -> &str {
+	r#type.split('.').next().unwrap_or(r#type)
+}
+
+/// Googlevideo urls take the byte range as a parameter, which avoids a CORS preflight for a
+/// `Range` header.
+async fn fetch_range(url: &str, range: ByteRange) -> Result<Vec<u8>, RustyTubeError> {
+	let range_url = format!("{}&range={}-{}", url, range.start, range.end);
+	let response = Request::get(&range_url).send().await?;
+	if !response.ok() {
+		return Err(RustyTubeError::instance_error(response.status(), &response.status_text()));
+	}
+	Ok(response.binary().await?)
+}
+
+/// Milliseconds since the page loaded.
+fn now() -> f64 {
+	window().performance().map(|performance| performance.now()).unwrap_or_default()
+}
+
+async fn append(buffer: &SourceBuffer, mut bytes: Vec<u8>) -> Result<(), RustyTubeError> {
+	let update_end = next_event(buffer, "updateend");
+	buffer.append_buffer_with_u8_array(&mut bytes[..])?;
+	update_end.await;
+	Ok(())
+}
+
+async fn remove(buffer: &SourceBuffer, start: f64, end: f64) -> Result<(), RustyTubeError> {
+	let update_end = next_event(buffer, "updateend");
+	buffer.remove(start, end)?;
+	update_end.await;
+	Ok(())
+}
+
+/// The end of the buffered range that contains `time`, if it is buffered.
+fn buffered_end(buffer: &SourceBuffer, time: f64) -> Result<Option<f64>, RustyTubeError> {
+	let buffered = buffer.buffered()?;
+	for range in 0..buffered.length() {
+		let (start, end) = (buffered.start(range)?, buffered.end(range)?);
+		if start <= time + SEGMENT_LOOKUP_MARGIN && time <= end {
+			return Ok(Some(end));
+		}
+	}
+	Ok(None)
+}
+
+/// Seeks `video` to `time` once the source it was just given has loaded its metadata, as seeking
+/// any earlier is lost when the source changes.
+pub fn seek_on_metadata(video: &HtmlVideoElement, time: f64) {
+	if time <= 0f64 {
+		return;
+	}
+	let target = video.clone();
+	EventListener::once(video, "loadedmetadata", move |_| target.set_current_time(time)).forget();
+}
+
+/// Resolves once `target` next fires the `event_type` event.
+fn next_event(
+	target: &EventTarget,
+	event_type: &'static str,
+) -> impl std::future::Future<Output = ()> {
+	let (sender, receiver) = oneshot::channel::<()>();
+	let listener = EventListener::once(target, event_type, move |_| {
+		let _ = sender.send(());
+	});
+	async move {
+		let _ = receiver.await;
+		drop(listener);
+	}
+}
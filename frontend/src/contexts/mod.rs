@@ -1,9 +1,17 @@
 mod config;
+mod media_source;
 mod player;
+mod queue;
+mod search;
 mod toaster;
+mod transport;
 mod user;
 
 pub use config::*;
+pub use media_source::*;
 pub use player::*;
+pub use queue::*;
+pub use search::*;
 pub use toaster::*;
+pub use transport::*;
 pub use user::*;
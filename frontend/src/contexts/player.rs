@@ -1,15 +1,17 @@
-use std::ops::RangeBounds;
-
-use invidious::Format;
+use config::SegmentBehaviour;
+use invidious::{DashFormat, Format, QualityLadder, VideoFormat, VideoQuality};
 use leptos::{error::Result, *};
 use rustytube_error::RustyTubeError;
+use sponsorblock_rs::{Action, Segment};
 use utils::get_element_by_id;
-use web_sys::{HtmlAudioElement, HtmlVideoElement};
+use web_sys::HtmlVideoElement;
 
 use crate::{
-	contexts::PlayerConfigCtx,
+	contexts::{
+		seek_on_metadata, MediaSourceEngine, PlayQueueCtx, PlayerConfigCtx, SponsorBlockConfigCtx,
+	},
 	resources::SponsorBlockResource,
-	utils::{i18n, is_webkit},
+	utils::i18n,
 };
 
 use super::{toast, Toast};
@@ -17,7 +19,6 @@ use super::{toast, Toast};
 pub const VIDEO_CONTAINER_ID: &'static str = "video_container";
 pub const VIDEO_PLAYER_ID: &'static str = "video_player";
 pub const VIDEO_CONTROLS_ID: &'static str = "video_controls";
-pub const AUDIO_PLAYER_ID: &'static str = "audio_player";
 
 #[derive(Copy, Clone, PartialEq)]
 pub enum PlaybackState {
@@ -29,84 +30,129 @@ pub enum PlaybackState {
 
 #[derive(Clone, Copy)]
 pub struct PlayerState {
-	format: RwSignal<Option<Format>>,
+	pub format: RwSignal<Option<Format>>,
+	engine: StoredValue<Option<MediaSourceEngine>>,
+	/// Whether the video quality follows the measured bandwidth, rather than a chosen format.
+	pub auto_quality: RwSignal<bool>,
 	pub playback_state: RwSignal<PlaybackState>,
 	video_ready: RwSignal<bool>,
-	audio_ready: RwSignal<bool>,
 	pub volume: RwSignal<f64>,
 	pub current_time: RwSignal<f64>,
 	pub duration: RwSignal<f64>,
 	pub current_time_str: RwSignal<String>,
 	pub duration_str: RwSignal<String>,
+	/// The SponsorBlock segment playing that can be skipped with a button.
+	pub skippable_segment: RwSignal<Option<Segment>>,
+	/// Whether the video is muted for a SponsorBlock segment.
+	sponsorblock_muted: RwSignal<bool>,
 }
 
 impl PlayerState {
 	pub fn init() -> Self {
 		let format = create_rw_signal(None);
+		let engine = store_value(None);
+		let auto_quality = create_rw_signal(true);
 		let playback_state = create_rw_signal(PlaybackState::Initial);
 		let video_ready = create_rw_signal(false);
-		let audio_ready = create_rw_signal(false);
 		let volume = create_rw_signal(expect_context::<PlayerConfigCtx>().volume_slice.0.get());
 		let current_time_str = create_rw_signal(String::from("0:00"));
 		let duration_str = create_rw_signal(String::from("0:00"));
 		let current_time = create_rw_signal(0f64);
 		let duration = create_rw_signal(0f64);
+		let skippable_segment = create_rw_signal(None);
+		let sponsorblock_muted = create_rw_signal(false);
 
 		Self {
 			format,
+			engine,
+			auto_quality,
 			playback_state,
 			video_ready,
-			audio_ready,
 			volume,
 			current_time,
 			current_time_str,
 			duration,
 			duration_str,
+			skippable_segment,
+			sponsorblock_muted,
 		}
 	}
 
-	pub fn ready(&self) -> Result<bool, RustyTubeError> {
+	/// Starts playing `format` in the video element. Legacy formats are played from their url,
+	/// DASH and audio formats through a [`MediaSourceEngine`]. Playback starts `start_time`
+	/// seconds in.
+	pub fn load(&self, format: Format, start_time: f64) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
-
-		let ready = match is_webkit() {
-			true => {
-				if self.format.get().map_or(false, |format| format.is_audio_only()) {
-					audio.ready_state() >= 3
-				} else if self.format.get().map_or(false, |format| format.is_legacy()) {
-					video.ready_state() >= 3
-				} else {
-					video.ready_state() >= 3 && audio.ready_state() >= 3
-				}
+
+		self.engine.update_value(|engine| {
+			if let Some(engine) = engine.take() {
+				engine.detach();
 			}
-			false => {
-				if self.format.get().map_or(false, |format| format.is_audio_only()) {
-					self.audio_ready.get() && audio.ready_state() >= 3
-				} else if self.format.get().map_or(false, |format| format.is_legacy()) {
-					self.video_ready.get() && video.ready_state() >= 3
-				} else {
-					self.video_ready.get()
-						&& self.audio_ready.get()
-						&& video.ready_state() >= 3
-						&& audio.ready_state() >= 3
-				}
+		});
+		self.format.set(Some(format.clone()));
+
+		match format {
+			Format::Legacy(legacy) => {
+				video.set_src(&legacy.url);
+				seek_on_metadata(&video, start_time);
 			}
-		};
+			format => {
+				let state = *self;
+				let owner = Owner::current();
+				spawn_local(async move {
+					match MediaSourceEngine::attach(&video, &format, start_time).await {
+						// Another format may have been loaded while this one was attaching.
+						Ok(engine)
+							if state
+								.format
+								.with_untracked(|current| current.as_ref() != Some(&format)) =>
+						{
+							engine.detach()
+						}
+						Ok(engine) => {
+							engine.fill();
+							state.engine.set_value(Some(engine));
+						}
+						Err(err) => {
+							let toast_error = move || {
+								toast(Toast::new(
+									err.description,
+									Some(super::ToastDuration::Normal),
+									Some(super::ToastType::Error),
+								))
+							};
+							match owner {
+								Some(owner) => with_owner(owner, toast_error),
+								None => toast_error(),
+							}
+						}
+					}
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Appends the media needed around the playback position when playing through MSE.
+	pub fn buffer(&self) {
+		self.engine.with_value(|engine| {
+			if let Some(engine) = engine {
+				engine.fill();
+			}
+		});
+	}
 
-		Ok(ready)
+	pub fn ready(&self) -> Result<bool, RustyTubeError> {
+		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
+		Ok(self.video_ready.get() && video.ready_state() >= 3)
 	}
 
 	pub fn play(&self) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
 
 		if self.ready()? {
-			audio.set_volume(self.volume.get());
-			let video_play = video.play();
-			video.set_current_time(audio.current_time());
-			let audio_play = audio.play();
-
-			if audio_play.is_ok() && video_play.is_ok() {
+			video.set_volume(self.volume.get());
+			if video.play().is_ok() {
 				self.playback_state.set(PlaybackState::Playing);
 			}
 		}
@@ -116,27 +162,13 @@ impl PlayerState {
 
 	pub fn resume(&self) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
 
 		if self.playback_state.get() == PlaybackState::Loading
 			|| self.playback_state.get() == PlaybackState::Paused
 		{
-			if is_webkit() {
-				let video_play = video.play();
-				let audio_play = audio.play();
-
-				if audio_play.is_ok() && video_play.is_ok() {
-					self.playback_state.set(PlaybackState::Playing);
-				}
-			} else {
-				audio.set_volume(self.volume.get());
-				let video_play = video.play();
-				audio.set_current_time(video.current_time());
-				let audio_play = audio.play();
-
-				if audio_play.is_ok() && video_play.is_ok() {
-					self.playback_state.set(PlaybackState::Playing);
-				}
+			video.set_volume(self.volume.get());
+			if video.play().is_ok() {
+				self.playback_state.set(PlaybackState::Playing);
 			}
 		}
 		Ok(())
@@ -144,11 +176,8 @@ impl PlayerState {
 
 	pub fn pause(&self) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
-		let video_pause = video.pause();
-		let audio_pause = audio.pause();
-		if audio_pause.is_ok() && video_pause.is_ok() {
-			self.playback_state.set(PlaybackState::Playing);
+		if video.pause().is_ok() {
+			self.playback_state.set(PlaybackState::Paused);
 		}
 		Ok(())
 	}
@@ -171,58 +200,17 @@ impl PlayerState {
 		Ok(())
 	}
 
-	pub fn set_audio_ready(&self, ready: bool) -> Result<(), RustyTubeError> {
-		self.audio_ready.set(ready);
-		if self.ready()? && self.playback_state.get() != PlaybackState::Initial {
-			self.resume()?;
-		}
-		Ok(())
-	}
-
-	pub fn sync(&self) -> Result<(), RustyTubeError> {
-		if let Some(Some(format)) = self.format.try_get() {
-			if !format.is_audio_only() && !format.is_legacy() {
-				let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-				let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
-
-				let video_time = video.current_time();
-				let audio_time = audio.current_time();
-
-				let initial_start = video_time < 3.0 || audio_time < 3.0;
-				let out_of_sync =
-					video_time > audio_time + 0.125 || video_time + 0.125 < audio_time;
-				if !initial_start && out_of_sync {
-					video.set_current_time(audio_time);
-				}
-			}
-		}
-
-		Ok(())
-	}
-
 	pub fn seek(&self, time: f64) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
 
 		self.pause()?;
 		self.set_video_ready(false)?;
 		self.playback_state.set(PlaybackState::Loading);
 
-		match is_webkit() {
-			true => {
-				video.set_current_time(time);
-				audio.set_current_time(time);
-			}
-			false => {
-				self.set_audio_ready(false)?;
-				let fast_seek_video = video.fast_seek(time);
-				let fast_seek_audio = audio.fast_seek(time);
-				if fast_seek_audio.is_err() || fast_seek_video.is_err() {
-					video.set_current_time(time);
-					audio.set_current_time(time);
-				}
-			}
+		if video.fast_seek(time).is_err() {
+			video.set_current_time(time);
 		}
+		self.buffer();
 
 		self.current_time.set(time);
 		self.current_time_str.set(utils::unix_to_hours_secs_mins(time));
@@ -240,53 +228,156 @@ impl PlayerState {
 		self.current_time_str.set(utils::unix_to_hours_secs_mins(current_time));
 		self.duration_str.set(utils::unix_to_hours_secs_mins(total_time));
 		self.check_sponsorblock(current_time)?;
+		self.buffer();
 		Ok(())
 	}
 
+	/// Switches to `format` at the current position. Formats whose codecs match the playing ones
+	/// take over seamlessly, others are reloaded.
 	pub fn change_format(&self, format: Format) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
+
+		let engine = self
+			.engine
+			.with_value(|engine| engine.clone().filter(|engine| engine.can_switch(&format)));
+		if let Some(engine) = engine {
+			engine.switch(&format);
+			self.format.set(Some(format));
+			return Ok(());
+		}
 
 		let current_time = video.current_time();
-		video.set_src(&format.video_url().unwrap_or_default());
-		audio.set_src(&format.audio_url().unwrap_or_default());
 		self.pause()?;
 		self.set_video_ready(false)?;
 		self.playback_state.set(PlaybackState::Loading);
-		self.format.set(Some(format));
-		video.set_current_time(current_time);
-		audio.set_current_time(current_time);
+		self.load(format, current_time)?;
 		self.current_time.set(current_time);
 		self.current_time_str.set(utils::unix_to_hours_secs_mins(current_time));
 		Ok(())
 	}
 
+	/// Steps the video quality up or down while "Auto" quality is on, from the bandwidth measured
+	/// by the engine and how much is buffered, never above `max_quality`.
+	pub fn adapt_quality(
+		&self,
+		video_formats: &[VideoFormat],
+		max_quality: &VideoQuality,
+	) -> Result<(), RustyTubeError> {
+		if !self.auto_quality.get_untracked() {
+			return Ok(());
+		}
+		let Some(Format::Dash(dash)) = self.format.get_untracked() else {
+			return Ok(());
+		};
+		let Some(engine) = self.engine.get_value() else {
+			return Ok(());
+		};
+		let Some(bandwidth) = engine.bandwidth() else {
+			return Ok(());
+		};
+
+		let ladder = QualityLadder::new(video_formats, &dash.video, max_quality);
+		match ladder.next(&dash.video, bandwidth, engine.buffered_ahead()) {
+			Some(next) if next != &dash.video => {
+				self.change_format(Format::Dash(DashFormat::new(next.clone(), dash.audio)))
+			}
+			_ => Ok(()),
+		}
+	}
+
 	pub fn set_volume(&self, volume: f64) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
-		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
 
 		video.set_volume(volume);
-		audio.set_volume(volume);
 		self.volume.set(volume);
 		expect_context::<PlayerConfigCtx>().volume_slice.1.set(volume);
 
 		Ok(())
 	}
 
+	/// Opens the next video of the play queue once `video_id` has ended, resuming playback as
+	/// soon as it is ready.
+	pub fn play_next(
+		&self,
+		queue: PlayQueueCtx,
+		video_id: &str,
+		open_video: SignalSetter<Option<String>>,
+	) {
+		if let Some(next) = queue.advance(video_id) {
+			self.playback_state.set(PlaybackState::Loading);
+			open_video.set(Some(next));
+		}
+	}
+
+	/// Acts on the SponsorBlock segments playing at `time` as configured for their category:
+	/// skipping them as they start, muting them, or offering to skip them with a button.
 	pub fn check_sponsorblock(&self, time: f64) -> Result<(), RustyTubeError> {
-		if let Some(segments) = expect_context::<SponsorBlockResource>().get_segments() {
-			segments.into_iter().for_each(|segment| {
-				let range =
-					(segment.timeframe.0.round() - 1f64)..=(segment.timeframe.0.round() + 2f64);
-				if range.contains(&time) {
-					self.seek(segment.timeframe.1);
+		let config = expect_context::<SponsorBlockConfigCtx>();
+		let categories = config.categories_slice.0.get_untracked();
+		let min_segment_length = config.min_segment_length_slice.0.get_untracked();
+		let segments = expect_context::<SponsorBlockResource>().get_segments().unwrap_or_default();
+
+		let playing = segments
+			.into_iter()
+			.filter(|segment| segment.duration() >= min_segment_length)
+			.filter(|segment| (segment.start()..segment.end()).contains(&time))
+			.filter_map(|segment| {
+				let behaviour = categories.get(segment.category()?);
+				(behaviour != SegmentBehaviour::Ignore).then_some((segment, behaviour))
+			})
+			.collect::<Vec<(Segment, SegmentBehaviour)>>();
+
+		// Segments of the mute action type only make sense muted, whatever the category's setting.
+		let muted = playing.iter().any(|(segment, behaviour)| {
+			segment.action() == Some(Action::Mute) || *behaviour == SegmentBehaviour::Mute
+		});
+		self.set_sponsorblock_muted(muted)?;
+
+		let mut skippable = None;
+		for (segment, behaviour) in playing {
+			if segment.action() != Some(Action::Skip) {
+				continue;
+			}
+			match behaviour {
+				// Only skip as the segment starts, so seeking back into it plays it.
+				SegmentBehaviour::AutoSkip if time <= segment.start().round() + 2f64 => {
+					self.skippable_segment.set(None);
+					self.seek(segment.end())?;
 					toast(Toast::new(
 						i18n("sponsorblock.skipped")(),
 						Some(super::ToastDuration::Normal),
 						Some(super::ToastType::Info),
-					))
+					));
+					return Ok(());
 				}
-			});
+				SegmentBehaviour::AutoSkip | SegmentBehaviour::ManualSkip => {
+					skippable = Some(segment)
+				}
+				SegmentBehaviour::Mute | SegmentBehaviour::Ignore => (),
+			}
+		}
+		if self.skippable_segment.with_untracked(|current| current.as_ref().map(|s| &s.uuid))
+			!= skippable.as_ref().map(|s| &s.uuid)
+		{
+			self.skippable_segment.set(skippable);
+		}
+		Ok(())
+	}
+
+	/// Skips to the end of the segment offered by [`PlayerState::check_sponsorblock`].
+	pub fn skip_segment(&self) -> Result<(), RustyTubeError> {
+		if let Some(segment) = self.skippable_segment.get_untracked() {
+			self.skippable_segment.set(None);
+			self.seek(segment.end())?;
+		}
+		Ok(())
+	}
+
+	fn set_sponsorblock_muted(&self, muted: bool) -> Result<(), RustyTubeError> {
+		if self.sponsorblock_muted.get_untracked() != muted {
+			let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
+			video.set_muted(muted);
+			self.sponsorblock_muted.set(muted);
 		}
 		Ok(())
 	}
@@ -305,6 +396,7 @@ pub struct PlayerStyle {
 	pub controls_visible: RwSignal<bool>,
 	pub full_window: RwSignal<bool>,
 	pub fullscreen: RwSignal<bool>,
+	pub annotations_visible: RwSignal<bool>,
 }
 
 impl PlayerStyle {
@@ -312,7 +404,8 @@ impl PlayerStyle {
 		let controls_visible = create_rw_signal(false);
 		let full_window = create_rw_signal(false);
 		let fullscreen = create_rw_signal(false);
+		let annotations_visible = create_rw_signal(true);
 
-		Self { controls_visible, full_window, fullscreen }
+		Self { controls_visible, full_window, fullscreen, annotations_visible }
 	}
 }
@@ -0,0 +1,38 @@
+use invidious::Mix;
+use leptos::*;
+
+/// The mix currently being played through, one video after another.
+#[derive(Clone, Copy)]
+pub struct PlayQueueCtx {
+	pub mix: RwSignal<Option<Mix>>,
+}
+
+impl PlayQueueCtx {
+	pub fn start(&self, mix: Mix) {
+		self.mix.set(Some(mix));
+	}
+
+	pub fn stop(&self) {
+		self.mix.set(None);
+	}
+
+	pub fn is_playing(&self, mix_id: &str) -> bool {
+		self.mix.with(|mix| mix.as_ref().map_or(false, |mix| mix.id == mix_id))
+	}
+
+	/// Returns the video to open once `video_id` has ended, stopping the queue when the mix
+	/// runs out.
+	pub fn advance(&self, video_id: &str) -> Option<String> {
+		let next = self.mix.with_untracked(|mix| {
+			mix.as_ref().map(|mix| mix.next_after(video_id).map(|video| video.id.clone()))
+		})?;
+		if next.is_none() {
+			self.stop();
+		}
+		next
+	}
+}
+
+pub fn provide_play_queue_ctx() {
+	provide_context(PlayQueueCtx { mix: RwSignal::new(None) });
+}
@@ -0,0 +1,54 @@
+use invidious::{SearchArgs, SearchResults};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+/// The pages loaded for a search, kept while browsing so going back to the search shows them
+/// where they were left.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SearchPages {
+	/// The server, query string and language the results were fetched with.
+	pub key: (String, String, String),
+	pub args: SearchArgs,
+	pub results: SearchResults,
+	pub next_page: u32,
+	/// Whether the last page fetched had nothing new, so there is nothing more to load.
+	pub exhausted: bool,
+}
+
+impl SearchPages {
+	pub fn new(key: (String, String, String), args: SearchArgs, results: SearchResults) -> Self {
+		let next_page = args.page.unwrap_or(1) + 1;
+		let exhausted = results.items.is_empty();
+		Self { key, args, results, next_page, exhausted }
+	}
+
+	pub fn push_page(&mut self, page: SearchResults) {
+		self.exhausted = self.results.append(page) == 0;
+		self.next_page += 1;
+	}
+}
+
+/// The most recent search, the one a back navigation returns to.
+#[derive(Clone, Copy)]
+pub struct SearchCacheCtx {
+	pub pages: StoredValue<Option<SearchPages>>,
+	/// How far the results of the search were scrolled.
+	pub scroll_top: StoredValue<i32>,
+}
+
+impl SearchCacheCtx {
+	pub fn get(&self, key: &(String, String, String)) -> Option<SearchPages> {
+		self.pages.with_value(|pages| pages.as_ref().filter(|pages| &pages.key == key).cloned())
+	}
+
+	pub fn store(&self, pages: SearchPages) {
+		if self.get(&pages.key).is_none() {
+			self.scroll_top.set_value(0);
+		}
+		self.pages.set_value(Some(pages));
+	}
+}
+
+pub fn provide_search_cache_ctx() {
+	provide_context(SearchCacheCtx { pages: store_value(None), scroll_top: store_value(0) });
+}
@@ -0,0 +1,65 @@
+use invidious::{
+	BrowserCacheStore, BrowserTransport, CachePolicy, CachedTransport, FailoverTransport,
+	InstanceSwitch,
+};
+use leptos::*;
+
+use crate::{
+	contexts::{NetworkConfigCtx, RegionConfigCtx, Toast, ToastDuration, ToastType, Toaster},
+	resources::InstancesResource,
+};
+
+pub type InvidiousTransport = CachedTransport<FailoverTransport<BrowserTransport>>;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TransportCtx(StoredValue<InvidiousTransport>);
+
+impl TransportCtx {
+	pub fn get(&self) -> InvidiousTransport {
+		self.0.get_value()
+	}
+
+	/// Keeps the failover list in sync with the usable public instances and custom servers.
+	pub fn follow_instances(&self, instances: InstancesResource) {
+		let custom_servers = expect_context::<NetworkConfigCtx>().custom_servers_slice.0;
+		let transport = self.0;
+
+		create_effect(move |_| {
+			let mut servers = custom_servers.get().unwrap_or_default();
+			if let Some(Ok(instances)) = instances.resource.get() {
+				instances
+					.into_iter()
+					.filter(|(_, info)| info.is_usable())
+					.for_each(|(_, info)| servers.push(info.uri));
+			}
+			transport.with_value(|transport| transport.inner().set_servers(servers));
+		});
+	}
+}
+
+pub fn provide_transport_ctx() {
+	let failover = FailoverTransport::new(BrowserTransport, vec![]);
+	let server = expect_context::<NetworkConfigCtx>().server_slice.1;
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+	let toaster = expect_context::<Toaster>();
+
+	failover.on_switch(move |switch: InstanceSwitch| {
+		server.set(switch.to.clone());
+		let message = t!(
+			"network.instance_switched",
+			server = switch.to,
+			locale = &locale.get_untracked().id()
+		);
+		toaster.add_toast(Toast::new(
+			message.to_string(),
+			Some(ToastDuration::Long),
+			Some(ToastType::Warning),
+		));
+	});
+
+	let transport = CachedTransport::new(failover, CachePolicy::default())
+		.with_store(BrowserCacheStore)
+		.with_revalidator(spawn_local);
+
+	provide_context(TransportCtx(StoredValue::new(transport)));
+}
@@ -19,10 +19,13 @@ pub use themes::*;
 
 use crate::{
 	components::Page,
-	contexts::{provide_config_context_slices, provide_toaster_ctx},
+	contexts::{
+		provide_config_context_slices, provide_play_queue_ctx, provide_search_cache_ctx,
+		provide_toaster_ctx, provide_transport_ctx, TransportCtx,
+	},
 	pages::{
-		ChannelPage, PopularSection, SearchSection, SettingsPage, SubscriptionsSection,
-		TrendingSection, VideoPage,
+		ChannelPage, HashtagPage, MixPage, PopularSection, SearchSection, SettingsPage,
+		SubscriptionsSection, TrendingSection, VideoPage,
 	},
 	resources::{
 		InstancesResource, SponsorBlockResource, SubscriptionsCtx, SubscriptionsThumbnailsResource,
@@ -35,16 +38,27 @@ use crate::{
 fn App() -> impl IntoView {
 	console_error_panic_hook::set_once();
 	provide_toaster_ctx();
+	provide_play_queue_ctx();
+	provide_search_cache_ctx();
 	provide_context::<SponsorBlockResource>(SponsorBlockResource::empty());
 
-	provide_config_context_slices(Config::load().unwrap_or_default());
+	let config = Config::load();
+	let first_run = config.is_err();
+	provide_config_context_slices(config.unwrap_or_default());
+
+	provide_transport_ctx();
+	let instances_resource = InstancesResource::initialise();
+	expect_context::<TransportCtx>().follow_instances(instances_resource);
+	if first_run {
+		instances_resource.select_best_on_load();
+	}
 
 	let subscriptions_resource = SubscriptionsCtx::initialise();
 
 	provide_context(subscriptions_resource);
 	provide_context(SubscriptionsVideosResource::initialise(subscriptions_resource));
 	provide_context(SubscriptionsThumbnailsResource::initialise(subscriptions_resource));
-	provide_context(InstancesResource::initialise());
+	provide_context(instances_resource);
 
 	view! {
 		<Router>
@@ -58,6 +72,8 @@ fn App() -> impl IntoView {
 					<Route path="/popular" view=move || view! { <PopularSection/> }/>
 					<Route path="/search" view=move || view! { <SearchSection/> }/>
 					<Route path="/playlist" view=move || ().into_view()/>
+					<Route path="/mix" view=move || view! { <MixPage/> }/>
+					<Route path="/hashtag" view=move || view! { <HashtagPage/> }/>
 					<Route path="/settings" view=move || view! { <SettingsPage/> }/>
 					<Route path="/about" view=move || ().into_view()/>
 				</Route>
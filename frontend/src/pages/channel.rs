@@ -1,15 +1,23 @@
 use invidious::{
-	Channel, ChannelLivestreams, ChannelPlaylists, ChannelShorts, ChannelVideos, CommonPlaylist,
-	CommonVideo,
+	Channel, ChannelLivestreams, ChannelPlaylists, ChannelSearch, ChannelShorts, ChannelVideos,
+	CommonImage, CommonPlaylist, CommonVideo, CommunityPost, CommunityPosts, PollChoice,
+	PostAttachment, PostPlaylist, PostVideo, SearchResult,
 };
-use leptos::*;
+use leptos::{html::Input, *};
 use leptos_router::create_query_signal;
+use num_format::ToFormattedString;
+use phosphor_leptos::{Chat, IconWeight, MagnifyingGlass, ThumbsUp};
 use rustytube_error::RustyTubeError;
+use web_sys::KeyboardEvent;
 
 use crate::{
-	components::{FerrisError, PlaceholderCardArray, PlaylistPreviewCard, VideoPreviewCard},
-	contexts::{NetworkConfigCtx, RegionConfigCtx},
+	components::{
+		ChannelPreviewCard, FerrisError, PlaceholderCardArray, PlaylistPreviewCard,
+		VideoPreviewCard,
+	},
+	contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx},
 	resources::SubscriptionsCtx,
+	utils::i18n,
 };
 
 #[derive(Clone)]
@@ -18,19 +26,24 @@ enum ContentCategory {
 	Shorts,
 	Livestreams,
 	Playlists,
+	Community,
+	Search(String),
 }
 
 #[component]
 pub fn ChannelPage() -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 	let id_query: Memo<Option<String>> = create_query_signal("id").0;
 
 	let channel = create_resource(
 		move || {
 			(server.get(), id_query.get().unwrap_or_default(), locale.get().to_invidious_lang())
 		},
-		|(server, id, lang)| async move { Channel::fetch_channel(&server, &id, &lang).await },
+		move |(server, id, lang)| async move {
+			Channel::fetch_channel(&transport.get(), &server, &id, &lang).await
+		},
 	);
 
 	view! {
@@ -181,30 +194,73 @@ fn ContentCategoryButtons() -> impl IntoView {
 	let content_category = expect_context::<RwSignal<ContentCategory>>();
 
 	view! {
-		<div class="flex flex-row gap-x-3">
-			<button
-				on:click=move |_| content_category.set(ContentCategory::Videos)
-				class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
-			>
-				Videos
-			</button>
-			<button
-				on:click=move |_| content_category.set(ContentCategory::Shorts)
-				class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
-			>
-				Shorts
-			</button>
-			<button
-				on:click=move |_| content_category.set(ContentCategory::Livestreams)
-				class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
-			>
-				Livestreams
-			</button>
-			<button
-				on:click=move |_| content_category.set(ContentCategory::Playlists)
-				class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
-			>
-				Playlists
+		<div class="flex flex-row flex-wrap gap-3 justify-between">
+			<div class="flex flex-row gap-x-3">
+				<button
+					on:click=move |_| content_category.set(ContentCategory::Videos)
+					class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
+				>
+					Videos
+				</button>
+				<button
+					on:click=move |_| content_category.set(ContentCategory::Shorts)
+					class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
+				>
+					Shorts
+				</button>
+				<button
+					on:click=move |_| content_category.set(ContentCategory::Livestreams)
+					class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
+				>
+					Livestreams
+				</button>
+				<button
+					on:click=move |_| content_category.set(ContentCategory::Playlists)
+					class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
+				>
+					Playlists
+				</button>
+				<button
+					on:click=move |_| content_category.set(ContentCategory::Community)
+					class="btn btn-outline btn-sm rounded-lg font-normal normal-case"
+				>
+					{i18n("channel.community.title")}
+				</button>
+			</div>
+			<ChannelSearchBar/>
+		</div>
+	}
+}
+
+#[component]
+fn ChannelSearchBar() -> impl IntoView {
+	let content_category = expect_context::<RwSignal<ContentCategory>>();
+	let search_bar = create_node_ref::<Input>();
+
+	let search = move || {
+		let query = search_bar.get().unwrap().value();
+		if !query.trim().is_empty() {
+			content_category.set(ContentCategory::Search(query));
+		}
+	};
+
+	let check_for_enter_key = move |keyboard_event: KeyboardEvent| {
+		if keyboard_event.key_code() == 13 {
+			search();
+		}
+	};
+
+	view! {
+		<div class="join">
+			<input
+				on:keydown=check_for_enter_key
+				_ref=search_bar
+				type="text"
+				placeholder=i18n("channel.search")
+				class="input input-bordered input-sm w-64 join-item"
+			/>
+			<button class="btn btn-outline btn-sm join-item" on:click=move |_| search()>
+				<MagnifyingGlass weight=IconWeight::Regular class="h-4 w-4 base-content"/>
 			</button>
 		</div>
 	}
@@ -219,6 +275,8 @@ fn Content() -> impl IntoView {
 		ContentCategory::Shorts => view! { <Shorts/> },
 		ContentCategory::Livestreams => view! { <Livestreams/> },
 		ContentCategory::Playlists => view! { <Playlists/> },
+		ContentCategory::Community => view! { <Community/> },
+		ContentCategory::Search(query) => view! { <Search query=query/> },
 	}
 }
 
@@ -235,14 +293,15 @@ fn ContentContainer(children: Children) -> impl IntoView {
 fn Videos() -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 	let id_query: Memo<Option<String>> = create_query_signal("id").0;
 
 	let videos = create_resource(
 		move || {
 			(server.get(), id_query.get().unwrap_or_default(), locale.get().to_invidious_lang())
 		},
-		|(server, id, lang)| async move {
-			Channel::fetch_channel_videos(&server, &id, None, &lang).await
+		move |(server, id, lang)| async move {
+			Channel::fetch_channel_videos(&transport.get(), &server, &id, None, &lang).await
 		},
 	);
 
@@ -302,6 +361,7 @@ fn VideosInner(videos: ChannelVideos) -> impl IntoView {
 
 async fn fetch_more_videos(args: VideosFetchArgs) -> Result<(), RustyTubeError> {
 	let mut channel_videos = Channel::fetch_channel_videos(
+		&args.transport.get(),
 		&args.server.get(),
 		&args.id.get(),
 		args.continuation.get().as_deref(),
@@ -317,14 +377,15 @@ async fn fetch_more_videos(args: VideosFetchArgs) -> Result<(), RustyTubeError>
 fn Shorts() -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 	let id_query: Memo<Option<String>> = create_query_signal("id").0;
 
 	let shorts = create_resource(
 		move || {
 			(server.get(), id_query.get().unwrap_or_default(), locale.get().to_invidious_lang())
 		},
-		|(server, id, lang)| async move {
-			Channel::fetch_channel_shorts(&server, &id, None, &lang).await
+		move |(server, id, lang)| async move {
+			Channel::fetch_channel_shorts(&transport.get(), &server, &id, None, &lang).await
 		},
 	);
 
@@ -388,6 +449,7 @@ fn ShortsInner(shorts: ChannelShorts) -> impl IntoView {
 
 async fn fetch_more_shorts(args: ShortsFetchArgs) -> Result<(), RustyTubeError> {
 	let mut channel_shorts = Channel::fetch_channel_shorts(
+		&args.transport.get(),
 		&args.server.get(),
 		&args.id.get(),
 		args.continuation.get().as_deref(),
@@ -403,14 +465,15 @@ async fn fetch_more_shorts(args: ShortsFetchArgs) -> Result<(), RustyTubeError>
 fn Livestreams() -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 	let id_query: Memo<Option<String>> = create_query_signal("id").0;
 
 	let livestreams = create_resource(
 		move || {
 			(server.get(), id_query.get().unwrap_or_default(), locale.get().to_invidious_lang())
 		},
-		|(server, id, lang)| async move {
-			Channel::fetch_channel_livestreams(&server, &id, None, &lang).await
+		move |(server, id, lang)| async move {
+			Channel::fetch_channel_livestreams(&transport.get(), &server, &id, None, &lang).await
 		},
 	);
 
@@ -483,6 +546,7 @@ fn LivestreamsInner(livestreams: ChannelLivestreams) -> impl IntoView {
 
 async fn fetch_more_livestreams(args: LivestreamsFetchArgs) -> Result<(), RustyTubeError> {
 	let mut channel_livestreams = Channel::fetch_channel_livestreams(
+		&args.transport.get(),
 		&args.server.get(),
 		&args.id.get(),
 		args.continuation.get().as_deref(),
@@ -498,14 +562,15 @@ async fn fetch_more_livestreams(args: LivestreamsFetchArgs) -> Result<(), RustyT
 fn Playlists() -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 	let id_query: Memo<Option<String>> = create_query_signal("id").0;
 
 	let playlists = create_resource(
 		move || {
 			(server.get(), id_query.get().unwrap_or_default(), locale.get().to_invidious_lang())
 		},
-		|(server, id, lang)| async move {
-			Channel::fetch_channel_playlists(&server, &id, None, &lang).await
+		move |(server, id, lang)| async move {
+			Channel::fetch_channel_playlists(&transport.get(), &server, &id, None, &lang).await
 		},
 	);
 
@@ -576,6 +641,7 @@ fn PlaylistsInner(playlists: ChannelPlaylists) -> impl IntoView {
 
 async fn fetch_more_playlists(args: PlaylistsFetchArgs) -> Result<(), RustyTubeError> {
 	let mut channel_playlists = Channel::fetch_channel_playlists(
+		&args.transport.get(),
 		&args.server.get(),
 		&args.id.get(),
 		args.continuation.get().as_deref(),
@@ -587,6 +653,372 @@ async fn fetch_more_playlists(args: PlaylistsFetchArgs) -> Result<(), RustyTubeE
 	Ok(())
 }
 
+#[component]
+fn Community() -> impl IntoView {
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
+	let id_query: Memo<Option<String>> = create_query_signal("id").0;
+
+	let posts = create_resource(
+		move || {
+			(server.get(), id_query.get().unwrap_or_default(), locale.get().to_invidious_lang())
+		},
+		move |(server, id, lang)| async move {
+			Channel::fetch_channel_community(&transport.get(), &server, &id, None, &lang).await
+		},
+	);
+
+	view! {
+		<Suspense fallback=move || {
+			view! { <PlaceholderCardArray/> }
+		}>
+			{move || {
+				posts
+					.get()
+					.map(|posts_result| match posts_result {
+						Ok(posts) => view! { <CommunityInner posts=posts/> },
+						Err(err) => view! { <FerrisError error=err/> },
+					})
+			}}
+
+		</Suspense>
+	}
+}
+
+#[component]
+fn CommunityInner(posts: CommunityPosts) -> impl IntoView {
+	let lang = StoredValue::new(
+		expect_context::<RegionConfigCtx>().locale_slice.0.get().to_invidious_lang(),
+	);
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let id = RwSignal::new(create_query_signal::<String>("id").0.get().unwrap_or_default());
+	let continuation = RwSignal::new(posts.continuation);
+	let posts_vec = RwSignal::new(posts.posts);
+	let fetch_more_posts = create_action(|args: &PostsFetchArgs| fetch_more_posts(*args));
+
+	let posts_fetch_args =
+		PostsFetchArgs::new(posts_vec, server, lang, id, continuation, fetch_more_posts);
+
+	let posts_view =
+		move || posts_vec.get().into_iter().map(|post| view! { <Post post=post/> }).collect_view();
+
+	let load_more_posts = move |_| fetch_more_posts.dispatch(posts_fetch_args);
+	let fetch_more_btn = move || match continuation.get() {
+		None => ().into_view(),
+		Some(_) => view! {
+			<button class="btn btn-primary btn-outline btn-sm" on:click=load_more_posts>
+				{i18n("general.load_more")}
+			</button>
+		}
+		.into_view(),
+	};
+
+	view! {
+		<div class="pb-12">
+			<div class="flex flex-col gap-y-8 pb-12 max-w-3xl">{posts_view}</div>
+			{fetch_more_btn}
+		</div>
+	}
+}
+
+async fn fetch_more_posts(args: PostsFetchArgs) -> Result<(), RustyTubeError> {
+	let mut community = Channel::fetch_channel_community(
+		&args.transport.get(),
+		&args.server.get(),
+		&args.id.get(),
+		args.continuation.get().as_deref(),
+		&args.lang.get_value(),
+	)
+	.await?;
+	args.posts_vec.update(|posts| posts.append(&mut community.posts));
+	args.continuation.set(community.continuation);
+	Ok(())
+}
+
+#[component]
+fn Post(post: CommunityPost) -> impl IntoView {
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+
+	let author_thumb_url = post.author_thumbnails.first().map(|thumb| thumb.url.clone());
+	let likes = move || post.likes.to_formatted_string(&locale.get().to_num_fmt());
+	let edited = post.edited.then(|| i18n("channel.community.edited"));
+
+	let attachment = match post.attachment {
+		Some(PostAttachment::Video(video)) => view! { <PostVideoAttachment video/> },
+		Some(PostAttachment::Image { thumbnails }) => view! { <PostImage images=thumbnails/> },
+		Some(PostAttachment::MultiImage { images }) => view! { <PostImages images/> },
+		Some(PostAttachment::Poll { total_votes, choices }) => {
+			view! { <PostPoll total_votes choices/> }
+		}
+		Some(PostAttachment::Playlist(playlist)) => {
+			view! { <PostPlaylistAttachment playlist/> }
+		}
+		Some(PostAttachment::Unknown) | None => ().into_view(),
+	};
+
+	view! {
+		<div class="flex flex-row w-full items-start space-x-4 p-4 rounded-xl border border-neutral">
+			<img src=author_thumb_url class="w-12 h-12 rounded-full bg-neutral"/>
+			<div class="flex flex-col w-full text-sm">
+				<div class="flex flex-row gap-1">
+					<p class="font-semibold">{post.author}</p>
+					<p>{"•"}</p>
+					<p>{post.published_text}</p>
+					<p>{edited}</p>
+				</div>
+				<div class="mt-1" inner_html=post.content_html></div>
+				<div class="mt-3">{attachment}</div>
+				<div class="mt-3 flex flex-row gap-1 items-center">
+					<ThumbsUp weight=IconWeight::Regular class="h-4 w-4 base-content"/>
+					<p>{likes}</p>
+					<p>{"•"}</p>
+					<Chat weight=IconWeight::Regular class="h-4 w-4 base-content"/>
+					<p>{post.replies}</p>
+				</div>
+			</div>
+		</div>
+	}
+}
+
+/// Picks the largest size of an image, community posts list them smallest first.
+fn largest_image(images: &[CommonImage]) -> Option<String> {
+	images.iter().max_by_key(|image| image.width).map(|image| image.url.clone())
+}
+
+#[component]
+fn PostImage(images: Vec<CommonImage>) -> impl IntoView {
+	let src = largest_image(&images);
+
+	view! { <img decoding="async" src=src class="w-full max-h-[32rem] object-contain rounded-xl bg-neutral"/> }
+}
+
+#[component]
+fn PostImages(images: Vec<Vec<CommonImage>>) -> impl IntoView {
+	let images_view = images
+		.iter()
+		.map(|image| {
+			let src = largest_image(image);
+			view! {
+				<img
+					decoding="async"
+					src=src
+					class="h-64 aspect-square object-cover rounded-xl bg-neutral snap-start"
+				/>
+			}
+		})
+		.collect_view();
+
+	view! { <div class="flex flex-row gap-x-2 overflow-x-auto snap-x">{images_view}</div> }
+}
+
+#[component]
+fn PostPoll(total_votes: u64, choices: Vec<PollChoice>) -> impl IntoView {
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+	let votes = move || {
+		let vote_count = total_votes.to_formatted_string(&locale.get().to_num_fmt());
+		t!("channel.community.votes", vote_count = vote_count, locale = &locale.get().id())
+			.to_string()
+	};
+
+	let choices_view = choices
+		.into_iter()
+		.map(|choice| {
+			let image = largest_image(&choice.image).map(|src| {
+				view! { <img src=src class="h-10 w-10 rounded-lg object-cover"/> }
+			});
+			view! {
+				<div class="flex flex-row items-center gap-x-3 px-4 py-2 rounded-lg border border-neutral">
+					{image}
+					<p>{choice.text}</p>
+				</div>
+			}
+		})
+		.collect_view();
+
+	view! {
+		<div class="flex flex-col gap-y-2">
+			{choices_view}
+			<p class="text-xs">{votes}</p>
+		</div>
+	}
+}
+
+#[component]
+fn PostVideoAttachment(video: PostVideo) -> impl IntoView {
+	let src = video
+		.thumbnails
+		.iter()
+		.find(|thumb| thumb.quality == "medium")
+		.map(|thumb| thumb.url.clone());
+	let length = utils::unix_to_hours_secs_mins(video.length as f64);
+
+	let id = video.id;
+	let open_video = move |_| {
+		let navigate = leptos_router::use_navigate();
+		let id = id.clone();
+		request_animation_frame(move || {
+			_ = navigate(&format!("/player?id={}", id), Default::default());
+		})
+	};
+
+	view! {
+		<div
+			on:click=open_video
+			class="flex flex-row items-center space-x-4 p-2 rounded-lg cursor-pointer hover:bg-base-300"
+		>
+			<img decoding="async" src=src class="w-64 aspect-video object-cover rounded-lg bg-neutral"/>
+			<div class="flex flex-col">
+				<p class="font-semibold line-clamp-2">{video.title}</p>
+				<div class="flex flex-row flex-wrap mt-2 space-x-1">
+					<p>{video.author}</p>
+					<p>{"•"}</p>
+					<p>{video.views_text}</p>
+					<p>{"•"}</p>
+					<p>{length}</p>
+				</div>
+			</div>
+		</div>
+	}
+}
+
+/// Shown without a link, as there is no playlist page to open yet.
+#[component]
+fn PostPlaylistAttachment(playlist: PostPlaylist) -> impl IntoView {
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+	let videos = move || {
+		let video_count = playlist.video_count.to_formatted_string(&locale.get().to_num_fmt());
+		t!("playlist.videos", video_count = video_count, locale = &locale.get().id()).to_string()
+	};
+
+	view! {
+		<div class="flex flex-row items-center space-x-4 p-2 rounded-lg">
+			<img
+				decoding="async"
+				src=playlist.thumbnail
+				class="w-64 aspect-video object-cover rounded-lg bg-neutral"
+			/>
+			<div class="flex flex-col">
+				<p class="font-semibold line-clamp-2">{playlist.title}</p>
+				<div class="flex flex-row flex-wrap mt-2 space-x-1">
+					<p>{playlist.author}</p>
+					<p>{"•"}</p>
+					<p>{videos}</p>
+				</div>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+fn Search(query: String) -> impl IntoView {
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
+	let id_query: Memo<Option<String>> = create_query_signal("id").0;
+	let query = StoredValue::new(query);
+
+	let results = create_resource(
+		move || {
+			(server.get(), id_query.get().unwrap_or_default(), locale.get().to_invidious_lang())
+		},
+		move |(server, id, lang)| async move {
+			ChannelSearch::search(&transport.get(), &server, &id, &query.get_value(), 1, &lang)
+				.await
+		},
+	);
+
+	view! {
+		<Suspense fallback=move || {
+			view! { <PlaceholderCardArray/> }
+		}>
+			{move || {
+				results
+					.get()
+					.map(|results_result| match results_result {
+						Ok(results) => view! { <SearchInner results=results query=query/> },
+						Err(err) => view! { <FerrisError error=err/> },
+					})
+			}}
+
+		</Suspense>
+	}
+}
+
+#[component]
+fn SearchInner(results: ChannelSearch, query: StoredValue<String>) -> impl IntoView {
+	let lang = StoredValue::new(
+		expect_context::<RegionConfigCtx>().locale_slice.0.get().to_invidious_lang(),
+	);
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let id = RwSignal::new(create_query_signal::<String>("id").0.get().unwrap_or_default());
+	let has_more = RwSignal::new(!results.items.is_empty());
+	let page = RwSignal::new(1);
+	let results_vec = RwSignal::new(results.items);
+	let fetch_more_results =
+		create_action(|args: &SearchFetchArgs| fetch_more_search_results(*args));
+
+	let search_fetch_args = SearchFetchArgs::new(
+		results_vec,
+		server,
+		lang,
+		id,
+		query,
+		page,
+		has_more,
+		fetch_more_results,
+	);
+
+	let results_view = move || {
+		results_vec
+			.get()
+			.into_iter()
+			.map(|result| match result {
+				SearchResult::Video(video) => view! { <VideoPreviewCard video=video/> },
+				SearchResult::Playlist(playlist) => {
+					view! { <PlaylistPreviewCard playlist=playlist/> }
+				}
+				SearchResult::Channel(channel) => view! { <ChannelPreviewCard channel=channel/> },
+			})
+			.collect_view()
+	};
+
+	let load_more_results = move |_| fetch_more_results.dispatch(search_fetch_args);
+	let fetch_more_btn = move || match has_more.get() {
+		false => ().into_view(),
+		true => view! {
+			<button class="btn btn-primary btn-outline btn-sm" on:click=load_more_results>
+				{i18n("general.load_more")}
+			</button>
+		}
+		.into_view(),
+	};
+
+	view! {
+		<div class="pb-12">
+			<ContentContainer>{results_view}</ContentContainer>
+			{fetch_more_btn}
+		</div>
+	}
+}
+
+async fn fetch_more_search_results(args: SearchFetchArgs) -> Result<(), RustyTubeError> {
+	let next_page = args.page.get() + 1;
+	let mut search = ChannelSearch::search(
+		&args.transport.get(),
+		&args.server.get(),
+		&args.id.get(),
+		&args.query.get_value(),
+		next_page,
+		&args.lang.get_value(),
+	)
+	.await?;
+	args.has_more.set(!search.items.is_empty());
+	args.results_vec.update(|results| results.append(&mut search.items));
+	args.page.set(next_page);
+	Ok(())
+}
+
 #[component]
 fn ChannelSectionPlaceholder() -> impl IntoView {
 	view! {
@@ -621,6 +1053,7 @@ fn ChannelSectionPlaceholder() -> impl IntoView {
 struct VideosFetchArgs {
 	pub videos_vec: RwSignal<Vec<CommonVideo>>,
 	pub server: Signal<String>,
+	pub transport: TransportCtx,
 	pub lang: StoredValue<String>,
 	pub id: RwSignal<String>,
 	pub continuation: RwSignal<Option<String>>,
@@ -636,7 +1069,15 @@ impl VideosFetchArgs {
 		continuation: RwSignal<Option<String>>,
 		fetch_more_videos: Action<Self, Result<(), RustyTubeError>>,
 	) -> Self {
-		Self { videos_vec, server, lang, id, continuation, fetch_more_videos }
+		Self {
+			videos_vec,
+			server,
+			transport: expect_context::<TransportCtx>(),
+			lang,
+			id,
+			continuation,
+			fetch_more_videos,
+		}
 	}
 }
 
@@ -644,6 +1085,7 @@ impl VideosFetchArgs {
 struct ShortsFetchArgs {
 	pub shorts_vec: RwSignal<Vec<CommonVideo>>,
 	pub server: Signal<String>,
+	pub transport: TransportCtx,
 	pub lang: StoredValue<String>,
 	pub id: RwSignal<String>,
 	pub continuation: RwSignal<Option<String>>,
@@ -659,7 +1101,15 @@ impl ShortsFetchArgs {
 		continuation: RwSignal<Option<String>>,
 		fetch_more_shorts: Action<Self, Result<(), RustyTubeError>>,
 	) -> Self {
-		Self { shorts_vec, server, lang, id, continuation, fetch_more_shorts }
+		Self {
+			shorts_vec,
+			server,
+			transport: expect_context::<TransportCtx>(),
+			lang,
+			id,
+			continuation,
+			fetch_more_shorts,
+		}
 	}
 }
 
@@ -667,6 +1117,7 @@ impl ShortsFetchArgs {
 struct LivestreamsFetchArgs {
 	pub livestreams_vec: RwSignal<Vec<CommonVideo>>,
 	pub server: Signal<String>,
+	pub transport: TransportCtx,
 	pub lang: StoredValue<String>,
 	pub id: RwSignal<String>,
 	pub continuation: RwSignal<Option<String>>,
@@ -682,7 +1133,15 @@ impl LivestreamsFetchArgs {
 		continuation: RwSignal<Option<String>>,
 		fetch_more_livestreams: Action<Self, Result<(), RustyTubeError>>,
 	) -> Self {
-		Self { livestreams_vec, server, lang, id, continuation, fetch_more_livestreams }
+		Self {
+			livestreams_vec,
+			server,
+			transport: expect_context::<TransportCtx>(),
+			lang,
+			id,
+			continuation,
+			fetch_more_livestreams,
+		}
 	}
 }
 
@@ -690,6 +1149,7 @@ impl LivestreamsFetchArgs {
 struct PlaylistsFetchArgs {
 	pub playlists_vec: RwSignal<Vec<CommonPlaylist>>,
 	pub server: Signal<String>,
+	pub transport: TransportCtx,
 	pub lang: StoredValue<String>,
 	pub id: RwSignal<String>,
 	pub continuation: RwSignal<Option<String>>,
@@ -705,6 +1165,84 @@ impl PlaylistsFetchArgs {
 		continuation: RwSignal<Option<String>>,
 		fetch_more_playlists: Action<Self, Result<(), RustyTubeError>>,
 	) -> Self {
-		Self { playlists_vec, server, lang, id, continuation, fetch_more_playlists }
+		Self {
+			playlists_vec,
+			server,
+			transport: expect_context::<TransportCtx>(),
+			lang,
+			id,
+			continuation,
+			fetch_more_playlists,
+		}
+	}
+}
+
+#[derive(Clone, Copy)]
+struct PostsFetchArgs {
+	pub posts_vec: RwSignal<Vec<CommunityPost>>,
+	pub server: Signal<String>,
+	pub transport: TransportCtx,
+	pub lang: StoredValue<String>,
+	pub id: RwSignal<String>,
+	pub continuation: RwSignal<Option<String>>,
+	pub fetch_more_posts: Action<Self, Result<(), RustyTubeError>>,
+}
+
+impl PostsFetchArgs {
+	fn new(
+		posts_vec: RwSignal<Vec<CommunityPost>>,
+		server: Signal<String>,
+		lang: StoredValue<String>,
+		id: RwSignal<String>,
+		continuation: RwSignal<Option<String>>,
+		fetch_more_posts: Action<Self, Result<(), RustyTubeError>>,
+	) -> Self {
+		Self {
+			posts_vec,
+			server,
+			transport: expect_context::<TransportCtx>(),
+			lang,
+			id,
+			continuation,
+			fetch_more_posts,
+		}
+	}
+}
+
+#[derive(Clone, Copy)]
+struct SearchFetchArgs {
+	pub results_vec: RwSignal<Vec<SearchResult>>,
+	pub server: Signal<String>,
+	pub transport: TransportCtx,
+	pub lang: StoredValue<String>,
+	pub id: RwSignal<String>,
+	pub query: StoredValue<String>,
+	pub page: RwSignal<u32>,
+	pub has_more: RwSignal<bool>,
+	pub fetch_more_results: Action<Self, Result<(), RustyTubeError>>,
+}
+
+impl SearchFetchArgs {
+	fn new(
+		results_vec: RwSignal<Vec<SearchResult>>,
+		server: Signal<String>,
+		lang: StoredValue<String>,
+		id: RwSignal<String>,
+		query: StoredValue<String>,
+		page: RwSignal<u32>,
+		has_more: RwSignal<bool>,
+		fetch_more_results: Action<Self, Result<(), RustyTubeError>>,
+	) -> Self {
+		Self {
+			results_vec,
+			server,
+			transport: expect_context::<TransportCtx>(),
+			lang,
+			id,
+			query,
+			page,
+			has_more,
+			fetch_more_results,
+		}
 	}
 }
@@ -0,0 +1,107 @@
+use invidious::{CommonVideo, Hashtag};
+use leptos::*;
+use locales::RustyTubeLocale;
+use rustytube_error::RustyTubeError;
+
+use crate::{
+	components::{FerrisError, PlaceholderCardArray, VideoPreviewCard},
+	contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx},
+	resources::HashtagResource,
+	utils::i18n,
+};
+
+#[component]
+pub fn HashtagPage() -> impl IntoView {
+	let hashtag = HashtagResource::initialise();
+
+	view! {
+		<div class="w-full flex justify-center mt-4">
+			<div class="w-[90%] flex flex-col gap-y-8">
+				<Suspense fallback=move || {
+					view! { <PlaceholderCardArray/> }
+				}>
+					{move || {
+						hashtag
+							.resource
+							.get()
+							.map(|hashtag_res| match hashtag_res {
+								Ok(hashtag) => view! { <HashtagVideos hashtag=hashtag/> },
+								Err(err) => view! { <FerrisError error=err/> },
+							})
+					}}
+
+				</Suspense>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+pub fn HashtagVideos(hashtag: Hashtag) -> impl IntoView {
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+
+	let title = format!("#{}", hashtag.tag);
+	let has_more = RwSignal::new(!hashtag.videos.is_empty());
+	let videos = RwSignal::new(hashtag.videos.clone());
+	let last_page = StoredValue::new(hashtag);
+
+	let fetch_more_videos =
+		create_action(|args: &HashtagFetchArgs| fetch_more_hashtag_videos(*args));
+	let fetch_args = HashtagFetchArgs { last_page, videos, has_more, server, transport, locale };
+
+	let videos_view = move || {
+		videos
+			.get()
+			.into_iter()
+			.map(|video| view! { <VideoPreviewCard video=video/> })
+			.collect_view()
+	};
+
+	let load_more_videos = move |_| fetch_more_videos.dispatch(fetch_args);
+	let fetch_more_btn = move || match has_more.get() {
+		false => ().into_view(),
+		true => view! {
+			<button
+				class="btn btn-primary btn-outline btn-sm"
+				disabled=move || fetch_more_videos.pending().get()
+				on:click=load_more_videos
+			>
+				{i18n("general.load_more")}
+			</button>
+		}
+		.into_view(),
+	};
+
+	view! {
+		<h1 class="font-semibold text-2xl">{title}</h1>
+		<div class="-ml-4 flex flex-row flex-wrap gap-y-12 h-[calc(100vh-11.75rem)] pb-12 overflow-y-hidden hover:overflow-y-auto scroll-smooth">
+			{videos_view}
+			{fetch_more_btn}
+		</div>
+	}
+}
+
+#[derive(Clone, Copy)]
+struct HashtagFetchArgs {
+	last_page: StoredValue<Hashtag>,
+	videos: RwSignal<Vec<CommonVideo>>,
+	has_more: RwSignal<bool>,
+	server: Signal<String>,
+	transport: TransportCtx,
+	locale: Signal<RustyTubeLocale>,
+}
+
+async fn fetch_more_hashtag_videos(args: HashtagFetchArgs) -> Result<(), RustyTubeError> {
+	let lang = args.locale.get_untracked().to_invidious_lang();
+	let mut next_page = args
+		.last_page
+		.get_value()
+		.fetch_next_page(&args.transport.get(), &args.server.get_untracked(), &lang)
+		.await?;
+	args.has_more.set(!next_page.videos.is_empty());
+	args.videos.update(|videos| videos.append(&mut next_page.videos));
+	args.last_page.set_value(next_page);
+	Ok(())
+}
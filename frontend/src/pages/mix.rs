@@ -0,0 +1,73 @@
+use invidious::Mix;
+use leptos::*;
+use phosphor_leptos::{IconWeight, Play};
+
+use crate::{
+	components::{FerrisError, MixVideoCard, PlaceholderCardArray},
+	contexts::PlayQueueCtx,
+	resources::MixResource,
+	utils::i18n,
+};
+
+#[component]
+pub fn MixPage() -> impl IntoView {
+	let mix = MixResource::initialise();
+
+	view! {
+		<div class="w-full flex justify-center mt-4">
+			<div class="w-[90%] flex flex-col gap-y-8">
+				<Suspense fallback=move || {
+					view! { <PlaceholderCardArray/> }
+				}>
+					{move || {
+						mix.resource
+							.get()
+							.map(|mix_res| match mix_res {
+								Ok(mix) => view! { <MixContent mix=mix/> },
+								Err(err) => view! { <FerrisError error=err/> },
+							})
+					}}
+
+				</Suspense>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+pub fn MixContent(mix: Mix) -> impl IntoView {
+	let queue = expect_context::<PlayQueueCtx>();
+
+	let title = mix.title.clone();
+	let first_video = mix.videos.first().map(|video| video.id.clone());
+	let mix_clone = mix.clone();
+	let play_mix = move |_| {
+		queue.start(mix_clone.clone());
+		if let Some(id) = first_video.clone() {
+			let navigate = leptos_router::use_navigate();
+			request_animation_frame(move || {
+				_ = navigate(&format!("/player?id={}", id), Default::default());
+			})
+		}
+	};
+
+	let videos_view = mix
+		.videos
+		.clone()
+		.into_iter()
+		.map(|video| view! { <MixVideoCard video=video mix=mix.clone()/> })
+		.collect_view();
+
+	view! {
+		<div class="flex flex-row items-center justify-between">
+			<h1 class="font-semibold text-2xl">{title}</h1>
+			<button on:click=play_mix class="btn btn-primary">
+				<Play weight=IconWeight::Regular class="h-5 w-5"/>
+				{i18n("mix.play")}
+			</button>
+		</div>
+		<div class="flex flex-col space-y-2 h-[calc(100vh-14rem)] pb-12 overflow-y-hidden hover:overflow-y-auto scroll-smooth">
+			{videos_view}
+		</div>
+	}
+}
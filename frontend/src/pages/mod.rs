@@ -1,4 +1,6 @@
 mod channel;
+mod hashtag;
+mod mix;
 mod popular;
 mod search;
 mod settings;
@@ -7,6 +9,8 @@ mod trending;
 mod video;
 
 pub use channel::ChannelPage;
+pub use hashtag::HashtagPage;
+pub use mix::MixPage;
 pub use popular::PopularSection;
 pub use search::SearchSection;
 pub use settings::SettingsPage;
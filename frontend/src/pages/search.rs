@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
 use invidious::{
-	Duration, Feature, ResponseType, SearchArgs, SearchResult, SearchResults, Sort, TimeSpan,
+	CountryCode, Duration, Feature, ResponseType, SearchArgs, SearchResult, SearchResults, Sort,
+	TimeSpan,
 };
-use leptos::*;
-use leptos_router::{use_query_map, ParamsMap};
+use leptos::{html::Div, *};
+use leptos_router::use_location;
 use rustytube_error::RustyTubeError;
 
 use crate::{
@@ -12,31 +13,46 @@ use crate::{
 		ChannelPreviewCard, FerrisError, PlaceholderCardArray, PlaylistPreviewCard,
 		VideoPreviewCard,
 	},
-	contexts::{NetworkConfigCtx, RegionConfigCtx},
-	utils::{get_current_video_query_signal, VideoQuerySignal},
+	contexts::{
+		toast, NetworkConfigCtx, RegionConfigCtx, SearchCacheCtx, SearchPages, Toast,
+		ToastDuration, ToastType, TransportCtx,
+	},
+	utils::i18n,
 };
 
 #[component]
 pub fn SearchSection() -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 
-	let query_map = use_query_map();
-	let search_args = move || get_search_args_from_query_map(query_map.get());
+	let search = use_location().search;
+	let search_args = create_memo(move |_| search.with(|search| SearchArgs::from_str(search).ok()));
+	let search_cache = expect_context::<SearchCacheCtx>();
 	let search_results_resource = create_resource(
-		move || (server.get(), search_args(), locale.get().to_invidious_lang()),
-		|(server, search_args, lang)| async move {
-			SearchResults::fetch_search_results(&server, search_args, 1, &lang).await
+		move || (server.get(), search.get(), locale.get().to_invidious_lang()),
+		move |key| async move {
+			if let Some(pages) = search_cache.get(&key) {
+				return Ok(pages);
+			}
+			let (server, search, lang) = &key;
+			let search_args = SearchArgs::from_str(search)?;
+			let results =
+				SearchResults::fetch_search_results(&transport.get(), server, &search_args, lang)
+					.await?;
+			let pages = SearchPages::new(key, search_args, results);
+			search_cache.store(pages.clone());
+			Ok(pages)
 		},
 	);
 
-	let title = move || search_args().query;
+	let title = move || search_args.get().map(|search_args| search_args.query);
 
 	view! {
 		<div class="w-full flex justify-center mt-4">
 			<div class="w-[90%] flex flex-col gap-y-8">
 				<h1 class="pl-4 font-semibold text-2xl">{title}</h1>
-				// <TrendingHeader category=category/>
+				<SearchFilters search_args=search_args/>
 				<Suspense fallback=move || {
 					view! { <PlaceholderCardArray/> }
 				}>
@@ -45,14 +61,7 @@ pub fn SearchSection() -> impl IntoView {
 							.get()
 							.map(|search_results_response| {
 								match search_results_response {
-									Ok(search_results) => {
-										view! {
-											<SearchResults
-												search_results=search_results
-												search_args=search_args()
-											/>
-										}
-									}
+									Ok(pages) => view! { <SearchResults pages=pages/> },
 									Err(err) => view! { <FerrisError error=err/> },
 								}
 							})
@@ -65,100 +74,339 @@ pub fn SearchSection() -> impl IntoView {
 }
 
 #[component]
-pub fn SearchResults(search_results: SearchResults, search_args: SearchArgs) -> impl IntoView {
-	let lang = expect_context::<RegionConfigCtx>().locale_slice.0.get().to_invidious_lang();
-	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+pub fn SearchFilters(search_args: Memo<Option<SearchArgs>>) -> impl IntoView {
+	let args = move || search_args.get().unwrap_or_default();
 
-	let results_vec = create_rw_signal(vec![search_results.items]);
-	let fetch_search_results = create_action(|input: &SearchResultFetchArgs| {
-		let args = input.clone();
-		async move { fetch_search_results(args).await }
-	});
-	let video_id = get_current_video_query_signal();
-
-	let search_results_fetch_args = SearchResultFetchArgs {
-		search_args,
-		results_vec,
-		video_id,
-		server,
-		fetch_search_results,
-		lang,
+	let sort_options = move || {
+		[Sort::Relevance, Sort::Rating, Sort::Date, Sort::Views]
+			.into_iter()
+			.map(|sort| (sort_label(sort), SearchArgs { sort: Some(sort), page: None, ..args() }))
+			.collect::<Vec<FilterOption>>()
+	};
+	let sort_text = move || sort_label(args().sort.unwrap_or(Sort::Relevance));
+
+	let timespan_options = move || {
+		let any = (i18n("search.any_date")(), SearchArgs { timespan: None, page: None, ..args() });
+		let timespans =
+			[TimeSpan::Hour, TimeSpan::Day, TimeSpan::Week, TimeSpan::Month, TimeSpan::Year]
+				.into_iter()
+				.map(|timespan| {
+					let args = SearchArgs { timespan: Some(timespan), page: None, ..args() };
+					(timespan_label(timespan), args)
+				});
+		std::iter::once(any).chain(timespans).collect::<Vec<FilterOption>>()
+	};
+	let timespan_text = move || match args().timespan {
+		Some(timespan) => timespan_label(timespan),
+		None => i18n("search.any_date")(),
+	};
+
+	let duration_options = move || {
+		let any =
+			(i18n("search.any_duration")(), SearchArgs { duration: None, page: None, ..args() });
+		let durations =
+			[Duration::Short, Duration::Medium, Duration::Long].into_iter().map(|duration| {
+				let args = SearchArgs { duration: Some(duration), page: None, ..args() };
+				(duration_label(duration), args)
+			});
+		std::iter::once(any).chain(durations).collect::<Vec<FilterOption>>()
+	};
+	let duration_text = move || match args().duration {
+		Some(duration) => duration_label(duration),
+		None => i18n("search.any_duration")(),
+	};
+
+	let type_options = move || {
+		[
+			ResponseType::All,
+			ResponseType::Video,
+			ResponseType::Channel,
+			ResponseType::Playlist,
+			ResponseType::Movie,
+			ResponseType::Show,
+		]
+		.into_iter()
+		.map(|response_type| {
+			let args = SearchArgs { response_type: Some(response_type), page: None, ..args() };
+			(response_type_label(response_type), args)
+		})
+		.collect::<Vec<FilterOption>>()
+	};
+	let type_text = move || response_type_label(args().response_type.unwrap_or_default());
+
+	let region_options = move || {
+		let any = (i18n("search.any_region")(), SearchArgs { region: None, page: None, ..args() });
+		let regions = isocountry::CountryCode::iter().filter_map(|country| {
+			let region = CountryCode::from_str(country.alpha2()).ok()?;
+			Some((
+				country.name().to_string(),
+				SearchArgs { region: Some(region), page: None, ..args() },
+			))
+		});
+		std::iter::once(any).chain(regions).collect::<Vec<FilterOption>>()
+	};
+	let region_text = move || {
+		args()
+			.region
+			.and_then(|region| isocountry::CountryCode::for_alpha2(&region.to_string()).ok())
+			.map(|country| country.name().to_string())
+			.unwrap_or_else(|| i18n("search.any_region")())
 	};
 
-	let fetch_results = move |_| fetch_search_results.dispatch(search_results_fetch_args.clone());
+	let features_view = move || {
+		[
+			Feature::HighDefinition,
+			Feature::_4K,
+			Feature::HighDynamicRange,
+			Feature::Subtitles,
+			Feature::CreativeCommons,
+			Feature::Live,
+			Feature::_360Degrees,
+			Feature::VirtualReality180,
+			Feature::_3D,
+			Feature::Location,
+			Feature::Purchased,
+		]
+		.into_iter()
+		.map(|feature| {
+			let enabled = args().features.unwrap_or_default().contains(&feature);
+			let mut features = args().features.unwrap_or_default();
+			match enabled {
+				true => features.retain(|enabled| enabled != &feature),
+				false => features.push(feature),
+			}
+			let toggled = SearchArgs { features: Some(features), page: None, ..args() };
+			let classes = match enabled {
+				true => "btn btn-xs btn-primary font-normal normal-case rounded-lg",
+				false => "btn btn-xs btn-outline font-normal normal-case rounded-lg",
+			};
 
-	let search_results_view = move || {
-		results_vec
+			view! {
+				<button on:click=move |_| open_search(&toggled) class=classes>
+					{feature_label(feature)}
+				</button>
+			}
+		})
+		.collect_view()
+	};
+
+	view! {
+		<div class="flex flex-col gap-y-3 pl-4">
+			<div class="flex flex-row flex-wrap gap-x-3">
+				<FilterDropdown label=Signal::derive(sort_text) options=Signal::derive(sort_options)/>
+				<FilterDropdown
+					label=Signal::derive(timespan_text)
+					options=Signal::derive(timespan_options)
+				/>
+				<FilterDropdown
+					label=Signal::derive(duration_text)
+					options=Signal::derive(duration_options)
+				/>
+				<FilterDropdown label=Signal::derive(type_text) options=Signal::derive(type_options)/>
+				<FilterDropdown
+					label=Signal::derive(region_text)
+					options=Signal::derive(region_options)
+				/>
+			</div>
+			<div class="flex flex-row flex-wrap gap-2">{features_view}</div>
+		</div>
+	}
+}
+
+/// A filter's label and the search it leads to.
+type FilterOption = (String, SearchArgs);
+
+#[component]
+fn FilterDropdown(label: Signal<String>, options: Signal<Vec<FilterOption>>) -> impl IntoView {
+	let options_view = move || {
+		options
 			.get()
 			.into_iter()
-			.map(|result_page| {
-				result_page
-					.into_iter()
-					.map(|result| match result {
-						SearchResult::Channel(channel) => {
-							view! { <ChannelPreviewCard channel=channel/> }
-						}
-						SearchResult::Video(video) => view! { <VideoPreviewCard video=video/> },
-						SearchResult::Playlist(playlist) => {
-							view! { <PlaylistPreviewCard playlist=playlist/> }
-						}
-					})
-					.collect_view()
+			.map(|(label, search_args)| {
+				view! {
+					<li>
+						<a
+							class="btn btn-sm btn-ghost h-fit btn-block justify-start text-left font-normal normal-case"
+							on:click=move |_| open_search(&search_args)
+						>
+							{label}
+						</a>
+					</li>
+				}
 			})
 			.collect_view()
 	};
 
 	view! {
-		<div class="flex flex-row flex-wrap gap-y-12 h-[calc(100vh-64px-4rem-128px)] pb-12 overflow-y-hidden hover:overflow-y-auto scroll-smooth">
-			{search_results_view}
-			<button class="btn btn-primary btn-outline btn-sm" on:click=fetch_results>
-				{t!("general.load_more")}
-			</button>
+		<div class="dropdown dropdown-bottom">
+			<div
+				tabindex="0"
+				role="button"
+				class="btn btn-sm btn-outline font-normal normal-case rounded-lg"
+			>
+				{label}
+			</div>
+			<ul
+				tabindex="0"
+				class="overflow-y-auto dropdown-content p-3 shadow bg-base-300 rounded-xl w-64 max-h-80 h-fit z-10"
+			>
+				{options_view}
+			</ul>
 		</div>
 	}
 }
 
-fn get_search_args_from_query_map(map: ParamsMap) -> SearchArgs {
-	let query = map.get("q").cloned().unwrap_or_default();
-	let response_type =
-		map.get("type").map(|response_type| ResponseType::from_str(response_type).ok()).flatten();
-	let sort = map.get("sort").map(|sort| Sort::from_str(sort).ok()).flatten();
-	let timespan = map.get("timespan").map(|timespan| TimeSpan::from_str(timespan).ok()).flatten();
-	let duration = map.get("duration").map(|duration| Duration::from_str(duration).ok()).flatten();
-	let features = map.get("features").map(|features| {
-		let mut features_vec = vec![];
-		features.split(',').for_each(|feature| {
-			if let Ok(feature) = Feature::from_str(feature) {
-				features_vec.push(feature)
-			}
-		});
-		features_vec
-	});
-	SearchArgs::new(query, sort, timespan, duration, response_type, features)
+/// Opens `search_args` in the search page, keeping the filters in the url so searches can be
+/// shared and survive reloads.
+fn open_search(search_args: &SearchArgs) {
+	let url = format!("/search{}", search_args.to_url());
+
+	let navigate = leptos_router::use_navigate();
+	request_animation_frame(move || {
+		_ = navigate(&url, Default::default());
+	})
 }
 
-async fn fetch_search_results(args: SearchResultFetchArgs) -> Result<(), RustyTubeError> {
-	let page_number = (args.results_vec.get().len() + 1) as u32;
-	let search_results = SearchResults::fetch_search_results(
-		&args.server.get(),
-		args.search_args,
-		page_number,
-		&args.lang,
-	)
-	.await
-	.unwrap();
-	let mut temp = args.results_vec.get();
-	temp.push(search_results.items);
-	args.results_vec.set(temp);
-	Ok(())
+fn sort_label(sort: Sort) -> String {
+	match sort {
+		Sort::Relevance => i18n("search.relevance")(),
+		Sort::Rating => i18n("search.rating")(),
+		Sort::Date => i18n("search.upload_date")(),
+		Sort::Views => i18n("search.views")(),
+	}
+}
+
+fn timespan_label(timespan: TimeSpan) -> String {
+	match timespan {
+		TimeSpan::Hour => i18n("search.hour")(),
+		TimeSpan::Day => i18n("search.day")(),
+		TimeSpan::Week => i18n("search.week")(),
+		TimeSpan::Month => i18n("search.month")(),
+		TimeSpan::Year => i18n("search.year")(),
+	}
+}
+
+fn duration_label(duration: Duration) -> String {
+	match duration {
+		Duration::Short => i18n("search.short")(),
+		Duration::Medium => i18n("search.medium")(),
+		Duration::Long => i18n("search.long")(),
+	}
+}
+
+fn response_type_label(response_type: ResponseType) -> String {
+	match response_type {
+		ResponseType::All => i18n("search.all")(),
+		ResponseType::Video => i18n("search.videos")(),
+		ResponseType::Channel => i18n("search.channels")(),
+		ResponseType::Playlist => i18n("search.playlists")(),
+		ResponseType::Movie => i18n("search.movies")(),
+		ResponseType::Show => i18n("search.shows")(),
+	}
+}
+
+fn feature_label(feature: Feature) -> String {
+	match feature {
+		Feature::HighDefinition => String::from("HD"),
+		Feature::_4K => String::from("4K"),
+		Feature::HighDynamicRange => String::from("HDR"),
+		Feature::Subtitles => i18n("search.subtitles")(),
+		Feature::CreativeCommons => String::from("Creative Commons"),
+		Feature::Live => i18n("search.live")(),
+		Feature::_360Degrees => String::from("360°"),
+		Feature::VirtualReality180 => String::from("VR180"),
+		Feature::_3D => String::from("3D"),
+		Feature::Location => i18n("search.location")(),
+		Feature::Purchased => i18n("search.purchased")(),
+	}
+}
+
+#[component]
+pub fn SearchResults(pages: SearchPages) -> impl IntoView {
+	let search_cache = expect_context::<SearchCacheCtx>();
+	let transport = expect_context::<TransportCtx>();
+
+	let pages = create_rw_signal(pages);
+	let fetch_more_results =
+		create_action(move |_: &()| fetch_more_search_results(pages, transport));
+	create_effect(move |_| pages.with(|pages| search_cache.store(pages.clone())));
+	let failed = move || fetch_more_results.value().with(|value| matches!(value, Some(Err(_))));
+	create_effect(move |_| {
+		if let Some(Err(err)) = fetch_more_results.value().get() {
+			toast(Toast::new(err.title, Some(ToastDuration::Long), Some(ToastType::Error)));
+		}
+	});
+
+	let results_ref = create_node_ref::<Div>();
+	results_ref.on_load(move |results| {
+		let scroll_top = search_cache.scroll_top.get_value();
+		request_animation_frame(move || results.set_scroll_top(scroll_top));
+	});
+	let on_scroll = move |_| {
+		let Some(results) = results_ref.get_untracked() else {
+			return;
+		};
+		search_cache.scroll_top.set_value(results.scroll_top());
+		let near_end =
+			results.scroll_top() + results.client_height() + LOAD_MARGIN >= results.scroll_height();
+		let has_more = !pages.with_untracked(|pages| pages.exhausted);
+		// After a failure, only the retry button fetches the page again.
+		let retry_pending = untrack(failed);
+		if near_end && has_more && !retry_pending && !fetch_more_results.pending().get_untracked() {
+			fetch_more_results.dispatch(());
+		}
+	};
+
+	// Keyed by id, so appending a page only mounts its new cards.
+	let results = move || pages.with(|pages| pages.results.items.clone());
+	let result_view = |result: SearchResult| match result {
+		SearchResult::Channel(channel) => view! { <ChannelPreviewCard channel=channel/> },
+		SearchResult::Video(video) => view! { <VideoPreviewCard video=video/> },
+		SearchResult::Playlist(playlist) => view! { <PlaylistPreviewCard playlist=playlist/> },
+	};
+
+	// Results that do not fill the page never scroll, so they can still be loaded by hand.
+	let load_more = move |_| fetch_more_results.dispatch(());
+	let load_more_btn = move || match pages.with(|pages| pages.exhausted) {
+		true => ().into_view(),
+		false => view! {
+			<button
+				class="btn btn-primary btn-outline btn-sm"
+				disabled=move || fetch_more_results.pending().get()
+				on:click=load_more
+			>
+				{move || match failed() {
+					true => i18n("general.retry")(),
+					false => i18n("general.load_more")(),
+				}}
+			</button>
+		}
+		.into_view(),
+	};
+
+	view! {
+		<div
+			node_ref=results_ref
+			on:scroll=on_scroll
+			class="flex flex-row flex-wrap gap-y-12 h-[calc(100vh-64px-4rem-128px)] pb-12 overflow-y-hidden hover:overflow-y-auto"
+		>
+			<For each=results key=|result| result.id().to_string() children=result_view/>
+			{load_more_btn}
+		</div>
+	}
 }
 
-#[derive(Clone)]
-pub struct SearchResultFetchArgs {
-	pub search_args: SearchArgs,
-	pub results_vec: RwSignal<Vec<Vec<SearchResult>>>,
-	pub server: Signal<String>,
-	pub lang: String,
-	pub video_id: VideoQuerySignal,
-	pub fetch_search_results: Action<Self, Result<(), RustyTubeError>>,
+/// How close to the end of the results, in pixels, the next page starts loading.
+const LOAD_MARGIN: i32 = 800;
+
+async fn fetch_more_search_results(
+	pages: RwSignal<SearchPages>,
+	transport: TransportCtx,
+) -> Result<(), RustyTubeError> {
+	let ((server, _, lang), search_args) =
+		pages.with_untracked(|pages| (pages.key.clone(), pages.args.with_page(pages.next_page)));
+	let page =
+		SearchResults::fetch_search_results(&transport.get(), &server, &search_args, &lang).await?;
+	pages.update(|pages| pages.push_page(page));
+	Ok(())
 }
@@ -1,21 +1,26 @@
 use std::borrow::Cow;
 
+use config::SegmentBehaviour;
 use gloo::{
 	file::Blob,
 	storage::{LocalStorage, Storage},
 };
-use invidious::{NewpipeSubscriptions, Subscriptions, SUBS_KEY};
+use invidious::{NewpipeSubscriptions, RankedInstance, Subscriptions, SUBS_KEY};
 use leptos::*;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
+use sponsorblock_rs::Category;
 use urlencoding::encode;
 use utils::get_element_by_id;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlDialogElement, HtmlInputElement, MouseEvent};
 
 use crate::{
-	contexts::{RegionConfigCtx, UiConfigCtx},
-	resources::{SubscriptionsCtx, SubscriptionsThumbnailsResource, SubscriptionsVideosResource},
+	contexts::{NetworkConfigCtx, RegionConfigCtx, SponsorBlockConfigCtx, UiConfigCtx},
+	resources::{
+		behaviour_name, category_name, create_instance_ranking, InstancesResource,
+		SubscriptionsCtx, SubscriptionsThumbnailsResource, SubscriptionsVideosResource,
+	},
 	themes::*,
 	utils::i18n,
 };
@@ -26,7 +31,9 @@ pub fn SettingsPage() -> impl IntoView {
 		<div class="flex flex-col w-full h-full items-center">
 			<div class="flex flex-col 2xl:w-[50vw] xl:w-[50vw] lg:w-[85vw] md:w-[90vw] sm:w-[95vw] my-[3vh] px-6 overscroll-contain overflow-visible overflow-y-auto gap-16">
 				<DataSettings/>
+				<InstanceSettings/>
 				<RegionSettings/>
+				<SponsorBlockSettings/>
 				<ThemeSettings/>
 			</div>
 		</div>
@@ -35,7 +42,74 @@ pub fn SettingsPage() -> impl IntoView {
 
 #[component]
 pub fn InstanceSettings() -> impl IntoView {
-	view! {}
+	let instances = expect_context::<InstancesResource>().resource;
+	let ranking = create_instance_ranking();
+
+	let auto_select = move |_| {
+		if let Some(Ok(instances)) = instances.get_untracked() {
+			ranking.dispatch(instances);
+		}
+	};
+	let auto_select_text = move || match ranking.pending().get() {
+		true => i18n("settings.probing")(),
+		false => i18n("settings.auto_select")(),
+	};
+
+	let ranked_view = move || {
+		ranking.value().get().map(|ranked| {
+			ranked
+				.into_iter()
+				.take(5)
+				.map(|instance| view! { <RankedInstanceItem instance=instance/> })
+				.collect_view()
+		})
+	};
+
+	view! {
+		<div class="flex flex-col">
+			<h1 class="font-sans text-3xl">{i18n("settings.instance")}</h1>
+			<div class="divider"></div>
+			<div class="form-control w-full">
+				<label class="cursor-pointer label">
+					<p class="font-mono text-2xl">{i18n("settings.best_instance")}</p>
+					<div class="flex flex-row justify-end gap-4">
+						<button
+							on:click=auto_select
+							disabled=move || ranking.pending().get()
+							class="btn btn-lg btn-primary"
+						>
+							{auto_select_text}
+						</button>
+					</div>
+				</label>
+				<div class="flex flex-col gap-2">{ranked_view}</div>
+				<div class="divider"></div>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+fn RankedInstanceItem(instance: RankedInstance) -> impl IntoView {
+	let server = expect_context::<NetworkConfigCtx>().server_slice;
+	let uri = StoredValue::new(instance.info.uri);
+
+	let classes = move || match server.0.get().eq_ignore_ascii_case(&uri.get_value()) {
+		true => "flex flex-row justify-between p-3 rounded-lg bg-primary text-primary-content",
+		false => "flex flex-row justify-between p-3 rounded-lg bg-base-200 cursor-pointer",
+	};
+	let latency = format!("{} ms", instance.latency.as_millis());
+	let score = format!("{:.0}%", instance.score * 100.0);
+
+	view! {
+		<div class=classes on:click=move |_| server.1.set(uri.get_value())>
+			<p class="font-mono">{instance.info.flag} " " {instance.name}</p>
+			<div class="flex flex-row gap-4 font-mono">
+				<p>{latency}</p>
+				<p>{score}</p>
+			</div>
+		</div>
+	}
 }
 
 #[component]
@@ -166,6 +240,103 @@ pub fn TrendingRegionDropdown() -> impl IntoView {
 	}
 }
 
+#[component]
+pub fn SponsorBlockSettings() -> impl IntoView {
+	let min_segment_length_slice =
+		expect_context::<SponsorBlockConfigCtx>().min_segment_length_slice;
+
+	let categories_view = Category::ALL
+		.into_iter()
+		.map(|category| {
+			view! {
+				<Setting title=category_name(category).into()>
+					<div class="flex flex-row justify-end gap-4">
+						<SegmentBehaviourDropdown category=category/>
+					</div>
+				</Setting>
+			}
+		})
+		.collect_view();
+
+	let set_min_segment_length = move |event| {
+		if let Ok(length) = event_target_value(&event).parse::<f64>() {
+			min_segment_length_slice.1.set(length.max(0f64));
+		}
+	};
+
+	view! {
+		<SettingsSection title=i18n("settings.sponsorblock.title")().into()>
+			<Setting title=i18n("settings.sponsorblock.hash_prefix_lookups")().into()>
+				<input
+					type="checkbox"
+					class="toggle toggle-primary"
+					prop:checked=move || hash_prefix_lookups_slice.0.get()
+					on:change=move |event| {
+						hash_prefix_lookups_slice.1.set(event_target_checked(&event))
+					}
+				/>
+			</Setting>
+			{categories_view}
+			<Setting title=i18n("settings.sponsorblock.min_segment_length")().into()>
+				<input
+					type="number"
+					min="0"
+					step="0.5"
+					class="input input-bordered w-32"
+					prop:value=move || min_segment_length_slice.0.get()
+					on:change=set_min_segment_length
+				/>
+			</Setting>
+		</SettingsSection>
+	}
+}
+
+#[component]
+pub fn SegmentBehaviourDropdown(category: Category) -> impl IntoView {
+	let categories_slice = expect_context::<SponsorBlockConfigCtx>().categories_slice;
+
+	let behaviours_view = [
+		SegmentBehaviour::AutoSkip,
+		SegmentBehaviour::ManualSkip,
+		SegmentBehaviour::Mute,
+		SegmentBehaviour::Ignore,
+	]
+	.into_iter()
+	.map(|behaviour| {
+		let set_behaviour = move |_| {
+			let mut categories = categories_slice.0.get_untracked();
+			categories.set(category, behaviour);
+			categories_slice.1.set(categories);
+		};
+
+		view! {
+			<li>
+				<a
+					class="btn btn-sm btn-ghost h-fit btn-block justify-start text-left"
+					on:click=set_behaviour
+				>
+					<p>{behaviour_name(behaviour)}</p>
+				</a>
+			</li>
+		}
+	})
+	.collect_view();
+
+	view! {
+		<div class="dropdown dropdown-end">
+			<div tabindex="0" role="button" class="btn btn-secondary m-1">
+				{move || behaviour_name(categories_slice.0.get().get(category))}
+			</div>
+			<ul
+				tabindex="0"
+				class="dropdown-content p-3 shadow bg-base-300 rounded-xl w-64 h-fit z-10"
+			>
+				{behaviours_view}
+			</ul>
+		</div>
+	}
+}
+
 #[component]
 pub fn DataSettings() -> impl IntoView {
 	view! {
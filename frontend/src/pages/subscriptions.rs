@@ -32,10 +32,12 @@ pub fn SubscriptionsSection() -> impl IntoView {
 #[component]
 pub fn SubscriptionsVideos() -> impl IntoView {
 	let subs_videos_resource = expect_context::<SubscriptionsVideosResource>().resource;
+	let progress = expect_context::<SubscriptionsVideosResource>().progress;
 
 	view! {
-		<Suspense fallback=move || {
-			view! { <PlaceholderCardArray/> }
+		<Suspense fallback=move || match progress.get().is_empty() {
+			true => view! { <PlaceholderCardArray/> },
+			false => view! { <SubscriptionsVideosInner subs_videos=progress.get()/> },
 		}>
 			{move || {
 				subs_videos_resource
@@ -1,4 +1,4 @@
-use invidious::Comment;
+use invidious::{link_hashtags, Comment};
 use leptos::*;
 use num_format::ToFormattedString;
 use phosphor_leptos::{Chat, IconWeight, ThumbsUp};
@@ -68,7 +68,7 @@ pub fn CommentsSection() -> impl IntoView {
 pub fn Comment(comment: Comment) -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 
-	let content = comment.content_html;
+	let content = link_hashtags(&comment.content_html);
 	let author = comment.author;
 	let author_thumb_url = comment.author_thumbnails.first().cloned().map(|thumb| thumb.url);
 	let published = comment.published_text;
@@ -93,7 +93,7 @@ pub fn Comment(comment: Comment) -> impl IntoView {
 						<p>{"•"}</p>
 						<p>{published}</p>
 					</div>
-					<div class="mt-1" inner_html=content></div>
+					<div class="mt-1 [&_a]:link [&_a]:link-info [&_a]:no-underline" inner_html=content></div>
 					<div class="mt-3 flex flex-row gap-1 items-center">
 						<ThumbsUp weight=IconWeight::Regular class="h-4 w-4 base-content"/>
 						<p>{likes}</p>
@@ -170,7 +170,10 @@ pub fn Reply(reply: Comment) -> impl IntoView {
 						<p>{"•"}</p>
 						<p>{reply.published}</p>
 					</div>
-					<div class="mt-1" inner_html=reply.content></div>
+					<div
+						class="mt-1 [&_a]:link [&_a]:link-info [&_a]:no-underline"
+						inner_html=link_hashtags(&reply.content)
+					></div>
 					<div class="mt-3 flex flex-row gap-1 items-center">
 						<ThumbsUp weight=IconWeight::Regular class="h-4 w-4 base-content"/>
 						<p>{likes}</p>
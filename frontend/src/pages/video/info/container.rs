@@ -1,16 +1,18 @@
-use invidious::{Dislikes, Formats, Video};
+use invidious::{link_hashtags, BrowserTransport, Chapters, Dislikes, Formats, Mix, Video};
 use leptos::*;
 use num_format::ToFormattedString;
 use phosphor_leptos::{
-	CalendarBlank, DownloadSimple, Eye, IconWeight, ShareNetwork, ThumbsDown, ThumbsUp,
+	CalendarBlank, DownloadSimple, Eye, IconWeight, Radio, ShareNetwork, ThumbsDown, ThumbsUp,
 };
 
 use crate::{
 	components::FerrisError,
-	contexts::{PlayerState, RegionConfigCtx},
+	contexts::{
+		toast, PlayQueueCtx, PlayerState, RegionConfigCtx, Toast, ToastDuration, ToastType,
+	},
 	pages::video::page::VideoResource,
-	resources::SubscriptionsCtx,
-	utils::get_current_video_query_signal,
+	resources::{create_radio_action, SubscriptionsCtx},
+	utils::{get_current_video_query_signal, i18n},
 };
 
 #[component]
@@ -44,9 +46,11 @@ pub fn VideoInfoContent(video: Video) -> impl IntoView {
 	let author_id = video.author_id.clone();
 	let sub_count_text = video.sub_count_text;
 	let author_thumb_url = video.author_thumbnails.first().cloned().map(|thumb| thumb.url);
-	let description = video.description_html;
+	let description = link_hashtags(&video.description_html);
+	let chapters = video.chapters();
 
 	let formats = Formats::from((video.adaptive_formats.clone(), video.format_streams.clone()));
+	let video_id = video.id.clone();
 
 	let img_loaded = create_rw_signal(false);
 	let image_classes = move || match img_loaded.get() {
@@ -56,7 +60,7 @@ pub fn VideoInfoContent(video: Video) -> impl IntoView {
 
 	let dislikes = create_resource(
 		move || video.id.clone(),
-		|id| async move { Dislikes::fetch_dislikes(&id).await },
+		|id| async move { Dislikes::fetch_dislikes(&BrowserTransport, &id).await },
 	);
 
 	let dislikes_view = move || {
@@ -112,6 +116,7 @@ pub fn VideoInfoContent(video: Video) -> impl IntoView {
 					</div>
 
 					<div class="collapse-content pl-0">
+						<VideoChapters chapters=chapters/>
 						<div
 							class="flex flex-col gap-y-4 [&_a]:link [&_a]:link-info [&_a]:no-underline"
 							inner_html=description
@@ -137,6 +142,7 @@ pub fn VideoInfoContent(video: Video) -> impl IntoView {
 						</div>
 					</div>
 					<div class="flex flex-row items-end justify-center space-x-2">
+						<RadioBtn video_id=video_id/>
 						<DownloadsDropdown formats=formats title=title.clone()/>
 						<ShareDropdown/>
 					</div>
@@ -146,6 +152,77 @@ pub fn VideoInfoContent(video: Video) -> impl IntoView {
 	}
 }
 
+#[component]
+pub fn VideoChapters(chapters: Chapters) -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+
+	let playing_chapters = chapters.clone();
+	let current = create_memo(move |_| {
+		let time = state.current_time.get();
+		playing_chapters.chapter_at(time).map(|chapter| chapter.start)
+	});
+	let chapters_view = chapters
+		.chapters
+		.clone()
+		.into_iter()
+		.map(|chapter| {
+			let start = chapter.start;
+			let active = move || (current.get() == Some(start)).to_string();
+
+			view! {
+				<li>
+					<button
+						on:click=move |_| {
+							let _ = state.seek(start);
+						}
+						data-active=active
+						class="btn btn-sm btn-ghost btn-block justify-start font-normal normal-case data-[active=true]:btn-active"
+					>
+						<span class="font-mono">{utils::unix_to_hours_secs_mins(start)}</span>
+						<span class="truncate">{chapter.title}</span>
+					</button>
+				</li>
+			}
+		})
+		.collect_view();
+
+	(!chapters.is_empty()).then(|| {
+		view! {
+			<div class="flex flex-col gap-y-2 mb-4">
+				<h2 class="font-semibold">{i18n("video.info.chapters")}</h2>
+				<ul class="flex flex-col max-h-64 overflow-y-auto">{chapters_view}</ul>
+			</div>
+		}
+	})
+}
+
+#[component]
+pub fn RadioBtn(video_id: String) -> impl IntoView {
+	let queue = expect_context::<PlayQueueCtx>();
+	let radio = create_radio_action();
+
+	let radio_id = Mix::radio_id(&video_id);
+	let active = move || queue.is_playing(&radio_id).to_string();
+
+	create_effect(move |_| {
+		if let Some(Err(err)) = radio.value().get() {
+			toast(Toast::new(err.title, Some(ToastDuration::Long), Some(ToastType::Error)));
+		}
+	});
+
+	view! {
+		<button
+			on:click=move |_| radio.dispatch(video_id.clone())
+			disabled=move || radio.pending().get()
+			data-active=active
+			title=i18n("mix.start_radio")
+			class="btn btn-circle btn-accent btn-outline data-[active=true]:btn-active"
+		>
+			<Radio weight=IconWeight::Regular class="h-6 w-6 base-content"/>
+		</button>
+	}
+}
+
 #[component]
 pub fn DownloadsDropdown(formats: Formats, title: String) -> impl IntoView {
 	view! {
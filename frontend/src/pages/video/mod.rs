@@ -1,7 +1,9 @@
 mod comments;
 mod info;
 mod page;
+mod queue;
 mod recommended;
+mod transcript;
 mod utils;
 mod video_player;
 
@@ -3,11 +3,11 @@ use leptos::*;
 use rustytube_error::RustyTubeError;
 
 use super::{
-	comments::CommentsSection, info::VideoInfo, recommended::RecommendedSection,
-	video_player::VideoContainer,
+	comments::CommentsSection, info::VideoInfo, queue::QueueSection,
+	recommended::RecommendedSection, transcript::TranscriptSection, video_player::VideoContainer,
 };
 use crate::{
-	contexts::{NetworkConfigCtx, PlayerState, PlayerStyle, RegionConfigCtx},
+	contexts::{NetworkConfigCtx, PlayerState, PlayerStyle, RegionConfigCtx, TransportCtx},
 	resources::SponsorBlockResource,
 	utils::get_current_video_query_signal,
 };
@@ -16,14 +16,17 @@ use crate::{
 pub fn VideoPage() -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
 	let id = get_current_video_query_signal().0;
 
 	expect_context::<SponsorBlockResource>().set_video(id);
+	provide_context(PlayerState::init());
+	provide_context(PlayerStyle::init());
 
 	let video_resource: VideoResource = create_resource(
 		move || (server.get(), id.get().unwrap_or_default(), locale.get().to_invidious_lang()),
-		|(server, id, lang)| async move {
-			let video = Video::fetch_video(&server, &id, &lang).await;
+		move |(server, id, lang)| async move {
+			let video = Video::fetch_video(&transport.get(), &server, &id, &lang).await;
 			video
 		},
 	);
@@ -39,7 +42,9 @@ pub fn VideoPage() -> impl IntoView {
 					<CommentsSection/>
 				</div>
 			</div>
-			<div class="flex flex-col basis-2/6">
+			<div class="flex flex-col basis-2/6 gap-y-4">
+				<QueueSection/>
+				<TranscriptSection video_resource=video_resource/>
 				<RecommendedSection video_resource=video_resource/>
 			</div>
 		</div>
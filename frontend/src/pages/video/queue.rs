@@ -0,0 +1,49 @@
+use invidious::Mix;
+use leptos::*;
+use phosphor_leptos::{IconWeight, X};
+
+use crate::{components::MixVideoCard, contexts::PlayQueueCtx, utils::i18n};
+
+#[component]
+pub fn QueueSection() -> impl IntoView {
+	let queue = expect_context::<PlayQueueCtx>();
+
+	move || queue.mix.get().map(|mix| view! { <QueueContent mix=mix/> })
+}
+
+#[component]
+pub fn QueueContent(mix: Mix) -> impl IntoView {
+	let queue = expect_context::<PlayQueueCtx>();
+
+	let mix_id = mix.id.clone();
+	let go_to_mix_page = move |_| {
+		let navigate = leptos_router::use_navigate();
+		let mix_id = mix_id.clone();
+		request_animation_frame(move || {
+			_ = navigate(&format!("/mix?list={}", mix_id), Default::default());
+		})
+	};
+	let videos_view = mix
+		.videos
+		.clone()
+		.into_iter()
+		.map(|video| view! { <MixVideoCard video=video mix=mix.clone()/> })
+		.collect_view();
+
+	view! {
+		<div class="flex flex-col rounded-lg bg-base-200 p-4 space-y-4">
+			<div class="flex flex-row items-center justify-between">
+				<div class="flex flex-col">
+					<h1 class="font-semibold text-xl">{i18n("mix.queue")}</h1>
+					<p on:click=go_to_mix_page class="cursor-pointer text-sm text-primary line-clamp-1">
+						{mix.title.clone()}
+					</p>
+				</div>
+				<button on:click=move |_| queue.stop() class="btn btn-circle btn-ghost btn-sm">
+					<X weight=IconWeight::Regular class="h-5 w-5"/>
+				</button>
+			</div>
+			<div class="flex flex-col space-y-2 max-h-96 overflow-y-auto">{videos_view}</div>
+		</div>
+	}
+}
@@ -0,0 +1,201 @@
+use invidious::{Caption, CaptionTrack, Cue};
+use leptos::*;
+use phosphor_leptos::{CaretDown, CaretUp, IconWeight};
+
+use crate::{
+	components::FerrisError,
+	contexts::{NetworkConfigCtx, PlayerState, RegionConfigCtx, TransportCtx},
+	pages::video::page::VideoResource,
+	utils::i18n,
+};
+
+#[component]
+pub fn TranscriptSection(video_resource: VideoResource) -> impl IntoView {
+	let open = RwSignal::new(false);
+
+	let transcript_view = move || {
+		video_resource.get().map(|video_result| match video_result {
+			Ok(video) => view! { <TranscriptContent captions=video.captions/> },
+			Err(err) => view! { <FerrisError error=err/> },
+		})
+	};
+	let caret = move || match open.get() {
+		true => view! { <CaretUp weight=IconWeight::Regular class="h-5 w-5"/> },
+		false => view! { <CaretDown weight=IconWeight::Regular class="h-5 w-5"/> },
+	};
+
+	view! {
+		<div class="flex flex-col rounded-lg bg-base-200 p-4 space-y-4">
+			<div
+				on:click=move |_| open.set(!open.get())
+				class="flex flex-row items-center justify-between cursor-pointer"
+			>
+				<h1 class="font-semibold text-xl">{i18n("video.transcript.title")}</h1>
+				{caret}
+			</div>
+			<Show when=move || open.get()>
+				<Suspense fallback=move || {
+					view! { <TranscriptPlaceholder/> }
+				}>{transcript_view}</Suspense>
+			</Show>
+		</div>
+	}
+}
+
+#[component]
+pub fn TranscriptContent(captions: Vec<Caption>) -> impl IntoView {
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
+
+	if captions.is_empty() {
+		return view! { <p class="text-sm">{i18n("video.transcript.no_captions")}</p> }.into_view();
+	}
+
+	let selected =
+		RwSignal::new(default_caption(&captions, &locale.get_untracked().to_invidious_lang()));
+	let captions = StoredValue::new(captions);
+	let query = RwSignal::new(String::new());
+
+	let track = create_resource(
+		move || (server.get(), selected.get()),
+		move |(server, selected)| async move {
+			let caption = captions.with_value(|captions| captions[selected].clone());
+			CaptionTrack::fetch_track(&transport.get(), &server, &caption, None).await
+		},
+	);
+
+	let track_view = move || {
+		track.get().map(|track_result| match track_result {
+			Ok(track) => view! { <TranscriptCues track=track query=query/> },
+			Err(err) => view! { <FerrisError error=err/> },
+		})
+	};
+
+	let captions_view = captions.with_value(|captions| {
+		captions
+			.iter()
+			.enumerate()
+			.map(|(index, caption)| {
+				view! {
+					<li>
+						<a
+							class="btn btn-sm btn-ghost h-fit btn-block justify-start text-left"
+							on:click=move |_| selected.set(index)
+						>
+							<p>{caption.label.clone()}</p>
+						</a>
+					</li>
+				}
+			})
+			.collect_view()
+	});
+	let selected_label =
+		move || captions.with_value(|captions| captions[selected.get()].label.clone());
+
+	view! {
+		<div class="flex flex-col space-y-4">
+			<div class="flex flex-row gap-2">
+				<div class="dropdown">
+					<div tabindex="0" role="button" class="btn btn-sm btn-outline font-normal">
+						{selected_label}
+					</div>
+					<ul
+						tabindex="0"
+						class="overflow-y-scroll dropdown-content p-3 shadow bg-base-300 rounded-xl w-64 max-h-80 h-fit z-10"
+					>
+						{captions_view}
+					</ul>
+				</div>
+				<input
+					on:input=move |event| query.set(event_target_value(&event))
+					prop:value=query
+					type="text"
+					placeholder=i18n("video.transcript.search")
+					class="input input-bordered input-sm w-full"
+				/>
+			</div>
+			<Suspense fallback=move || {
+				view! { <TranscriptPlaceholder/> }
+			}>{track_view}</Suspense>
+		</div>
+	}
+	.into_view()
+}
+
+#[component]
+pub fn TranscriptCues(track: CaptionTrack, query: RwSignal<String>) -> impl IntoView {
+	let cues = StoredValue::new(track.cues);
+
+	let cues_view = move || {
+		let query = query.get().to_lowercase();
+		let matching = cues.with_value(|cues| {
+			cues.iter()
+				.filter(|cue| query.is_empty() || cue.text().to_lowercase().contains(&query))
+				.cloned()
+				.collect::<Vec<Cue>>()
+		});
+
+		match matching.is_empty() {
+			true => {
+				view! { <p class="text-sm">{i18n("video.transcript.no_matches")}</p> }.into_view()
+			}
+			false => {
+				matching.into_iter().map(|cue| view! { <TranscriptCue cue=cue/> }).collect_view()
+			}
+		}
+	};
+
+	view! { <div class="flex flex-col space-y-1 max-h-96 overflow-y-auto">{cues_view}</div> }
+}
+
+#[component]
+pub fn TranscriptCue(cue: Cue) -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+
+	let start = cue.start;
+	let end = cue.end;
+	let is_current = move || {
+		let time = state.current_time.get();
+		start <= time && time < end
+	};
+	let seek = move |_| {
+		let _ = state.seek(start);
+	};
+
+	view! {
+		<div
+			on:click=seek
+			data-current=move || is_current().to_string()
+			class="flex flex-row gap-x-3 p-2 rounded-lg cursor-pointer hover:bg-base-300 data-[current=true]:bg-base-300 data-[current=true]:text-primary"
+		>
+			<p class="w-14 shrink-0 text-xs font-mono pt-0.5 text-primary">
+				{utils::unix_to_hours_secs_mins(start)}
+			</p>
+			<p class="text-sm">{cue.text()}</p>
+		</div>
+	}
+}
+
+#[component]
+pub fn TranscriptPlaceholder() -> impl IntoView {
+	view! {
+		<div class="flex flex-col space-y-3">
+			<div class="h-4 w-full rounded bg-neutral animate-pulse"></div>
+			<div class="h-4 w-5/6 rounded bg-neutral animate-pulse"></div>
+			<div class="h-4 w-4/6 rounded bg-neutral animate-pulse"></div>
+		</div>
+	}
+}
+
+/// Prefers captions written by a person in the user's language, then any in their language.
+fn default_caption(captions: &[Caption], lang: &str) -> usize {
+	let language = lang.split('-').next().unwrap_or(lang);
+	let in_language = |caption: &&Caption| caption.language.starts_with(language);
+
+	captions
+		.iter()
+		.position(|caption| in_language(&caption) && !caption.is_auto_generated())
+		.or(captions.iter().position(|caption| in_language(&caption)))
+		.unwrap_or_default()
+}
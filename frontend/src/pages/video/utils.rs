@@ -1,34 +1,44 @@
-use invidious::{AudioFormat, Container, DashFormat, Format, Formats, LegacyFormat, VideoFormat};
+use invidious::{AudioFormat, DashFormat, Format, Formats, LegacyFormat, VideoFormat};
 use leptos::*;
 use rustytube_error::RustyTubeError;
 
-use crate::{contexts::PlayerConfigCtx, utils::is_webkit};
+use crate::contexts::{MediaSourceEngine, PlayerConfigCtx};
 
+/// Prefers DASH, then falls back to the muxed legacy streams when the browser can't play the
+/// DASH formats through MSE, or has no MSE at all.
 pub fn get_format(formats: &Formats) -> Result<Format, RustyTubeError> {
-	let audio_format = find_audio_format(&formats)?;
+	let audio_format = find_audio_format(&formats);
 	let video_format = find_video_format(&formats);
 
-	let format = match video_format {
-		Ok(video_format) => Format::Dash(DashFormat::new(video_format, audio_format)),
-		Err(_) => match find_legacy_format(&formats) {
-			Ok(legacy_format) => Format::Legacy(legacy_format),
-			Err(_) => Format::Audio(audio_format),
+	match (video_format, audio_format) {
+		(Ok(video_format), Ok(audio_format)) => {
+			Ok(Format::Dash(DashFormat::new(video_format, audio_format)))
+		}
+		(_, audio_format) => match find_legacy_format(&formats) {
+			Ok(legacy_format) => Ok(Format::Legacy(legacy_format)),
+			Err(_) => audio_format.map(Format::Audio),
 		},
-	};
-	Ok(format)
+	}
 }
 
 pub fn find_video_format(formats: &Formats) -> Result<VideoFormat, RustyTubeError> {
 	let default_video_quality =
 		move || expect_context::<PlayerConfigCtx>().default_video_quality_slice.0.get();
 
+	let video_formats = formats
+		.video_formats
+		.iter()
+		.filter(|format| MediaSourceEngine::supports_type(&format.r#type))
+		.collect::<Vec<&VideoFormat>>();
+
 	let preferred_format =
-		formats.video_formats.iter().find(|x| x.quality_label == default_video_quality()).cloned();
+		video_formats.iter().find(|x| x.quality_label == default_video_quality()).cloned();
 
 	match preferred_format {
 		Some(_) => preferred_format,
-		None => formats.video_formats.first().cloned(),
+		None => video_formats.first().cloned(),
 	}
+	.cloned()
 	.ok_or(RustyTubeError::no_dash_video_format_available())
 }
 
@@ -40,10 +50,12 @@ pub fn find_audio_format(formats: &Formats) -> Result<AudioFormat, RustyTubeErro
 	let default_audio_quality =
 		move || expect_context::<PlayerConfigCtx>().default_audio_quality_slice.0.get();
 
-	let audio_formats = match is_webkit() {
-		true => filter_mp4_audio_formats(&formats.audio_formats),
-		false => formats.audio_formats.clone(),
-	};
+	let audio_formats = formats
+		.audio_formats
+		.iter()
+		.filter(|format| MediaSourceEngine::supports_type(&format.r#type))
+		.cloned()
+		.collect::<Vec<AudioFormat>>();
 
 	let preferred_format = audio_formats
 		.iter()
@@ -56,15 +68,3 @@ pub fn find_audio_format(formats: &Formats) -> Result<AudioFormat, RustyTubeErro
 	}
 	.ok_or(RustyTubeError::no_audio_format_available())
 }
-
-pub fn filter_mp4_audio_formats(formats: &Vec<AudioFormat>) -> Vec<AudioFormat> {
-	formats
-		.iter()
-		.filter_map(|format| {
-			let a = format.r#type.contains("mp4");
-			let b = format.clone().container.map(|container| (container.eq(&Container::M4A)));
-
-			(a && b.unwrap_or_default()).then(|| format.clone())
-		})
-		.collect::<Vec<AudioFormat>>()
-}
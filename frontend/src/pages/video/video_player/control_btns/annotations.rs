@@ -0,0 +1,21 @@
+use leptos::*;
+use phosphor_leptos::{ChatText, IconWeight};
+
+use crate::contexts::PlayerStyle;
+
+#[component]
+pub fn AnnotationsBtn() -> impl IntoView {
+	let style = expect_context::<PlayerStyle>();
+	let toggle_annotations =
+		move |_| style.annotations_visible.set(!style.annotations_visible.get());
+	let weight = move || match style.annotations_visible.get() {
+		true => IconWeight::Fill,
+		false => IconWeight::Regular,
+	};
+
+	view! {
+		<button on:click=toggle_annotations class="btn btn-ghost btn-xs">
+			<ChatText weight=weight class="h-4 w-4 base-content"/>
+		</button>
+	}
+}
@@ -50,6 +50,7 @@ pub fn FormatList() -> impl IntoView {
 				let quality_str = format.audio_quality.clone().to_string();
 
 				let change_format = move |_| {
+					state.auto_quality.set(false);
 					let _ = state.change_format(Format::Audio(format.clone()));
 				};
 
@@ -86,6 +87,7 @@ pub fn FormatList() -> impl IntoView {
 					});
 
 				let change_format = move |_| {
+					state.auto_quality.set(false);
 					let _ = state.change_format(Format::Dash(DashFormat::new(
 						format.clone(),
 						audio_format.clone().unwrap(),
@@ -110,6 +112,7 @@ pub fn FormatList() -> impl IntoView {
 				let quality_str = format.quality_label.clone().to_string();
 
 				let change_format = move |_| {
+					state.auto_quality.set(false);
 					let _ = state.change_format(Format::Legacy(format.clone()));
 				};
 
@@ -122,6 +125,15 @@ pub fn FormatList() -> impl IntoView {
 			.collect_view()
 	};
 
+	let auto_label = move || match (state.auto_quality.get(), state.format.get()) {
+		(true, Some(Format::Dash(dash))) => format!("Auto ({})", dash.video.quality_label),
+		_ => String::from("Auto"),
+	};
+	let auto_classes = move || match state.auto_quality.get() {
+		true => "btn btn-sm lowercase btn-ghost text-primary",
+		false => "btn btn-sm lowercase btn-ghost",
+	};
+
 	view! {
 		<div class="flex h-max w-max flex-row space-x-4 rounded-lg bg-base-200 p-2">
 			<div class="flex flex-col items-center">
@@ -136,7 +148,12 @@ pub fn FormatList() -> impl IntoView {
 
 			<div class="flex flex-col items-center">
 				<h1>Dash</h1>
-				<div class="my-4 flex flex-col h-64 overflow-y-scroll">{adaptive_formats_view}</div>
+				<div class="my-4 flex flex-col h-64 overflow-y-scroll">
+					<button on:click=move |_| state.auto_quality.set(true) class=auto_classes>
+						{auto_label}
+					</button>
+					{adaptive_formats_view}
+				</div>
 			</div>
 		</div>
 	}
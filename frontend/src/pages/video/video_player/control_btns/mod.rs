@@ -1,3 +1,4 @@
+mod annotations;
 mod captions;
 mod format;
 mod fullscreen;
@@ -7,6 +8,7 @@ mod theatre;
 mod time_info;
 mod volume;
 
+pub use annotations::AnnotationsBtn;
 pub use format::FormatDropdown;
 pub use fullscreen::FullScreenBtn;
 pub use pause::PauseBtn;
@@ -1,13 +1,17 @@
+use invidious::{Chapters, Storyboard, StoryboardTile};
 use leptos::*;
 use wasm_bindgen::JsCast;
 use web_sys::{DragEvent, HtmlProgressElement, MouseEvent};
 
-use crate::contexts::{PlayerState, PlayerStyle};
+use crate::contexts::{NetworkConfigCtx, PlayerState, PlayerStyle};
 
 #[component]
 pub fn ProgressBar() -> impl IntoView {
 	let state = expect_context::<PlayerState>();
 	let style = expect_context::<PlayerStyle>();
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let storyboard = expect_context::<RwSignal<Option<Storyboard>>>();
+	let chapters = expect_context::<RwSignal<Chapters>>();
 
 	let on_click = move |mouse_event| {
 		let _ = state.seek(seek_pos(mouse_event));
@@ -19,45 +23,88 @@ pub fn ProgressBar() -> impl IntoView {
 	let tip_styles = RwSignal::new("bottom: 48px; left: 0px;".to_string());
 	let tip_classes = RwSignal::new("hidden".to_string());
 	let tip_time = RwSignal::new("0:00".to_string());
+	let tip_preview: RwSignal<Option<StoryboardTile>> = RwSignal::new(None);
+	let tip_chapter: RwSignal<Option<String>> = RwSignal::new(None);
 
 	let open_tip = move |mouse_event: MouseEvent| {
-		tip_time.set(get_seek_pos_as_time_str(mouse_event.clone()));
+		let time = seek_pos(mouse_event.clone());
+		tip_time.set(utils::unix_to_hours_secs_mins(time));
+		tip_chapter.set(
+			chapters
+				.with(|chapters| chapters.chapter_at(time).map(|chapter| chapter.title.clone())),
+		);
+		tip_preview.set(
+			storyboard.get().and_then(|storyboard| {
+				storyboard.tile_at(&server.get(), time, state.duration.get())
+			}),
+		);
 		let styles = format!("bottom: 48px; left: {}px;", mouse_event.offset_x());
 		tip_styles.set(styles);
 		tip_classes.set(
-			"absolute p-2 rounded-lg z-100 bg-primary text-primary-content h-fit w-fit".to_string(),
+			"absolute flex flex-col items-center p-2 rounded-lg z-100 bg-primary \
+			 text-primary-content h-fit w-fit"
+				.to_string(),
 		);
 	};
+	let preview = move || {
+		tip_preview.get().map(|tile| {
+			let styles = format!(
+				"width: {}px; height: {}px; background-image: url('{}'); background-position: \
+				 -{}px -{}px;",
+				tile.width, tile.height, tile.sheet_url, tile.x, tile.y
+			);
+			view! { <div style=styles class="mb-1 rounded bg-no-repeat"></div> }
+		})
+	};
+	let chapter_title =
+		move || tip_chapter.get().map(|title| view! { <p class="font-semibold">{title}</p> });
+	let chapter_markers = move || {
+		let duration = state.duration.get();
+		chapters.with(|chapters| {
+			chapters
+				.chapters
+				.iter()
+				.skip(1)
+				.filter(|_| duration > 0f64)
+				.map(|chapter| {
+					let styles = format!("left: {}%;", chapter.start / duration * 100f64);
+					view! {
+						<div
+							style=styles
+							class="absolute inset-y-0 w-0.5 bg-base-300 pointer-events-none z-20"
+						></div>
+					}
+				})
+				.collect_view()
+		})
+	};
 	let close_tip = move |_| {
 		tip_classes.set("hidden".to_string());
 	};
 
 	view! {
 		<div style=tip_styles class=tip_classes>
-			{tip_time}
+			{preview}
+			{chapter_title}
+			<p>{tip_time}</p>
+		</div>
+		<div class="relative w-full flex">
+			<progress
+				on:mouseover=open_tip
+				on:mousemove=open_tip
+				on:mouseout=close_tip
+				on:click=on_click
+				on:dragend=on_drag
+				data-controlsvisible=style.controls_visible
+				max=state.duration.read_only()
+				value=state.current_time.read_only()
+				class=PROGRESS_BAR
+			></progress>
+			{chapter_markers}
 		</div>
-		<progress
-			on:mouseover=open_tip
-			on:mousemove=open_tip
-			on:mouseout=close_tip
-			on:click=on_click
-			on:dragend=on_drag
-			data-controlsvisible=style.controls_visible
-			max=state.duration.read_only()
-			value=state.current_time.read_only()
-			class=PROGRESS_BAR
-		></progress>
 	}
 }
 
-fn get_seek_pos_as_time_str<E>(event: E) -> String
-where
-	E: AsRef<MouseEvent>,
-{
-	let seek_pos = seek_pos(event);
-	utils::unix_to_hours_secs_mins(seek_pos)
-}
-
 fn seek_pos<E>(event: E) -> f64
 where
 	E: AsRef<MouseEvent>,
@@ -1,3 +1,4 @@
+use invidious::Chapters;
 use leptos::*;
 
 use crate::contexts::PlayerState;
@@ -5,12 +6,26 @@ use crate::contexts::PlayerState;
 #[component]
 pub fn TimeInfo() -> impl IntoView {
 	let state = expect_context::<PlayerState>();
+	let chapters = expect_context::<RwSignal<Chapters>>();
+
+	let chapter_view = move || {
+		chapters.with(|chapters| {
+			chapters.chapter_at(state.current_time.get()).map(|chapter| {
+				let title = chapter.title.clone();
+				view! {
+					<p>{"•"}</p>
+					<p class="font-sans truncate max-w-xs">{title}</p>
+				}
+			})
+		})
+	};
 
 	view! {
 		<div class="flex flex-row space-x-2 ml-2 font-mono">
 			<p>{state.current_time_str}</p>
 			<p>/</p>
 			<p>{state.duration_str}</p>
+			{chapter_view}
 		</div>
 	}
 }
@@ -30,6 +30,7 @@ pub fn VideoPlayerControls() -> impl IntoView {
 						<TimeInfo/>
 					</div>
 					<div class="flex flex-row">
+						<AnnotationsBtn/>
 						<FormatDropdown/>
 						<FullWindowBtn/>
 						<FullScreenBtn/>
@@ -0,0 +1,113 @@
+use invidious::{Annotation, AnnotationLink, AnnotationStyle, Annotations};
+use leptos::*;
+use web_sys::MouseEvent;
+
+use crate::contexts::{NetworkConfigCtx, PlayerState, PlayerStyle, TransportCtx};
+
+#[component]
+pub fn AnnotationsOverlay(video_id: String) -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+	let style = expect_context::<PlayerStyle>();
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
+	let video_id = StoredValue::new(video_id);
+
+	let annotations = create_resource(
+		move || server.get(),
+		move |server| async move {
+			Annotations::fetch_annotations(&transport.get(), &server, &video_id.get_value()).await
+		},
+	);
+
+	// Most videos never had annotations, so failing to load them isn't worth an error.
+	let active_annotations = move || {
+		let time = state.current_time.get();
+		annotations
+			.get()
+			.and_then(|annotations| annotations.ok())
+			.map(|annotations| annotations.active_at(time).cloned().collect::<Vec<Annotation>>())
+			.unwrap_or_default()
+	};
+
+	view! {
+		<Show when=move || style.annotations_visible.get()>
+			<div class="absolute inset-0 pointer-events-none overflow-hidden [container-type:size]">
+				<For
+					each=active_annotations
+					key=|annotation| annotation.id.clone()
+					let:annotation
+				>
+					<AnnotationItem annotation=annotation/>
+				</For>
+			</div>
+		</Show>
+	}
+}
+
+#[component]
+pub fn AnnotationItem(annotation: Annotation) -> impl IntoView {
+	let region = annotation.region;
+	let appearance = annotation.appearance;
+	let mut css = format!(
+		"left: {}%; top: {}%; width: {}%; height: {}%;",
+		region.x, region.y, region.width, region.height
+	);
+	match annotation.style {
+		AnnotationStyle::Highlight => {
+			let border = appearance.background.unwrap_or(String::from("#ffffff"));
+			css.push_str(&format!(" border: 2px solid {};", border));
+		}
+		_ => {
+			let background =
+				appearance.background_css().unwrap_or(String::from("rgba(0, 0, 0, 0.6)"));
+			let foreground = appearance.foreground.unwrap_or(String::from("#ffffff"));
+			css.push_str(&format!(" background-color: {}; color: {};", background, foreground));
+		}
+	}
+	if let Some(text_size) = appearance.text_size {
+		css.push_str(&format!(" font-size: {}cqh;", text_size));
+	}
+
+	let classes = match annotation.style {
+		AnnotationStyle::Speech => "absolute p-1 rounded-xl leading-tight overflow-hidden",
+		AnnotationStyle::Title => {
+			"absolute p-1 font-semibold text-center leading-tight overflow-hidden"
+		}
+		_ => "absolute p-1 rounded leading-tight overflow-hidden",
+	};
+	let text = annotation.text;
+
+	match annotation.link {
+		Some(link) => {
+			let (href, target) = match link {
+				AnnotationLink::Video { id, start } => {
+					let href = match start {
+						Some(start) => format!("/player?id={}&t={}", id, start),
+						None => format!("/player?id={}", id),
+					};
+					(href, "_self")
+				}
+				AnnotationLink::Url(url) => (url, "_blank"),
+			};
+			view! {
+				<a
+					href=href
+					target=target
+					rel="noreferrer"
+					on:click=|event: MouseEvent| event.stop_propagation()
+					style=css
+					class=format!("{} pointer-events-auto cursor-pointer hover:brightness-125", classes)
+				>
+					{text}
+				</a>
+			}
+			.into_view()
+		}
+		None => view! {
+			<div style=css class=classes>
+				{text}
+			</div>
+		}
+		.into_view(),
+	}
+}
@@ -1,4 +1,4 @@
-mod audio;
+mod annotations;
 mod player;
 mod video;
 
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use invidious::{Format, Formats, Video, VideoFormat};
+use invidious::{Chapters, Format, Formats, Storyboard, Video, VideoFormat};
 use leptos::{leptos_dom::helpers::TimeoutHandle, *};
 use utils::get_element_by_id;
 use wasm_bindgen::JsCast;
@@ -8,25 +8,24 @@ use web_sys::{Element, HtmlDivElement};
 
 use crate::{
 	components::FerrisError,
-	contexts::{PlaybackState, PlayerState, PlayerStyle, VIDEO_CONTAINER_ID, VIDEO_CONTROLS_ID},
+	contexts::{
+		PlaybackState, PlayerConfigCtx, PlayerState, PlayerStyle, VIDEO_CONTAINER_ID,
+		VIDEO_CONTROLS_ID,
+	},
 	pages::video::{
 		page::VideoResource,
 		utils::get_format,
 		video_player::{
-			player::{audio::AudioStream, video::VideoStream},
+			player::{annotations::AnnotationsOverlay, video::VideoStream},
 			VideoPlayerControls,
 		},
 	},
+	resources::category_name,
 	utils::i18n,
 };
 
 #[component]
 pub fn VideoContainer(video_resource: VideoResource) -> impl IntoView {
-	let state = PlayerState::init();
-	let style = PlayerStyle::init();
-	provide_context(state);
-	provide_context(style);
-
 	let video_player_view = move || {
 		video_resource.get().map(|video_result| match video_result {
 			Ok(video) => view! { <VideoPlayer video=video/> },
@@ -45,12 +44,31 @@ pub fn VideoPlayer(video: Video) -> impl IntoView {
 
 	let formats = Formats::from((video.adaptive_formats.clone(), video.format_streams.clone()));
 	let format = get_format(&formats).ok();
-	provide_context(create_rw_signal(formats));
+	let formats = create_rw_signal(formats);
+	provide_context(formats);
 	provide_context::<RwSignal<Option<Format>>>(create_rw_signal(format));
+	let storyboard = Storyboard::largest(&video.storyboards).cloned();
+	provide_context::<RwSignal<Option<Storyboard>>>(create_rw_signal(storyboard));
+	provide_context::<RwSignal<Chapters>>(create_rw_signal(video.chapters()));
 
 	// let format = get_video_format_ctx(&formats).ok();
 	// provide_context(create_rw_signal(format));
 
+	let max_video_quality = expect_context::<PlayerConfigCtx>().max_video_quality_slice.0;
+	let adapt_quality = set_interval_with_handle(
+		move || {
+			let _ = formats.with_untracked(|formats| {
+				state.adapt_quality(&formats.video_formats, &max_video_quality.get_untracked())
+			});
+		},
+		Duration::from_secs(2),
+	);
+	on_cleanup(move || {
+		if let Ok(handle) = adapt_quality {
+			handle.clear();
+		}
+	});
+
 	let handle_store: RwSignal<Option<TimeoutHandle>> = create_rw_signal(None);
 
 	let idle_detection = move |_| {
@@ -68,13 +86,6 @@ pub fn VideoPlayer(video: Video) -> impl IntoView {
 		handle_store.set(Some(handle));
 	};
 
-	set_interval(
-		move || {
-			let _ = state.sync();
-		},
-		Duration::from_secs(3),
-	);
-
 	view! {
 		<div
 			data-controls=move || style.controls_visible.get().to_string()
@@ -98,13 +109,44 @@ pub fn VideoPlayer(video: Video) -> impl IntoView {
 			id=VIDEO_CONTAINER_ID
 		>
 			<VideoStream video=video.clone()/>
-			<AudioStream/>
+			<AnnotationsOverlay video_id=video.id.clone()/>
+			<SponsorBlockSkipBtn/>
 			<VideoPlayerControls/>
 			<LoadingCircle/>
 		</div>
 	}
 }
 
+/// Offers to skip the SponsorBlock segment playing, for categories not skipped automatically.
+#[component]
+pub fn SponsorBlockSkipBtn() -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+
+	let label = move || {
+		state.skippable_segment.with(|segment| {
+			segment.as_ref().and_then(|segment| segment.category()).map(|category| {
+				format!("{} {}", i18n("sponsorblock.skip")(), category_name(category))
+			})
+		})
+	};
+
+	move || {
+		label().map(|label| {
+			view! {
+				<button
+					on:click=move |ev| {
+						ev.stop_propagation();
+						let _ = state.skip_segment();
+					}
+					class="absolute z-20 right-4 bottom-20 btn btn-sm btn-neutral opacity-90"
+				>
+					{label}
+				</button>
+			}
+		})
+	}
+}
+
 #[component]
 pub fn VideoPlaceholder() -> impl IntoView {
 	view! {
@@ -2,14 +2,31 @@ use invidious::{Format, Video};
 use leptos::*;
 
 use crate::{
-	contexts::{PlayerState, PlayerStyle, VIDEO_PLAYER_ID},
-	utils::is_webkit,
+	contexts::{PlayQueueCtx, PlayerState, PlayerStyle, VIDEO_PLAYER_ID},
+	utils::{get_current_video_query_signal, get_start_time_query_signal, is_webkit},
 };
 
+/// The only media element of the player. DASH formats are played in it through MSE, so their
+/// audio and video can't drift apart.
 #[component]
 pub fn VideoStream(video: Video) -> impl IntoView {
 	let state = expect_context::<PlayerState>();
 	let style = expect_context::<PlayerStyle>();
+	let queue = expect_context::<PlayQueueCtx>();
+	let open_video = get_current_video_query_signal().1;
+	let format = expect_context::<RwSignal<Option<Format>>>();
+	let video_id = video.id.clone();
+	let start_time = get_start_time_query_signal().0;
+
+	let video_ref = create_node_ref::<html::Video>();
+	video_ref.on_load(move |_| {
+		request_animation_frame(move || {
+			if let Some(format) = format.get_untracked() {
+				let start_time = start_time.get_untracked().unwrap_or_default();
+				let _ = state.load(format, start_time as f64);
+			}
+		})
+	});
 
 	view! {
 		<video
@@ -17,6 +34,7 @@ pub fn VideoStream(video: Video) -> impl IntoView {
 			data-fullscreen=move || style.fullscreen.get().to_string()
 			on:waiting=move |_| {
 				let _ = state.set_video_ready(false);
+				state.buffer();
 			}
 
 			on:loadedmetadata=move |_| {
@@ -29,27 +47,20 @@ pub fn VideoStream(video: Video) -> impl IntoView {
 				let _ = state.set_video_ready(true);
 			}
 
+			node_ref=video_ref
 			class="w-full rounded max-h-[calc(100vh-12rem)] data-[fullwindow=true]:max-h-screen data-[fullscreen=true]:max-h-screen"
 			id=VIDEO_PLAYER_ID
 			on:timeupdate=move |_| {
 				let _ = state.update_time();
 			}
 
+			on:ended=move |_| state.play_next(queue, &video_id, open_video)
+
 			poster=&video.thumbnails.first().unwrap().url
 			preload="auto"
 			controls=false
 			autoplay=false
 			playsinline=true
-		>
-			<VideoSource/>
-		</video>
+		></video>
 	}
 }
-
-#[component]
-pub fn VideoSource() -> impl IntoView {
-	let format = expect_context::<RwSignal<Option<Format>>>();
-	let source = move || format.get().map(|format| format.video_url()).flatten();
-
-	move || view! { <source src=source/> }
-}
@@ -4,11 +4,12 @@ use leptos_router::create_query_signal;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 
-use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
+use crate::contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct CommentsResourceArgs {
 	server: Signal<String>,
+	transport: TransportCtx,
 	locale: Signal<RustyTubeLocale>,
 	video_id: Memo<Option<String>>,
 	comments_vec: RwSignal<Vec<Comment>>,
@@ -22,6 +23,7 @@ impl CommentsResourceArgs {
 	) -> Self {
 		Self {
 			server: expect_context::<NetworkConfigCtx>().server_slice.0,
+			transport: expect_context::<TransportCtx>(),
 			locale: expect_context::<RegionConfigCtx>().locale_slice.0,
 			video_id: create_query_signal("id").0,
 			comments_vec,
@@ -48,6 +50,7 @@ impl CommentsResource {
 async fn fetch_comments(args: CommentsResourceArgs) -> Result<(), RustyTubeError> {
 	if args.continuation.get().is_some() || args.comments_vec.get().len() == 0 {
 		let comments = Comments::fetch_comments(
+			&args.transport.get(),
 			args.server.get().as_str(),
 			args.video_id.get().unwrap().as_str(),
 			args.continuation.get().as_deref(),
@@ -0,0 +1,54 @@
+use invidious::Hashtag;
+use leptos::*;
+use locales::RustyTubeLocale;
+use rustytube_error::RustyTubeError;
+
+use crate::{
+	contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx},
+	utils::get_current_hashtag_query_signal,
+};
+
+#[derive(Clone, PartialEq)]
+pub struct HashtagResourceArgs {
+	server: String,
+	transport: TransportCtx,
+	locale: RustyTubeLocale,
+	tag: String,
+}
+
+impl HashtagResourceArgs {
+	fn new(tag: Memo<Option<String>>) -> Self {
+		Self {
+			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			transport: expect_context::<TransportCtx>(),
+			locale: expect_context::<RegionConfigCtx>().locale_slice.0.get(),
+			tag: tag.get().unwrap_or_default(),
+		}
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct HashtagResource {
+	pub resource: Resource<HashtagResourceArgs, Result<Hashtag, RustyTubeError>>,
+}
+
+impl HashtagResource {
+	pub fn initialise() -> Self {
+		let tag = get_current_hashtag_query_signal().0;
+		let resource =
+			Resource::new(move || HashtagResourceArgs::new(tag), move |args| fetch_hashtag(args));
+
+		HashtagResource { resource }
+	}
+}
+
+async fn fetch_hashtag(args: HashtagResourceArgs) -> Result<Hashtag, RustyTubeError> {
+	Hashtag::fetch_hashtag(
+		&args.transport.get(),
+		&args.server,
+		&args.tag,
+		1,
+		&args.locale.to_invidious_lang(),
+	)
+	.await
+}
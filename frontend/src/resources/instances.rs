@@ -1,9 +1,9 @@
-use invidious::{fetch_instance_info, Instances};
+use invidious::{fetch_instance_info, rank_instances, BrowserTransport, Instances, RankedInstance};
 use leptos::*;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 
-use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
+use crate::contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx};
 
 use super::{initial_value, save_resource};
 
@@ -16,18 +16,58 @@ pub struct InstancesResource {
 
 impl InstancesResource {
 	pub fn initialise() -> Self {
+		let transport = expect_context::<TransportCtx>();
 		let resource = create_resource_with_initial_value(
 			move || (),
-			move |()| fetch_instances(),
+			move |()| fetch_instances(transport),
 			initial_value(INSTANCES_KEY),
 		);
 
 		InstancesResource { resource }
 	}
+
+	/// Switches to the best-ranked instance as soon as the instance list has loaded.
+	pub fn select_best_on_load(&self) {
+		let resource = self.resource;
+		let ranking = create_instance_ranking();
+
+		create_effect(move |dispatched: Option<bool>| {
+			if dispatched == Some(true) {
+				return true;
+			}
+			match resource.get() {
+				Some(Ok(instances)) => {
+					ranking.dispatch(instances);
+					true
+				}
+				_ => false,
+			}
+		});
+	}
+}
+
+pub type InstanceRankingAction = Action<Instances, Vec<RankedInstance>>;
+
+/// Probes the given instances, then switches to the best one for the trending region.
+pub fn create_instance_ranking() -> InstanceRankingAction {
+	let server = expect_context::<NetworkConfigCtx>().server_slice.1;
+	let region = expect_context::<RegionConfigCtx>().trending_region_slice.0;
+
+	create_action(move |instances: &Instances| {
+		let instances = instances.clone();
+		let region = region.get_untracked();
+		async move {
+			let ranked = rank_instances(&BrowserTransport, instances, Some(region.alpha2())).await;
+			if let Some(best) = ranked.first() {
+				server.set(best.info.uri.clone());
+			}
+			ranked
+		}
+	})
 }
 
-async fn fetch_instances() -> Result<Instances, RustyTubeError> {
-	let instances = fetch_instance_info().await?;
+async fn fetch_instances(transport: TransportCtx) -> Result<Instances, RustyTubeError> {
+	let instances = fetch_instance_info(&transport.get()).await?;
 	save_resource(INSTANCES_KEY, &instances).await?;
 	Ok(instances)
 }
@@ -0,0 +1,68 @@
+use invidious::Mix;
+use leptos::*;
+use locales::RustyTubeLocale;
+use rustytube_error::RustyTubeError;
+
+use crate::{
+	contexts::{NetworkConfigCtx, PlayQueueCtx, RegionConfigCtx, TransportCtx},
+	utils::get_current_mix_query_signal,
+};
+
+#[derive(Clone, PartialEq)]
+pub struct MixResourceArgs {
+	server: String,
+	transport: TransportCtx,
+	locale: RustyTubeLocale,
+	id: String,
+}
+
+impl MixResourceArgs {
+	fn new(id: Memo<Option<String>>) -> Self {
+		Self {
+			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			transport: expect_context::<TransportCtx>(),
+			locale: expect_context::<RegionConfigCtx>().locale_slice.0.get(),
+			id: id.get().unwrap_or_default(),
+		}
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct MixResource {
+	pub resource: Resource<MixResourceArgs, Result<Mix, RustyTubeError>>,
+}
+
+impl MixResource {
+	pub fn initialise() -> Self {
+		let id = get_current_mix_query_signal().0;
+		let resource = Resource::new(move || MixResourceArgs::new(id), move |args| fetch_mix(args));
+
+		MixResource { resource }
+	}
+}
+
+async fn fetch_mix(args: MixResourceArgs) -> Result<Mix, RustyTubeError> {
+	Mix::fetch_mix(&args.transport.get(), &args.server, &args.id, &args.locale.to_invidious_lang())
+		.await
+}
+
+pub type RadioAction = Action<String, Result<(), RustyTubeError>>;
+
+/// Fetches the radio mix of a video and queues it up behind the video.
+pub fn create_radio_action() -> RadioAction {
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let transport = expect_context::<TransportCtx>();
+	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
+	let queue = expect_context::<PlayQueueCtx>();
+
+	create_action(move |video_id: &String| {
+		let video_id = video_id.clone();
+		let server = server.get_untracked();
+		let lang = locale.get_untracked().to_invidious_lang();
+		async move {
+			let mix = Mix::fetch_radio(&transport.get(), &server, &video_id, &lang).await?;
+			queue.start(mix);
+			Ok(())
+		}
+	})
+}
@@ -1,6 +1,8 @@
 mod comments;
 mod config;
+mod hashtag;
 mod instances;
+mod mix;
 mod popular;
 mod replies;
 mod sponsorblock;
@@ -10,7 +12,9 @@ mod trending;
 
 pub use comments::*;
 pub use config::*;
+pub use hashtag::*;
 pub use instances::*;
+pub use mix::*;
 pub use popular::*;
 pub use r#struct::*;
 pub use replies::*;
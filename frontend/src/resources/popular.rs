@@ -3,7 +3,7 @@ use leptos::*;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 
-use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
+use crate::contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx};
 
 use super::{initial_value, save_resource};
 
@@ -12,6 +12,7 @@ static POPULAR_KEY: &'static str = "popular_videos";
 #[derive(Clone, PartialEq)]
 pub struct PopularResourceArgs {
 	server: String,
+	transport: TransportCtx,
 	locale: RustyTubeLocale,
 }
 
@@ -19,6 +20,7 @@ impl PopularResourceArgs {
 	fn new() -> Self {
 		Self {
 			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			transport: expect_context::<TransportCtx>(),
 			locale: expect_context::<RegionConfigCtx>().locale_slice.0.get(),
 		}
 	}
@@ -42,7 +44,12 @@ impl PopularResource {
 }
 
 async fn fetch_popular(args: PopularResourceArgs) -> Result<Popular, RustyTubeError> {
-	let popular = Popular::fetch_popular(&args.server, &args.locale.to_invidious_lang()).await?;
+	let popular = Popular::fetch_popular(
+		&args.transport.get(),
+		&args.server,
+		&args.locale.to_invidious_lang(),
+	)
+	.await?;
 	save_resource(POPULAR_KEY, &popular).await?;
 	Ok(popular)
 }
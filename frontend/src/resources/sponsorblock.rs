@@ -1,17 +1,34 @@
+use config::{SegmentBehaviour, SponsorBlockCategories};
 use leptos::*;
 use rustytube_error::RustyTubeError;
-use sponsorblock_rs::{Query, Response, Segment};
+use sponsorblock_rs::{Action, Category, Lookup, Query, Response, Segment};
+
+use crate::{contexts::SponsorBlockConfigCtx, utils::i18n};
 
 #[derive(Clone, Copy)]
 pub struct SponsorBlockResource {
-	pub resource: RwSignal<Option<Resource<String, Result<Option<Response>, RustyTubeError>>>>,
+	pub resource: RwSignal<
+		Option<
+			Resource<
+				(String, SponsorBlockCategories, bool),
+				Result<Option<Response>, RustyTubeError>,
+			>,
+		>,
+	>,
 }
 
 impl SponsorBlockResource {
 	pub fn set_video(&self, video_id: Memo<Option<String>>) {
+		let config = expect_context::<SponsorBlockConfigCtx>();
+		let categories = config.categories_slice.0;
+		let hash_prefix_lookups = config.hash_prefix_lookups_slice.0;
 		self.resource.set(Some(Resource::new(
-			move || video_id.get().unwrap_or_default(),
-			move |id: String| fetch_sponsorblock_segments(id),
+			move || {
+				(video_id.get().unwrap_or_default(), categories.get(), hash_prefix_lookups.get())
+			},
+			move |(id, categories, hash_prefix_lookups)| {
+				fetch_sponsorblock_segments(id, categories, hash_prefix_lookups)
+			},
 		)));
 	}
 
@@ -24,6 +41,44 @@ impl SponsorBlockResource {
 	}
 }
 
-async fn fetch_sponsorblock_segments(id: String) -> Result<Option<Response>, RustyTubeError> {
-	Ok(Query::build(id).send_query().await.unwrap())
+async fn fetch_sponsorblock_segments(
+	id: String,
+	categories: SponsorBlockCategories,
+	hash_prefix_lookups: bool,
+) -> Result<Option<Response>, RustyTubeError> {
+	let categories = categories.enabled();
+	if categories.is_empty() {
+		return Ok(None);
+	}
+	let lookup = match hash_prefix_lookups {
+		true => Lookup::HashPrefix,
+		false => Lookup::VideoId,
+	};
+	let query = Query::build(id)
+		.set_lookup(lookup)
+		.set_categories(Some(categories))
+		.set_actions(Some(vec![Action::Skip, Action::Mute]));
+	query.send_query().await.map_err(RustyTubeError::sponsorblock_fetch)
+}
+
+pub fn category_name(category: Category) -> String {
+	match category {
+		Category::Sponsor => i18n("settings.sponsorblock.sponsor")(),
+		Category::SelfPromotion => i18n("settings.sponsorblock.selfpromo")(),
+		Category::Interaction => i18n("settings.sponsorblock.interaction")(),
+		Category::Intro => i18n("settings.sponsorblock.intro")(),
+		Category::Outro => i18n("settings.sponsorblock.outro")(),
+		Category::Preview => i18n("settings.sponsorblock.preview")(),
+		Category::OffTopicMusic => i18n("settings.sponsorblock.offtopic_music")(),
+		Category::Filler => i18n("settings.sponsorblock.filler")(),
+	}
+}
+
+pub fn behaviour_name(behaviour: SegmentBehaviour) -> String {
+	match behaviour {
+		SegmentBehaviour::AutoSkip => i18n("settings.sponsorblock.auto_skip")(),
+		SegmentBehaviour::Mute => i18n("settings.sponsorblock.mute")(),
+		SegmentBehaviour::ManualSkip => i18n("settings.sponsorblock.manual_skip")(),
+		SegmentBehaviour::Ignore => i18n("settings.sponsorblock.ignore")(),
+	}
 }
@@ -1,10 +1,11 @@
+use futures::StreamExt;
 use gloo::storage::{LocalStorage, Storage};
-use invidious::{SubsThumbsResult, SubsVideosResult, Subscription, Subscriptions};
+use invidious::{ChannelVideos, SubsThumbsResult, SubsVideosResult, Subscription, Subscriptions};
 use leptos::*;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 
-use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
+use crate::contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx};
 
 use super::{initial_value, load_resource, save_resource};
 
@@ -46,6 +47,7 @@ static SUBSCRIPTIONS_VIDEOS_KEY: &'static str = "subscriptions_videos";
 #[derive(Clone, PartialEq)]
 pub struct SubscriptionsVideosResourceArgs {
 	server: String,
+	transport: TransportCtx,
 	locale: RustyTubeLocale,
 	subscriptions: Subscriptions,
 }
@@ -54,6 +56,7 @@ impl SubscriptionsVideosResourceArgs {
 	pub fn new(subscriptions: SubscriptionsCtx) -> Self {
 		Self {
 			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			transport: expect_context::<TransportCtx>(),
 			locale: expect_context::<RegionConfigCtx>().locale_slice.0.get(),
 			subscriptions: subscriptions.0.get(),
 		}
@@ -63,27 +66,41 @@ impl SubscriptionsVideosResourceArgs {
 #[derive(Copy, Clone)]
 pub struct SubscriptionsVideosResource {
 	pub resource: Resource<SubscriptionsVideosResourceArgs, SubsVideosResult>,
+	/// Channels fetched so far, filled in while the resource is loading.
+	pub progress: RwSignal<Vec<Result<ChannelVideos, RustyTubeError>>>,
 }
 
 impl SubscriptionsVideosResource {
 	pub fn initialise(subscriptions: SubscriptionsCtx) -> Self {
+		let progress = RwSignal::new(vec![]);
+
 		SubscriptionsVideosResource {
 			resource: create_resource_with_initial_value(
 				move || SubscriptionsVideosResourceArgs::new(subscriptions),
-				move |args| fetch_subs_videos(args),
+				move |args| fetch_subs_videos(args, progress),
 				initial_value(SUBSCRIPTIONS_KEY),
 			),
+			progress,
 		}
 	}
 }
 
-async fn fetch_subs_videos(args: SubscriptionsVideosResourceArgs) -> SubsVideosResult {
-	let videos = args
-		.subscriptions
-		.fetch_videos(&args.server, false, &args.locale.to_invidious_lang())
+async fn fetch_subs_videos(
+	args: SubscriptionsVideosResourceArgs,
+	progress: RwSignal<Vec<Result<ChannelVideos, RustyTubeError>>>,
+) -> SubsVideosResult {
+	progress.set(vec![]);
+	let transport = args.transport.get();
+	let lang = args.locale.to_invidious_lang();
+	args.subscriptions
+		.stream_videos(&transport, &args.server, false, &lang)
+		.for_each(|channel_videos| {
+			progress.update(|progress| progress.push(channel_videos));
+			async {}
+		})
 		.await;
 	// save_resource(SUBSCRIPTIONS_VIDEOS_KEY, &videos).await?;
-	videos
+	Ok(progress.get_untracked())
 }
 
 static SUBSCRIPTIONS_THUMBNAILS_KEY: &'static str = "subscriptions_thumbs";
@@ -91,6 +108,7 @@ static SUBSCRIPTIONS_THUMBNAILS_KEY: &'static str = "subscriptions_thumbs";
 #[derive(Clone, PartialEq)]
 pub struct SubscriptionsThumbnailsResourceArgs {
 	server: String,
+	transport: TransportCtx,
 	subscriptions: Subscriptions,
 }
 
@@ -98,6 +116,7 @@ impl SubscriptionsThumbnailsResourceArgs {
 	pub fn new(subscriptions: SubscriptionsCtx) -> Self {
 		Self {
 			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			transport: expect_context::<TransportCtx>(),
 			subscriptions: subscriptions.0.get(),
 		}
 	}
@@ -121,7 +140,7 @@ impl SubscriptionsThumbnailsResource {
 }
 
 async fn fetch_subs_thumbnails(args: SubscriptionsThumbnailsResourceArgs) -> SubsThumbsResult {
-	let thumbs = args.subscriptions.fetch_channel_thumbs(&args.server).await;
+	let thumbs = args.subscriptions.fetch_channel_thumbs(&args.transport.get(), &args.server).await;
 	save_resource(SUBSCRIPTIONS_THUMBNAILS_KEY, &thumbs.clone().unwrap()).await?;
 	thumbs
 }
@@ -4,7 +4,7 @@ use leptos::*;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 
-use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
+use crate::contexts::{NetworkConfigCtx, RegionConfigCtx, TransportCtx};
 
 use super::{initial_value, save_resource};
 
@@ -13,6 +13,7 @@ static TRENDING_KEY: &'static str = "trending_videos";
 #[derive(Clone, PartialEq)]
 pub struct TrendingResourceArgs {
 	pub server: String,
+	pub transport: TransportCtx,
 	pub category: TrendingCategory,
 	pub locale: RustyTubeLocale,
 	pub region: CountryCode,
@@ -22,6 +23,7 @@ impl TrendingResourceArgs {
 	fn new(category: RwSignal<TrendingCategory>) -> Self {
 		Self {
 			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			transport: expect_context::<TransportCtx>(),
 			category: category.get(),
 			locale: expect_context::<RegionConfigCtx>().locale_slice.0.get(),
 			region: expect_context::<RegionConfigCtx>().trending_region_slice.0.get(),
@@ -47,6 +49,7 @@ impl TrendingResource {
 
 async fn fetch_trending(args: TrendingResourceArgs) -> Result<Trending, RustyTubeError> {
 	let trending = Trending::fetch_trending(
+		&args.transport.get(),
 		&args.server,
 		&args.category,
 		&args.region.alpha2(),
@@ -1,12 +1,47 @@
 use gloo::console::debug;
-use leptos::{expect_context, window, Memo, SignalGet, SignalSetter};
-use leptos_router::create_query_signal;
+use leptos::{
+	create_memo, expect_context, window, Memo, SignalGet, SignalGetUntracked, SignalSetter,
+	SignalWith,
+};
+use leptos_router::{create_query_signal, use_location, use_navigate, use_query_map};
 
 use crate::contexts::RegionConfigCtx;
 
 pub type VideoQuerySignal = (Memo<Option<String>>, SignalSetter<Option<String>>);
+/// Opening another video drops the `t` start time, which only belongs to the video it came with.
 pub fn get_current_video_query_signal() -> VideoQuerySignal {
-	create_query_signal("id")
+	let query = use_query_map();
+	let location = use_location();
+	let navigate = use_navigate();
+
+	let video_id = create_memo(move |_| query.with(|query| query.get("id").cloned()));
+	let set_video_id = SignalSetter::map(move |video_id: Option<String>| {
+		let mut query = query.get_untracked();
+		query.remove("t");
+		match video_id {
+			Some(video_id) => _ = query.insert(String::from("id"), video_id),
+			None => _ = query.remove("id"),
+		}
+		let url = format!("{}{}", location.pathname.get_untracked(), query.to_query_string());
+		navigate(&url, Default::default());
+	});
+	(video_id, set_video_id)
+}
+
+/// The second a video link asks playback to start at.
+pub type StartTimeQuerySignal = (Memo<Option<u32>>, SignalSetter<Option<u32>>);
+pub fn get_start_time_query_signal() -> StartTimeQuerySignal {
+	create_query_signal("t")
+}
+
+pub type MixQuerySignal = (Memo<Option<String>>, SignalSetter<Option<String>>);
+pub fn get_current_mix_query_signal() -> MixQuerySignal {
+	create_query_signal("list")
+}
+
+pub type HashtagQuerySignal = (Memo<Option<String>>, SignalSetter<Option<String>>);
+pub fn get_current_hashtag_query_signal() -> HashtagQuerySignal {
+	create_query_signal("tag")
 }
 
 pub fn i18n(key: &'static str) -> impl Fn() -> String {
@@ -5,6 +5,7 @@ use invidious::{AudioQuality, VideoQuality};
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
+use sponsorblock_rs::Category;
 use utils::save_to_browser_storage;
 
 use crate::RememberPosition::VideosOnly;
@@ -31,6 +32,9 @@ pub struct PlayerConfig {
 	pub auto_play: bool,
 	pub fast_forward_interval: u8,
 	pub default_video_quality: VideoQuality,
+	/// The highest quality the "Auto" quality switches up to.
+	#[serde(default = "PlayerConfig::default_max_video_quality")]
+	pub max_video_quality: VideoQuality,
 	pub default_audio_quality: AudioQuality,
 	pub remember_position: RememberPosition,
 	pub volume: f64,
@@ -49,6 +53,32 @@ pub struct RegionConfig {
 	pub trending_region: isocountry::CountryCode,
 }
 
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SponsorBlockConfig {
+	pub categories: SponsorBlockCategories,
+	/// Segments shorter than this many seconds are played through.
+	pub min_segment_length: f64,
+	/// Whether segments are looked up by a prefix of the video's hash rather than its ID, so the
+	/// SponsorBlock server can't tell which video is watched.
+	#[serde(default = "SponsorBlockConfig::default_hash_prefix_lookups")]
+	pub hash_prefix_lookups: bool,
+}
+
+/// What the player does with the segments of each SponsorBlock category.
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SponsorBlockCategories {
+	pub sponsor: SegmentBehaviour,
+	pub selfpromo: SegmentBehaviour,
+	pub interaction: SegmentBehaviour,
+	pub intro: SegmentBehaviour,
+	pub outro: SegmentBehaviour,
+	pub preview: SegmentBehaviour,
+	pub music_offtopic: SegmentBehaviour,
+	pub filler: SegmentBehaviour,
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct Config {
@@ -57,6 +87,8 @@ pub struct Config {
 	pub player: PlayerConfig,
 	pub privacy: PrivacyConfig,
 	pub region: RegionConfig,
+	#[serde(default)]
+	pub sponsorblock: SponsorBlockConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -67,6 +99,16 @@ pub enum RememberPosition {
 	Never,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentBehaviour {
+	AutoSkip,
+	Mute,
+	/// Shows a button to skip the segment while it plays.
+	ManualSkip,
+	Ignore,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum HomepageCategory {
@@ -100,6 +142,7 @@ impl Default for PlayerConfig {
 		let auto_play = true;
 		let fast_forward_interval = 10u8;
 		let default_video_quality = VideoQuality::_1080p;
+		let max_video_quality = Self::default_max_video_quality();
 		let default_audio_quality = AudioQuality::Medium;
 		let remember_position = VideosOnly;
 		let volume = 0.5f64;
@@ -109,12 +152,19 @@ impl Default for PlayerConfig {
 			volume,
 			fast_forward_interval,
 			default_video_quality,
+			max_video_quality,
 			default_audio_quality,
 			remember_position,
 		}
 	}
 }
 
+impl PlayerConfig {
+	fn default_max_video_quality() -> VideoQuality {
+		VideoQuality::_1080p60
+	}
+}
+
 impl Default for PrivacyConfig {
 	fn default() -> Self {
 		let keep_history = true;
@@ -132,6 +182,74 @@ impl Default for RegionConfig {
 	}
 }
 
+impl Default for SponsorBlockConfig {
+	fn default() -> Self {
+		let categories = SponsorBlockCategories::default();
+		let min_segment_length = 0f64;
+		let hash_prefix_lookups = Self::default_hash_prefix_lookups();
+
+		Self { categories, min_segment_length, hash_prefix_lookups }
+	}
+}
+
+impl SponsorBlockConfig {
+	fn default_hash_prefix_lookups() -> bool {
+		true
+	}
+}
+
+impl Default for SponsorBlockCategories {
+	fn default() -> Self {
+		Self {
+			sponsor: SegmentBehaviour::AutoSkip,
+			selfpromo: SegmentBehaviour::ManualSkip,
+			interaction: SegmentBehaviour::ManualSkip,
+			intro: SegmentBehaviour::Ignore,
+			outro: SegmentBehaviour::Ignore,
+			preview: SegmentBehaviour::Ignore,
+			music_offtopic: SegmentBehaviour::Ignore,
+			filler: SegmentBehaviour::Ignore,
+		}
+	}
+}
+
+impl SponsorBlockCategories {
+	pub fn get(&self, category: Category) -> SegmentBehaviour {
+		match category {
+			Category::Sponsor => self.sponsor,
+			Category::SelfPromotion => self.selfpromo,
+			Category::Interaction => self.interaction,
+			Category::Intro => self.intro,
+			Category::Outro => self.outro,
+			Category::Preview => self.preview,
+			Category::OffTopicMusic => self.music_offtopic,
+			Category::Filler => self.filler,
+		}
+	}
+
+	pub fn set(&mut self, category: Category, behaviour: SegmentBehaviour) {
+		let setting = match category {
+			Category::Sponsor => &mut self.sponsor,
+			Category::SelfPromotion => &mut self.selfpromo,
+			Category::Interaction => &mut self.interaction,
+			Category::Intro => &mut self.intro,
+			Category::Outro => &mut self.outro,
+			Category::Preview => &mut self.preview,
+			Category::OffTopicMusic => &mut self.music_offtopic,
+			Category::Filler => &mut self.filler,
+		};
+		*setting = behaviour;
+	}
+
+	/// The categories whose segments are acted on, the ones to ask SponsorBlock for.
+	pub fn enabled(&self) -> Vec<Category> {
+		Category::ALL
+			.into_iter()
+			.filter(|category| self.get(*category) != SegmentBehaviour::Ignore)
+			.collect()
+	}
+}
+
 impl Default for Config {
 	fn default() -> Self {
 		let network = NetworkConfig::default();
@@ -139,8 +257,9 @@ impl Default for Config {
 		let player = PlayerConfig::default();
 		let privacy = PrivacyConfig::default();
 		let region = RegionConfig::default();
+		let sponsorblock = SponsorBlockConfig::default();
 
-		Self { network, ui, player, privacy, region }
+		Self { network, ui, player, privacy, region, sponsorblock }
 	}
 }
 
@@ -2,7 +2,9 @@
 mod tests {
 	use std::{env::current_dir, fs};
 
-	use crate::{Config, NetworkConfig};
+	use sponsorblock_rs::Category;
+
+	use crate::{Config, NetworkConfig, SegmentBehaviour, SponsorBlockCategories};
 
 	#[test]
 	fn can_create_default_config() {
@@ -32,6 +34,7 @@ mod tests {
 			player: Default::default(),
 			privacy: Default::default(),
 			region: Default::default(),
+			sponsorblock: Default::default(),
 		};
 		let toml = config.to_toml_string().unwrap();
 
@@ -45,4 +48,37 @@ mod tests {
 		let toml = include_str!("files/config.toml");
 		let config: Config = toml::from_str(toml).unwrap();
 	}
+
+	#[test]
+	fn configure_sponsorblock_categories() {
+		let mut config = Config::default();
+		config.sponsorblock.categories.set(Category::Intro, SegmentBehaviour::Mute);
+		config.sponsorblock.categories.set(Category::Sponsor, SegmentBehaviour::Ignore);
+		let toml = config.to_toml_string().unwrap();
+		let config = Config::from_toml_string(&toml).unwrap();
+
+		assert_eq!(config.sponsorblock.categories.get(Category::Intro), SegmentBehaviour::Mute);
+		assert_eq!(
+			config.sponsorblock.categories.enabled(),
+			vec![Category::SelfPromotion, Category::Interaction, Category::Intro]
+		);
+
+		let without_sponsorblock = toml.split("[sponsorblock]").next().unwrap();
+		let config = Config::from_toml_string(without_sponsorblock).unwrap();
+		assert_eq!(config.sponsorblock.categories, SponsorBlockCategories::default());
+	}
+
+	#[test]
+	fn hash_prefix_lookups_by_default() {
+		let mut config = Config::default();
+		assert!(config.sponsorblock.hash_prefix_lookups);
+
+		config.sponsorblock.hash_prefix_lookups = false;
+		let toml = config.to_toml_string().unwrap();
+		assert!(!Config::from_toml_string(&toml).unwrap().sponsorblock.hash_prefix_lookups);
+
+		// Configs saved before the setting existed keep hashing.
+		let toml = toml.replace("hash_prefix_lookups = false\n", "");
+		assert!(Config::from_toml_string(&toml).unwrap().sponsorblock.hash_prefix_lookups);
+	}
 }
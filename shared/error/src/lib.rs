@@ -1,8 +1,9 @@
 use std::{
+	error::Error,
 	fmt::{self, Debug, Display, Formatter},
 	io,
-	ops::Range,
 	string::FromUtf8Error,
+	time::Duration,
 };
 
 use chrono::ParseError;
@@ -10,10 +11,45 @@ use gloo::{file::FileReadError, storage::errors::StorageError};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::JsValue;
 
+/// What went wrong, independent of the human-readable title and description.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+	Network,
+	Http(u16),
+	RateLimited,
+	/// The instance has disabled its API or the endpoint; another instance may serve it.
+	ApiDisabled,
+	Unavailable,
+	Parse,
+	Storage,
+	Io,
+	Player,
+	Tauri,
+	NotFound,
+	#[default]
+	Other,
+}
+
+impl ErrorKind {
+	/// Whether repeating the failed operation, possibly elsewhere, could succeed.
+	pub fn is_retryable(&self) -> bool {
+		match self {
+			ErrorKind::Network | ErrorKind::RateLimited | ErrorKind::ApiDisabled => true,
+			ErrorKind::Http(status) => *status >= 500,
+			_ => false,
+		}
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RustyTubeError {
+	#[serde(default)]
+	pub kind: ErrorKind,
 	pub title: String,
 	pub description: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub source: Option<Box<RustyTubeError>>,
 }
 
 impl Display for RustyTubeError {
@@ -22,11 +58,17 @@ impl Display for RustyTubeError {
 	}
 }
 
+impl Error for RustyTubeError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		self.source.as_deref().map(|source| source as &(dyn Error + 'static))
+	}
+}
+
 impl From<serde_json::Error> for RustyTubeError {
 	fn from(serde_json_err: serde_json::Error) -> Self {
 		let title = String::from("Serde Error");
 		let description = serde_json_err.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -34,7 +76,7 @@ impl From<csv::Error> for RustyTubeError {
 	fn from(csv_err: csv::Error) -> Self {
 		let title = String::from("CSV Error");
 		let description = csv_err.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -42,26 +84,30 @@ impl From<io::Error> for RustyTubeError {
 	fn from(io_error: io::Error) -> Self {
 		let title = String::from("I/O Error");
 		let description = io_error.kind().to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Io, title, description)
 	}
 }
 
 impl From<tauri_sys::error::Error> for RustyTubeError {
 	fn from(tauri_error: tauri_sys::error::Error) -> Self {
-		let mut tauri_error_object_string = tauri_error.to_string();
-
-		let range = Range { start: 0, end: 27 };
-		tauri_error_object_string.replace_range(range, "");
-		tauri_error_object_string.pop();
-		tauri_error_object_string.pop();
+		let tauri_error_string = tauri_error.to_string();
 
 		// Example tauri-sys error.
 		//
 		// "JS Binding: JsValue(Object({"description":"There was no save path
 		// selected.","title":"Save Error","verbose_description":null}))"
 
-		let error: RustyTubeError = serde_json::from_str(&tauri_error_object_string).unwrap();
-		error
+		let payload = tauri_error_string
+			.find('{')
+			.zip(tauri_error_string.rfind('}'))
+			.and_then(|(start, end)| tauri_error_string.get(start..=end));
+		let error =
+			payload.and_then(|payload| serde_json::from_str::<RustyTubeError>(payload).ok());
+
+		match error {
+			Some(error) => Self { kind: ErrorKind::Tauri, ..error },
+			None => Self::new(ErrorKind::Tauri, String::from("Tauri Error"), tauri_error_string),
+		}
 	}
 }
 
@@ -69,7 +115,7 @@ impl From<ron::Error> for RustyTubeError {
 	fn from(ron_error: ron::Error) -> Self {
 		let title = String::from("RON Error");
 		let description = ron_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -77,7 +123,7 @@ impl From<FromUtf8Error> for RustyTubeError {
 	fn from(utf8_error: FromUtf8Error) -> Self {
 		let title = String::from("File Read Error");
 		let description = utf8_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Io, title, description)
 	}
 }
 
@@ -85,7 +131,7 @@ impl From<ron::error::SpannedError> for RustyTubeError {
 	fn from(ron_error: ron::error::SpannedError) -> Self {
 		let title = String::from("RON Error");
 		let description = ron_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -93,7 +139,7 @@ impl From<StorageError> for RustyTubeError {
 	fn from(storage_error: StorageError) -> Self {
 		let title = String::from("Browser Storage Error");
 		let description = storage_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Storage, title, description)
 	}
 }
 
@@ -101,7 +147,7 @@ impl From<reqwasm::Error> for RustyTubeError {
 	fn from(reqwasm_error: reqwasm::Error) -> Self {
 		let title = String::from("Network Error");
 		let description = reqwasm_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Network, title, description)
 	}
 }
 
@@ -109,7 +155,7 @@ impl From<FileReadError> for RustyTubeError {
 	fn from(file_read_error: FileReadError) -> Self {
 		let title = String::from("File Read Error");
 		let description = file_read_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Io, title, description)
 	}
 }
 
@@ -117,7 +163,7 @@ impl From<toml::ser::Error> for RustyTubeError {
 	fn from(toml_ser_error: toml::ser::Error) -> Self {
 		let title = String::from("Toml Serialisation Error");
 		let description = toml_ser_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -125,7 +171,7 @@ impl From<toml::de::Error> for RustyTubeError {
 	fn from(toml_de_error: toml::de::Error) -> Self {
 		let title = String::from("Toml Serialisation Error");
 		let description = toml_de_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -133,7 +179,7 @@ impl From<ParseError> for RustyTubeError {
 	fn from(parse_error: ParseError) -> Self {
 		let title = String::from("Chrono RFC3339 parse error");
 		let description = parse_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -141,7 +187,7 @@ impl From<serde_xml_rs::Error> for RustyTubeError {
 	fn from(xml_error: serde_xml_rs::Error) -> Self {
 		let title = String::from("XML parse error");
 		let description = xml_error.to_string();
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 }
 
@@ -149,96 +195,189 @@ impl From<JsValue> for RustyTubeError {
 	fn from(js_value: JsValue) -> Self {
 		let title = String::from("Error");
 		let description = js_value.as_string().unwrap_or_default();
-		Self { title, description }
+		Self::new(ErrorKind::Other, title, description)
 	}
 }
 
 impl RustyTubeError {
+	pub fn new(kind: ErrorKind, title: String, description: String) -> Self {
+		Self { kind, title, description, source: None }
+	}
+
 	pub fn from(title: String, description: String) -> Self {
-		Self { title, description }
+		Self::new(ErrorKind::Other, title, description)
+	}
+
+	/// Attaches the error that caused this one, keeping this error's title and description.
+	pub fn with_source(mut self, source: RustyTubeError) -> Self {
+		self.source = Some(Box::new(source));
+		self
+	}
+
+	pub fn is_retryable(&self) -> bool {
+		self.kind.is_retryable()
 	}
 
 	pub fn element_not_found(id: &str) -> Self {
 		let title = String::from("Element Error");
 		let description = format!("An element with id: '{}' could not be found in the window.", id);
-		Self { title, description }
+		Self::new(ErrorKind::NotFound, title, description)
 	}
 
 	pub fn dyn_into_fail(id: &str) -> Self {
 		let title = String::from("Element Error");
 		let description = format!("An element with id: '{}' could not be dynamically changed.", id);
-		Self { title, description }
+		Self::new(ErrorKind::Other, title, description)
 	}
 
 	pub fn fetch_thumbnail_error() -> Self {
 		let title = String::from("Network Error");
 		let description = String::from("Could not fetch thumbnail.");
-		Self { title, description }
+		Self::new(ErrorKind::Network, title, description)
 	}
 
 	pub fn no_file_selected() -> Self {
 		let title = String::from("I/O Error");
 		let description = String::from("No file was chosen in the file dialog.");
-		Self { title, description }
+		Self::new(ErrorKind::Io, title, description)
 	}
 
 	pub fn parse_playlist_fail() -> Self {
 		let title = String::from("Playlist Error");
 		let description = String::from("Unable to parse playlist(s) from chosen file.");
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 
 	pub fn format_parse() -> Self {
 		let title = String::from("Format Parse Error");
 		let description = String::from("Unable to parse format.");
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 
 	pub fn no_dash_video_format_available() -> Self {
 		let title = String::from("DASH error");
 		let description = String::from("No DASH video format available.");
-		Self { title, description }
+		Self::new(ErrorKind::Player, title, description)
 	}
 
 	pub fn format_not_available() -> Self {
 		let title = String::from("Format Error");
 		let description = String::from("There is no format available.");
-		Self { title, description }
+		Self::new(ErrorKind::Player, title, description)
 	}
 
 	pub fn no_legacy_format_available() -> Self {
 		let title = String::from("Legacy Format Error");
 		let description = String::from("No legacy video format available.");
-		Self { title, description }
+		Self::new(ErrorKind::Player, title, description)
 	}
 
 	pub fn no_audio_format_available() -> Self {
 		let title = String::from("Audio Format error");
 		let description = String::from("No audio format available.");
-		Self { title, description }
+		Self::new(ErrorKind::Player, title, description)
+	}
+
+	pub fn webvtt_parse(line: usize) -> Self {
+		let title = String::from("Caption Parse Error");
+		let description = format!("Unable to parse WebVTT captions at line {}.", line);
+		Self::new(ErrorKind::Parse, title, description)
+	}
+
+	pub fn segment_index_parse(reason: &str) -> Self {
+		let title = String::from("Segment Index Error");
+		let description = format!("Unable to read the segments of a DASH format: {}.", reason);
+		Self::new(ErrorKind::Parse, title, description)
 	}
 
 	pub fn no_container_info(name: &str) -> Self {
 		let title = String::from("Container Info Error");
 		let description = format!("Unable to parse container info on format: {}", name);
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
+	}
+
+	pub fn country_code_parse(code: &str) -> Self {
+		let title = String::from("Country Code Parse Error");
+		let description = format!("{} is not a known country code.", code);
+		Self::new(ErrorKind::Parse, title, description)
 	}
 
 	pub fn search_url_parse() -> Self {
 		let title = String::from("Search Url Parse Error");
 		let description = format!("Unable to parse search url.");
-		Self { title, description }
+		Self::new(ErrorKind::Parse, title, description)
 	}
 
 	pub fn no_video_url_avaiable() -> Self {
 		let title = String::from("Video Url Error");
 		let description = format!("Could not find a video url on the selected format.");
-		Self { title, description }
+		Self::new(ErrorKind::Player, title, description)
+	}
+
+	pub fn no_mock_response(url: &str) -> Self {
+		let title = String::from("Network Error");
+		let description = format!("No mock response was registered for: {}", url);
+		Self::new(ErrorKind::Network, title, description)
+	}
+
+	pub fn video_unavailable(reason: &str) -> Self {
+		let title = String::from("Video Unavailable");
+		let description = reason.to_string();
+		Self::new(ErrorKind::Unavailable, title, description)
+	}
+
+	pub fn age_restricted(reason: &str) -> Self {
+		let title = String::from("Age Restricted");
+		let description = format!("This video is age-restricted and cannot be played: {}", reason);
+		Self::new(ErrorKind::Unavailable, title, description)
+	}
+
+	pub fn region_blocked(reason: &str) -> Self {
+		let title = String::from("Region Blocked");
+		let description =
+			format!("This video is not available in the instance's region: {}", reason);
+		Self::new(ErrorKind::Unavailable, title, description)
+	}
+
+	pub fn rate_limited() -> Self {
+		let title = String::from("Rate Limited");
+		let description =
+			String::from("The instance is receiving too many requests, try another one.");
+		Self::new(ErrorKind::RateLimited, title, description)
+	}
+
+	pub fn api_disabled() -> Self {
+		let title = String::from("API Disabled");
+		let description = String::from("The selected instance has disabled its API.");
+		Self::new(ErrorKind::ApiDisabled, title, description)
+	}
+
+	pub fn instance_error(status: u16, message: &str) -> Self {
+		let title = String::from("Instance Error");
+		let description = match message.is_empty() {
+			true => format!("The instance responded with HTTP status {}.", status),
+			false => format!("The instance responded with HTTP status {}: {}", status, message),
+		};
+		Self::new(ErrorKind::Http(status), title, description)
+	}
+
+	pub fn request_timeout(url: &str, timeout: Duration) -> Self {
+		let title = String::from("Network Error");
+		let description =
+			format!("No response from {} within {} seconds.", url, timeout.as_secs_f64());
+		Self::new(ErrorKind::Network, title, description)
 	}
 
 	pub fn no_audio_url_avaiable() -> Self {
 		let title = String::from("Audio Url Error");
 		let description = format!("Could not find an audio url on the selected format.");
-		Self { title, description }
+		Self::new(ErrorKind::Player, title, description)
+	}
+
+	pub fn sponsorblock_fetch(source: RustyTubeError) -> Self {
+		let title = String::from("SponsorBlock Error");
+		let description =
+			format!("Could not fetch the SponsorBlock segments: {}", source.description);
+		Self::new(ErrorKind::Network, title, description).with_source(source)
 	}
 }
@@ -1,7 +1,8 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{common::*, fetch::fetch, hidden::*};
+use super::CommunityPosts;
+use crate::{common::*, fetch::fetch, hidden::*, transport::Transport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -42,35 +43,43 @@ pub struct ChannelThumb {
 }
 
 impl Channel {
-	pub async fn fetch_channel(server: &str, id: &str, lang: &str) -> Result<Self, RustyTubeError> {
+	pub async fn fetch_channel(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
 		let channel_url = format!("{}/api/v1/channels/{}/", server, id);
-		let channel_json: String = fetch(&channel_url).await?;
+		let channel_json: String = fetch(transport, &channel_url).await?;
 		let channel: Self = serde_json::from_str(&channel_json)?;
 		Ok(channel)
 	}
 
 	pub async fn fetch_channel_thumbnails(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 	) -> Result<Vec<CommonImage>, RustyTubeError> {
 		let thumbnails_url = format!("{}/api/v1/channels/{}?fields=authorThumbnails", server, id);
-		let thumbnails_json = fetch(&thumbnails_url).await?;
+		let thumbnails_json = fetch(transport, &thumbnails_url).await?;
 		let thumbnails = serde_json::from_str(&thumbnails_json)?;
 		Ok(thumbnails)
 	}
 
 	pub async fn fetch_channel_thumb(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 	) -> Result<ChannelThumb, RustyTubeError> {
 		let thumbnails_url =
 			format!("{}/api/v1/channels/{}?fields=author,authorId,authorThumbnails", server, id);
-		let thumbnails_json = fetch(&thumbnails_url).await?;
+		let thumbnails_json = fetch(transport, &thumbnails_url).await?;
 		let thumbnails = serde_json::from_str(&thumbnails_json)?;
 		Ok(thumbnails)
 	}
 
 	pub async fn fetch_channel_videos(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 		continuation: Option<&str>,
@@ -83,12 +92,13 @@ impl Channel {
 			),
 			None => format!("{}/api/v1/channels/{}/videos?hl={}", server, id, lang),
 		};
-		let videos_json = fetch(&videos_url).await?;
+		let videos_json = fetch(transport, &videos_url).await?;
 		let videos = serde_json::from_str(&videos_json)?;
 		Ok(videos)
 	}
 
 	pub async fn fetch_channel_shorts(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 		continuation: Option<&str>,
@@ -101,12 +111,13 @@ impl Channel {
 			),
 			None => format!("{}/api/v1/channels/{}/shorts?hl={}", server, id, lang),
 		};
-		let shorts_json = fetch(&shorts_url).await?;
+		let shorts_json = fetch(transport, &shorts_url).await?;
 		let shorts = serde_json::from_str(&shorts_json)?;
 		Ok(shorts)
 	}
 
 	pub async fn fetch_channel_livestreams(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 		continuation: Option<&str>,
@@ -119,12 +130,13 @@ impl Channel {
 			),
 			None => format!("{}/api/v1/channels/{}/streams?hl={}", server, id, lang),
 		};
-		let livestreams_json = fetch(&livestreams_url).await?;
+		let livestreams_json = fetch(transport, &livestreams_url).await?;
 		let livestreams = serde_json::from_str(&livestreams_json)?;
 		Ok(livestreams)
 	}
 
 	pub async fn fetch_channel_playlists(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 		continuation: Option<&str>,
@@ -137,10 +149,29 @@ impl Channel {
 			),
 			None => format!("{}/api/v1/channels/{}/playlists?hl={}", server, id, lang),
 		};
-		let channel_videos_json = fetch(&playlists_url).await?;
+		let channel_videos_json = fetch(transport, &playlists_url).await?;
 		let channel_videos = serde_json::from_str(&channel_videos_json)?;
 		Ok(channel_videos)
 	}
+
+	pub async fn fetch_channel_community(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+		continuation: Option<&str>,
+		lang: &str,
+	) -> Result<CommunityPosts, RustyTubeError> {
+		let community_url = match continuation {
+			Some(continuation) => format!(
+				"{}/api/v1/channels/{}/community?continuation={}&hl={}",
+				server, id, continuation, lang
+			),
+			None => format!("{}/api/v1/channels/{}/community?hl={}", server, id, lang),
+		};
+		let community_json = fetch(transport, &community_url).await?;
+		let community = serde_json::from_str(&community_json)?;
+		Ok(community)
+	}
 }
 
 impl PartialEq for Channel {
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{CommonImage, CommonThumbnail};
+
+/// A page of a channel's community tab.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommunityPosts {
+	#[serde(rename = "authorId")]
+	pub author_id: String,
+	#[serde(rename = "comments")]
+	pub posts: Vec<CommunityPost>,
+	pub continuation: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommunityPost {
+	#[serde(rename = "commentId")]
+	pub id: String,
+	pub author: String,
+	#[serde(rename = "authorId")]
+	pub author_id: String,
+	#[serde(rename = "authorUrl")]
+	pub author_url: String,
+	#[serde(rename = "authorThumbnails")]
+	pub author_thumbnails: Vec<CommonImage>,
+
+	pub content: String,
+	#[serde(rename = "contentHtml")]
+	pub content_html: String,
+	#[serde(rename = "isEdited")]
+	#[serde(default)]
+	pub edited: bool,
+	#[serde(rename = "likeCount")]
+	pub likes: u32,
+	#[serde(rename = "replyCount")]
+	#[serde(default)]
+	pub replies: u32,
+	pub published: u64,
+	#[serde(rename = "publishedText")]
+	pub published_text: String,
+
+	#[serde(default)]
+	pub attachment: Option<PostAttachment>,
+}
+
+impl PartialEq for CommunityPost {
+	fn eq(&self, other: &Self) -> bool {
+		self.id == other.id
+	}
+}
+
+/// Media attached to a community post. Attachment types we can't display become `Unknown`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PostAttachment {
+	Video(PostVideo),
+	Image {
+		#[serde(rename = "imageThumbnails")]
+		thumbnails: Vec<CommonImage>,
+	},
+	MultiImage {
+		images: Vec<Vec<CommonImage>>,
+	},
+	Poll {
+		#[serde(rename = "totalVotes")]
+		total_votes: u64,
+		choices: Vec<PollChoice>,
+	},
+	Playlist(PostPlaylist),
+	#[serde(other)]
+	Unknown,
+}
+
+/// A video shared in a post. Only the id is guaranteed, the rest is missing for removed videos.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PostVideo {
+	#[serde(rename = "videoId")]
+	pub id: String,
+	#[serde(default)]
+	pub title: String,
+	#[serde(rename = "videoThumbnails")]
+	#[serde(default)]
+	pub thumbnails: Vec<CommonThumbnail>,
+	#[serde(rename = "lengthSeconds")]
+	#[serde(default)]
+	pub length: u32,
+	#[serde(default)]
+	pub author: String,
+	#[serde(rename = "authorId")]
+	#[serde(default)]
+	pub author_id: String,
+	#[serde(rename = "viewCountText")]
+	#[serde(default)]
+	pub views_text: String,
+	#[serde(rename = "publishedText")]
+	#[serde(default)]
+	pub published_text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PostPlaylist {
+	#[serde(rename = "playlistId")]
+	pub id: String,
+	#[serde(default)]
+	pub title: String,
+	#[serde(rename = "playlistThumbnail")]
+	#[serde(default)]
+	pub thumbnail: String,
+	#[serde(default)]
+	pub author: String,
+	#[serde(rename = "authorId")]
+	#[serde(default)]
+	pub author_id: String,
+	#[serde(rename = "videoCount")]
+	#[serde(default)]
+	pub video_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PollChoice {
+	pub text: String,
+	#[serde(default)]
+	pub image: Vec<CommonImage>,
+}
@@ -1,17 +1,18 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch, ChannelVideos, CommonThumbnail, CommonVideo};
+use crate::{fetch, transport::Transport, ChannelVideos, CommonThumbnail, CommonVideo};
 
 /// Fetching feeds via RSS is currently broken due to CORS restrictions in regular browsers.
 impl Feed {
 	pub async fn fetch_videos_from_feed(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 	) -> Result<ChannelVideos, RustyTubeError> {
 		let playlist_id = id.replace("UC", "UULF");
 		let url = format!("{}/feed/playlist/{}/", server, playlist_id);
-		let response = fetch(&url).await?;
+		let response = fetch(transport, &url).await?;
 		let feed: Feed = serde_xml_rs::from_str(&response)?;
 		Ok(feed.into())
 	}
@@ -1,9 +1,9 @@
 mod channel;
-mod comments;
+mod community;
 mod feed;
 mod search;
 
 pub use channel::*;
-pub use comments::*;
+pub use community::*;
 pub use feed::*;
 pub use search::*;
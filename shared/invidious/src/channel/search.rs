@@ -1,22 +1,38 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch::fetch, hidden::*};
+use crate::{fetch::fetch, hidden::*, transport::Transport};
 
+/// One page of results from searching within a single channel.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelSearch {
 	pub items: Vec<SearchResult>,
 }
 
 impl ChannelSearch {
-	fn url(server: &str, args: &str) -> String {
-		format!("{}/api/v1/channels/search/{}/", server, args)
+	fn url(server: &str, id: &str, query: &str, page: u32, lang: &str) -> String {
+		format!(
+			"{}/api/v1/channels/search/{}?q={}&page={}&hl={}",
+			server,
+			id,
+			urlencoding::encode(query),
+			page,
+			lang
+		)
 	}
 
-	async fn search(server: &str, args: &str) -> Result<Self, RustyTubeError> {
-		let search_url: String = Self::url(server, args);
-		let search_json: String = fetch(&search_url).await?;
-		let search: Self = serde_json::from_str(&search_json)?;
-		Ok(search)
+	/// Pages start at 1. An empty page means there are no more results.
+	pub async fn search(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+		query: &str,
+		page: u32,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
+		let search_url: String = Self::url(server, id, query, page, lang);
+		let search_json: String = fetch(transport, &search_url).await?;
+		let items: Vec<SearchResult> = serde_json::from_str(&search_json)?;
+		Ok(Self { items })
 	}
 }
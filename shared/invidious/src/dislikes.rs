@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::fetch;
+use crate::{fetch, transport::Transport};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -16,9 +16,12 @@ pub struct Dislikes {
 }
 
 impl Dislikes {
-	pub async fn fetch_dislikes(id: &str) -> Result<u64, RustyTubeError> {
+	pub async fn fetch_dislikes(
+		transport: &impl Transport,
+		id: &str,
+	) -> Result<u64, RustyTubeError> {
 		let url = format!("https://returnyoutubedislikeapi.com/votes?videoId={}", id);
-		let dislike_info = serde_json::from_str::<Self>(&fetch(&url).await?)?;
+		let dislike_info = serde_json::from_str::<Self>(&fetch(transport, &url).await?)?;
 		Ok(dislike_info.dislikes)
 	}
 }
@@ -0,0 +1,98 @@
+use rustytube_error::RustyTubeError;
+use serde::Deserialize;
+
+use crate::transport::TransportResponse;
+
+/// An error reported by an Invidious instance, either through its HTTP status or through an
+/// `{"error": "..."}` body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+	VideoUnavailable(String),
+	AgeRestricted(String),
+	RegionBlocked(String),
+	RateLimited,
+	ApiDisabled,
+	Instance { status: u16, message: String },
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+	error: String,
+}
+
+impl ApiError {
+	/// Returns the error described by the response, or `None` if the response carries data.
+	pub fn from_response(response: &TransportResponse) -> Option<Self> {
+		let status = response.status;
+		let message = serde_json::from_str::<ErrorBody>(&response.body).ok().map(|body| body.error);
+		let success = (200..300).contains(&status);
+
+		// Statuses that only ever mean the instance itself is struggling, whatever the body says.
+		match status {
+			429 => return Some(Self::RateLimited),
+			502..=504 => {
+				return Some(Self::Instance { status, message: message.unwrap_or_default() })
+			}
+			_ => (),
+		}
+		match message {
+			Some(message) => Some(Self::from_message(status, message)),
+			None if success => None,
+			None if status == 403 => Some(Self::ApiDisabled),
+			None => Some(Self::Instance { status, message: String::new() }),
+		}
+	}
+
+	fn from_message(status: u16, message: String) -> Self {
+		let lowercase = message.to_lowercase();
+		let contains_any = |patterns: &[&str]| patterns.iter().any(|p| lowercase.contains(p));
+
+		if contains_any(&["confirm your age", "age-restricted", "inappropriate for some users"]) {
+			Self::AgeRestricted(message)
+		} else if contains_any(&["your country", "in your region"]) {
+			Self::RegionBlocked(message)
+		} else if contains_any(&["service unavailable", "temporarily unavailable"]) {
+			Self::Instance { status, message }
+		} else if contains_any(&["unavailable", "private", "removed", "does not exist"]) {
+			Self::VideoUnavailable(message)
+		} else if contains_any(&["api is disabled", "disabled this endpoint"]) {
+			Self::ApiDisabled
+		} else {
+			Self::Instance { status, message }
+		}
+	}
+
+	/// Converts the error, keeping the status the instance answered with as its source.
+	pub fn into_error(self, status: u16) -> RustyTubeError {
+		let is_instance_error = matches!(self, Self::Instance { .. });
+		let error: RustyTubeError = self.into();
+		match is_instance_error {
+			true => error,
+			false => error.with_source(RustyTubeError::instance_error(status, "")),
+		}
+	}
+
+	/// Whether the same request is worth retrying, possibly on another instance.
+	pub fn is_retryable(&self) -> bool {
+		match self {
+			Self::RateLimited | Self::ApiDisabled => true,
+			Self::Instance { status, .. } => *status >= 500,
+			_ => false,
+		}
+	}
+}
+
+impl From<ApiError> for RustyTubeError {
+	fn from(api_error: ApiError) -> Self {
+		match api_error {
+			ApiError::VideoUnavailable(reason) => RustyTubeError::video_unavailable(&reason),
+			ApiError::AgeRestricted(reason) => RustyTubeError::age_restricted(&reason),
+			ApiError::RegionBlocked(reason) => RustyTubeError::region_blocked(&reason),
+			ApiError::RateLimited => RustyTubeError::rate_limited(),
+			ApiError::ApiDisabled => RustyTubeError::api_disabled(),
+			ApiError::Instance { status, message } => {
+				RustyTubeError::instance_error(status, &message)
+			}
+		}
+	}
+}
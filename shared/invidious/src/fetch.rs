@@ -1,9 +1,11 @@
-use reqwasm::http::{Method, Request, RequestMode};
 use rustytube_error::RustyTubeError;
-use web_sys::RequestCache;
 
-pub async fn fetch(url: &str) -> Result<String, RustyTubeError> {
-	let request =
-		Request::new(&url).mode(RequestMode::Cors).method(Method::GET).cache(RequestCache::Default);
-	Ok(request.send().await?.text().await?)
+use crate::{error::ApiError, transport::Transport};
+
+pub async fn fetch<T: Transport>(transport: &T, url: &str) -> Result<String, RustyTubeError> {
+	let response = transport.get(url).await?;
+	match ApiError::from_response(&response) {
+		Some(api_error) => Err(api_error.into_error(response.status)),
+		None => Ok(response.body),
+	}
 }
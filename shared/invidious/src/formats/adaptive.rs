@@ -25,6 +25,8 @@ pub struct AdaptiveFormat {
 	pub container: Option<Container>,
 	pub encoding: Option<String>,
 	pub resolution: Option<Resolution>,
+	/// Frame dimensions as `WIDTHxHEIGHT`, only present for video formats.
+	pub size: Option<String>,
 	pub quality_label: Option<VideoQuality>,
 	pub color_info: Option<ColorInfo>,
 }
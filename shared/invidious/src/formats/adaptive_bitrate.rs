@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+
+use crate::formats::{VideoFormat, VideoQuality};
+
+/// Fraction of the estimated bandwidth a format's bitrate may use, leaving room for audio and
+/// for throughput dips.
+const BANDWIDTH_SAFETY: f64 = 0.75f64;
+/// Seconds of buffered media below which the quality is lowered right away.
+const LOW_BUFFER: f64 = 8f64;
+/// Seconds of buffered media needed before the quality is raised.
+const HEALTHY_BUFFER: f64 = 15f64;
+const FAST_HALF_LIFE: f64 = 2f64;
+const SLOW_HALF_LIFE: f64 = 5f64;
+/// Estimates from fewer bytes than this are dominated by request latency.
+const MIN_SAMPLED_BYTES: f64 = 128_000f64;
+
+/// Throughput measured from segment downloads, in bits per second.
+///
+/// Keeps a fast and a slow moving average, weighted by download time, and reports the lower of
+/// the two so drops are reacted to quickly while spikes are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BandwidthEstimate {
+	fast: MovingAverage,
+	slow: MovingAverage,
+	sampled_bytes: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct MovingAverage {
+	estimate: f64,
+	total_weight: f64,
+}
+
+impl MovingAverage {
+	fn sample(&mut self, half_life: f64, weight: f64, value: f64) {
+		let alpha = 0.5f64.powf(weight / half_life);
+		self.estimate = value * (1f64 - alpha) + alpha * self.estimate;
+		self.total_weight += weight;
+	}
+
+	/// Corrects the bias towards zero of an average that started from nothing.
+	fn value(&self, half_life: f64) -> f64 {
+		let zero_factor = 1f64 - 0.5f64.powf(self.total_weight / half_life);
+		self.estimate / zero_factor
+	}
+}
+
+impl BandwidthEstimate {
+	/// Records that `bytes` were downloaded in `seconds`.
+	pub fn sample(&mut self, bytes: u64, seconds: f64) {
+		if seconds <= 0f64 {
+			return;
+		}
+		let bits_per_second = bytes as f64 * 8f64 / seconds;
+		self.fast.sample(FAST_HALF_LIFE, seconds, bits_per_second);
+		self.slow.sample(SLOW_HALF_LIFE, seconds, bits_per_second);
+		self.sampled_bytes += bytes as f64;
+	}
+
+	/// The estimated bandwidth in bits per second, once enough has been downloaded to tell.
+	pub fn bits_per_second(&self) -> Option<f64> {
+		(self.sampled_bytes >= MIN_SAMPLED_BYTES)
+			.then(|| self.fast.value(FAST_HALF_LIFE).min(self.slow.value(SLOW_HALF_LIFE)))
+	}
+}
+
+/// The video formats an "Auto" quality can step through: those sharing the playing format's
+/// container and codec, so they can be switched to without reloading, up to a maximum quality.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityLadder {
+	/// Sorted from the lowest to the highest bitrate.
+	pub formats: Vec<VideoFormat>,
+}
+
+impl QualityLadder {
+	pub fn new(formats: &[VideoFormat], current: &VideoFormat, max: &VideoQuality) -> Self {
+		let codec_family =
+			|format: &VideoFormat| format.r#type.split('.').next().unwrap_or_default().to_string();
+		let mut formats = formats
+			.iter()
+			.filter(|format| codec_family(format) == codec_family(current))
+			.filter(|format| format.resolution.height() <= max.height())
+			.cloned()
+			.collect::<Vec<VideoFormat>>();
+		formats.sort_by_key(bitrate);
+		Self { formats }
+	}
+
+	/// The format to play next, given the estimated bandwidth in bits per second and how many
+	/// seconds are buffered ahead. Drops straight to a format the bandwidth can sustain when the
+	/// buffer runs low, and only steps up one quality at a time while it is healthy.
+	pub fn next(
+		&self,
+		current: &VideoFormat,
+		bandwidth: f64,
+		buffered_ahead: f64,
+	) -> Option<&VideoFormat> {
+		let current_index = self.formats.iter().position(|format| format == current);
+		let sustainable = self
+			.formats
+			.iter()
+			.rposition(|format| bitrate(format) as f64 <= bandwidth * BANDWIDTH_SAFETY)
+			.unwrap_or_default();
+
+		let Some(current_index) = current_index else {
+			// The playing format is above the maximum quality or from another codec.
+			return self.formats.get(sustainable);
+		};
+		let next_index = match sustainable.cmp(&current_index) {
+			Ordering::Less if buffered_ahead < LOW_BUFFER => sustainable,
+			Ordering::Greater if buffered_ahead >= HEALTHY_BUFFER => current_index + 1,
+			_ => current_index,
+		};
+		self.formats.get(next_index)
+	}
+}
+
+fn bitrate(format: &VideoFormat) -> u64 {
+	format.bitrate.parse().unwrap_or_default()
+}
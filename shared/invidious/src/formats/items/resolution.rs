@@ -39,3 +39,19 @@ impl fmt::Display for Resolution {
 		}
 	}
 }
+
+impl Resolution {
+	pub fn height(&self) -> u32 {
+		match self {
+			Resolution::_144p => 144,
+			Resolution::_240p => 240,
+			Resolution::_360p => 360,
+			Resolution::_480p => 480,
+			Resolution::_720p => 720,
+			Resolution::_1080p => 1080,
+			Resolution::_1440p => 1440,
+			Resolution::_2160p => 2160,
+			Resolution::_4320p => 4320,
+		}
+	}
+}
@@ -91,3 +91,19 @@ impl FromStr for VideoQuality {
 		}
 	}
 }
+
+impl VideoQuality {
+	pub fn height(&self) -> u32 {
+		match self {
+			VideoQuality::_144p | VideoQuality::_144p60 => 144,
+			VideoQuality::_240p | VideoQuality::_240p60 => 240,
+			VideoQuality::_360p | VideoQuality::_360p60 => 360,
+			VideoQuality::_480p | VideoQuality::_480p60 => 480,
+			VideoQuality::_720p | VideoQuality::_720p60 => 720,
+			VideoQuality::_1080p | VideoQuality::_1080p60 => 1080,
+			VideoQuality::_1440p | VideoQuality::_1440p60 => 1440,
+			VideoQuality::_2160p | VideoQuality::_2160p60 => 2160,
+			VideoQuality::_4320p | VideoQuality::_4320p60 => 4320,
+		}
+	}
+}
@@ -0,0 +1,172 @@
+use std::fmt::Write;
+
+use html_escape::{encode_double_quoted_attribute, encode_text};
+
+use crate::formats::AdaptiveFormat;
+
+const AUDIO_CHANNEL_SCHEME: &str = "urn:mpeg:dash:23003:3:audio_channel_configuration:2011";
+
+/// A static MPEG-DASH manifest for the adaptive formats of a video, using the on-demand profile so
+/// players fetch each representation by byte range from its `init` and `index` ranges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DashManifest {
+	/// Length of the video in seconds.
+	pub duration: f64,
+	pub adaptation_sets: Vec<AdaptationSet>,
+}
+
+/// Representations a player can switch between, which share a mime type and codec family.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptationSet {
+	pub mime_type: String,
+	/// The codec without its profile, e.g. `avc1` for `avc1.640028`.
+	pub codec_family: String,
+	pub representations: Vec<AdaptiveFormat>,
+}
+
+impl DashManifest {
+	/// Groups formats into adaptation sets, audio first, keeping the order Invidious lists them in.
+	/// Formats without byte ranges can't be addressed by a DASH player and are skipped.
+	pub fn new(formats: &[AdaptiveFormat], duration: f64) -> Self {
+		let mut adaptation_sets: Vec<AdaptationSet> = vec![];
+
+		formats
+			.iter()
+			.filter(|format| !format.init.is_empty() && !format.index.is_empty())
+			.for_each(|format| {
+				let (mime_type, codecs) = split_type(&format.r#type);
+				let codec_family = codecs.split('.').next().unwrap_or_default().to_string();
+				let existing = adaptation_sets
+					.iter_mut()
+					.find(|set| set.mime_type == mime_type && set.codec_family == codec_family);
+				match existing {
+					Some(set) => set.representations.push(format.clone()),
+					None => adaptation_sets.push(AdaptationSet {
+						mime_type: mime_type.to_string(),
+						codec_family,
+						representations: vec![format.clone()],
+					}),
+				}
+			});
+		adaptation_sets.sort_by_key(|set| !set.is_audio());
+
+		Self { duration, adaptation_sets }
+	}
+
+	pub fn to_xml(&self) -> String {
+		let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+		let _ = writeln!(
+			xml,
+			"<MPD xmlns=\"urn:mpeg:dash:schema:mpd:2011\" \
+			 profiles=\"urn:mpeg:dash:profile:isoff-on-demand:2011\" type=\"static\" \
+			 minBufferTime=\"PT1.5S\" mediaPresentationDuration=\"PT{}S\">",
+			self.duration
+		);
+		xml.push_str("\t<Period>\n");
+		self.adaptation_sets.iter().enumerate().for_each(|(id, set)| set.write_xml(&mut xml, id));
+		xml.push_str("\t</Period>\n");
+		xml.push_str("</MPD>\n");
+		xml
+	}
+}
+
+impl AdaptationSet {
+	pub fn is_audio(&self) -> bool {
+		self.mime_type.starts_with("audio/")
+	}
+
+	fn write_xml(&self, xml: &mut String, id: usize) {
+		let _ = write!(
+			xml,
+			"\t\t<AdaptationSet id=\"{}\" mimeType=\"{}\" contentType=\"{}\" \
+			 subsegmentAlignment=\"true\" subsegmentStartsWithSAP=\"1\"",
+			id,
+			encode_double_quoted_attribute(&self.mime_type),
+			match self.is_audio() {
+				true => "audio",
+				false => "video",
+			}
+		);
+		if !self.is_audio() {
+			xml.push_str(" scanType=\"progressive\"");
+		}
+		xml.push_str(">\n");
+
+		self.representations.iter().for_each(|format| {
+			let (_, codecs) = split_type(&format.r#type);
+			let _ = write!(
+				xml,
+				"\t\t\t<Representation id=\"{}\" codecs=\"{}\" bandwidth=\"{}\"",
+				encode_double_quoted_attribute(&format.itag),
+				encode_double_quoted_attribute(codecs),
+				encode_double_quoted_attribute(&format.bitrate)
+			);
+			match self.is_audio() {
+				true => {
+					if let Some(sample_rate) = format.audio_sample_rate {
+						let _ = write!(xml, " audioSamplingRate=\"{}\"", sample_rate);
+					}
+				}
+				false => {
+					let (width, height) = dimensions(format);
+					if let Some(width) = width {
+						let _ = write!(xml, " width=\"{}\"", width);
+					}
+					if let Some(height) = height {
+						let _ = write!(xml, " height=\"{}\"", height);
+					}
+					if let Some(fps) = format.fps {
+						let _ = write!(xml, " frameRate=\"{}\"", fps);
+					}
+				}
+			}
+			xml.push_str(">\n");
+
+			if let Some(channels) = format.audio_channels {
+				let _ = writeln!(
+					xml,
+					"\t\t\t\t<AudioChannelConfiguration schemeIdUri=\"{}\" value=\"{}\"/>",
+					AUDIO_CHANNEL_SCHEME, channels
+				);
+			}
+			let _ = writeln!(xml, "\t\t\t\t<BaseURL>{}</BaseURL>", encode_text(&format.url));
+			let _ = writeln!(
+				xml,
+				"\t\t\t\t<SegmentBase indexRange=\"{}\">",
+				encode_double_quoted_attribute(&format.index)
+			);
+			let _ = writeln!(
+				xml,
+				"\t\t\t\t\t<Initialization range=\"{}\"/>",
+				encode_double_quoted_attribute(&format.init)
+			);
+			xml.push_str("\t\t\t\t</SegmentBase>\n");
+			xml.push_str("\t\t\t</Representation>\n");
+		});
+
+		xml.push_str("\t\t</AdaptationSet>\n");
+	}
+}
+
+/// Splits `video/mp4; codecs="avc1.640028"` into its mime type and codecs.
+fn split_type(r#type: &str) -> (&str, &str) {
+	let (mime_type, params) = r#type.split_once(';').unwrap_or((r#type, ""));
+	let codecs = params
+		.split(';')
+		.find_map(|param| param.trim().strip_prefix("codecs="))
+		.unwrap_or_default()
+		.trim_matches('"');
+	(mime_type.trim(), codecs)
+}
+
+/// Uses the exact frame size when Invidious gives one, otherwise only the height is known.
+fn dimensions(format: &AdaptiveFormat) -> (Option<u32>, Option<u32>) {
+	let size = format.size.as_deref().and_then(|size| {
+		let (width, height) = size.split_once('x')?;
+		Some((width.parse().ok()?, height.parse().ok()?))
+	});
+	match size {
+		Some((width, height)) => (Some(width), Some(height)),
+		None => (None, format.resolution.as_ref().map(|resolution| resolution.height())),
+	}
+}
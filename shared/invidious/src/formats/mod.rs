@@ -1,15 +1,21 @@
 mod adaptive;
+mod adaptive_bitrate;
 mod audio;
 mod dash;
 mod format;
 mod items;
 mod legacy;
+mod manifest;
+mod segments;
 mod video;
 
 pub use adaptive::*;
+pub use adaptive_bitrate::*;
 pub use audio::*;
 pub use dash::*;
 pub use format::*;
 pub use items::*;
 pub use legacy::*;
+pub use manifest::*;
+pub use segments::*;
 pub use video::*;
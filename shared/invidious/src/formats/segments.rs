@@ -0,0 +1,314 @@
+use std::str::FromStr;
+
+use rustytube_error::RustyTubeError;
+
+const SIDX: &[u8; 4] = b"sidx";
+const EBML_SEGMENT: u32 = 0x1853_8067;
+const EBML_INFO: u32 = 0x1549_A966;
+const EBML_TIMECODE_SCALE: u32 = 0x2A_D7B1;
+const EBML_DURATION: u32 = 0x4489;
+const EBML_CUES: u32 = 0x1C53_BB6B;
+const EBML_CUE_POINT: u32 = 0xBB;
+const EBML_CUE_TIME: u32 = 0xB3;
+const EBML_CUE_TRACK_POSITIONS: u32 = 0xB7;
+const EBML_CUE_CLUSTER_POSITION: u32 = 0xF1;
+/// Matroska's default: timestamps are in milliseconds.
+const DEFAULT_TIMECODE_SCALE: u64 = 1_000_000;
+
+/// An inclusive byte range, such as the `init` and `index` ranges of an adaptive format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+	pub start: u64,
+	pub end: u64,
+}
+
+impl ByteRange {
+	pub fn new(start: u64, end: u64) -> Self {
+		Self { start, end }
+	}
+
+	pub fn len(&self) -> u64 {
+		self.end + 1 - self.start
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.end < self.start
+	}
+}
+
+impl FromStr for ByteRange {
+	type Err = RustyTubeError;
+
+	/// Parses `start-end`, e.g. `741-1684`.
+	fn from_str(range: &str) -> Result<Self, Self::Err> {
+		let (start, end) = range.split_once('-').ok_or(RustyTubeError::format_parse())?;
+		let start = start.trim().parse().map_err(|_| RustyTubeError::format_parse())?;
+		let end = end.trim().parse().map_err(|_| RustyTubeError::format_parse())?;
+		Ok(Self { start, end })
+	}
+}
+
+/// A chunk of an adaptive format that can be appended to a media buffer on its own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+	/// Seconds into the video at which the segment starts.
+	pub start: f64,
+	pub end: f64,
+	pub bytes: ByteRange,
+}
+
+/// Where each segment of an adaptive format starts, read from the box or element found in the
+/// format's `index` range: a `sidx` box for MP4 and the `Cues` element for WebM.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SegmentIndex {
+	pub segments: Vec<Segment>,
+}
+
+impl SegmentIndex {
+	/// Parses the index of a format from its first bytes, which must cover both its `init` and
+	/// `index` ranges. `clen` is the size of the whole format in bytes.
+	pub fn parse(
+		r#type: &str,
+		bytes: &[u8],
+		index: ByteRange,
+		clen: u64,
+	) -> Result<Self, RustyTubeError> {
+		match r#type.contains("webm") {
+			true => Self::parse_cues(bytes, index, clen),
+			false => {
+				let index_bytes = bytes
+					.get(index.start as usize..=index.end as usize)
+					.ok_or(RustyTubeError::segment_index_parse("the index range is missing"))?;
+				Self::parse_sidx(index_bytes, index.start)
+			}
+		}
+	}
+
+	/// Parses an MP4 `sidx` box that starts `offset` bytes into the format.
+	pub fn parse_sidx(bytes: &[u8], offset: u64) -> Result<Self, RustyTubeError> {
+		let mut reader = Reader::new(bytes);
+		let box_size = reader.uint(4)?;
+		if reader.take(4)? != SIDX {
+			return Err(RustyTubeError::segment_index_parse("expected a sidx box"));
+		}
+		let version = reader.uint(1)?;
+		reader.skip(3 + 4)?;
+		let timescale = reader.uint(4)? as f64;
+		let (earliest_time, first_offset) = match version {
+			0 => (reader.uint(4)?, reader.uint(4)?),
+			_ => (reader.uint(8)?, reader.uint(8)?),
+		};
+		reader.skip(2)?;
+		let reference_count = reader.uint(2)?;
+		if timescale == 0f64 {
+			return Err(RustyTubeError::segment_index_parse("the timescale is zero"));
+		}
+
+		let mut time = earliest_time;
+		let mut byte = offset + box_size + first_offset;
+		let segments = (0..reference_count)
+			.map(|_| {
+				let size = reader.uint(4)? & 0x7FFF_FFFF;
+				let duration = reader.uint(4)?;
+				reader.skip(4)?;
+				let segment = Segment {
+					start: time as f64 / timescale,
+					end: (time + duration) as f64 / timescale,
+					bytes: ByteRange::new(byte, (byte + size).saturating_sub(1)),
+				};
+				time += duration;
+				byte += size;
+				Ok(segment)
+			})
+			.collect::<Result<Vec<Segment>, RustyTubeError>>()?;
+
+		Ok(Self { segments })
+	}
+
+	/// Parses the WebM `Cues` element in `index`. Cluster positions are relative to the start of
+	/// the `Segment` element's data and the last cue's end comes from the `Info` element, so
+	/// `bytes` must also cover the header in the `init` range.
+	pub fn parse_cues(bytes: &[u8], index: ByteRange, clen: u64) -> Result<Self, RustyTubeError> {
+		// The last cue's segment ends with the format.
+		if clen == 0 {
+			return Err(RustyTubeError::segment_index_parse("the format size is unknown"));
+		}
+		let mut reader = Reader::new(bytes);
+		let segment_data_start = loop {
+			let (id, size) = reader.element_header()?;
+			match id {
+				EBML_SEGMENT => break reader.position,
+				_ => reader.skip(size)?,
+			}
+		};
+
+		let mut timecode_scale = DEFAULT_TIMECODE_SCALE;
+		let mut duration = None;
+		while (reader.position as u64) < index.start {
+			let (id, size) = reader.element_header()?;
+			match id {
+				EBML_INFO => {
+					let mut info = reader.child(size)?;
+					while !info.is_done() {
+						let (id, size) = info.element_header()?;
+						match id {
+							EBML_TIMECODE_SCALE => timecode_scale = info.uint(size as usize)?,
+							EBML_DURATION => duration = Some(info.float(size as usize)?),
+							_ => info.skip(size)?,
+						}
+					}
+				}
+				_ => reader.skip(size)?,
+			}
+		}
+
+		let mut cues = Reader::new(
+			bytes
+				.get(index.start as usize..=index.end as usize)
+				.ok_or(RustyTubeError::segment_index_parse("the index range is missing"))?,
+		);
+		let (id, size) = cues.element_header()?;
+		if id != EBML_CUES {
+			return Err(RustyTubeError::segment_index_parse("expected a Cues element"));
+		}
+		let mut cues = cues.child(size)?;
+		let mut cue_points: Vec<(u64, u64)> = vec![];
+		while !cues.is_done() {
+			let (id, size) = cues.element_header()?;
+			if id != EBML_CUE_POINT {
+				cues.skip(size)?;
+				continue;
+			}
+			let mut cue_point = cues.child(size)?;
+			let (mut time, mut position) = (None, None);
+			while !cue_point.is_done() {
+				let (id, size) = cue_point.element_header()?;
+				match id {
+					EBML_CUE_TIME => time = Some(cue_point.uint(size as usize)?),
+					EBML_CUE_TRACK_POSITIONS => {
+						let mut positions = cue_point.child(size)?;
+						while !positions.is_done() {
+							let (id, size) = positions.element_header()?;
+							match id {
+								EBML_CUE_CLUSTER_POSITION => {
+									position = Some(positions.uint(size as usize)?)
+								}
+								_ => positions.skip(size)?,
+							}
+						}
+					}
+					_ => cue_point.skip(size)?,
+				}
+			}
+			if let (Some(time), Some(position)) = (time, position) {
+				cue_points.push((time, segment_data_start as u64 + position));
+			}
+		}
+
+		let seconds = |time: f64| time * timecode_scale as f64 / 1_000_000_000f64;
+		let last_end = duration.map(seconds);
+		let segments = cue_points
+			.iter()
+			.enumerate()
+			.map(|(index, (time, position))| {
+				let next = cue_points.get(index + 1);
+				Segment {
+					start: seconds(*time as f64),
+					end: next
+						.map(|(time, _)| seconds(*time as f64))
+						.or(last_end)
+						.unwrap_or(seconds(*time as f64)),
+					bytes: ByteRange::new(
+						*position,
+						next.map(|(_, position)| *position).unwrap_or(clen) - 1,
+					),
+				}
+			})
+			.collect();
+
+		Ok(Self { segments })
+	}
+
+	/// The index of the segment playing at `time`.
+	pub fn segment_at(&self, time: f64) -> Option<usize> {
+		self.segments.iter().position(|segment| segment.start <= time && time < segment.end)
+	}
+
+	/// Seconds from the start of the first segment to the end of the last.
+	pub fn duration(&self) -> f64 {
+		self.segments.last().map(|segment| segment.end).unwrap_or_default()
+	}
+}
+
+/// Reads big-endian integers and EBML variable-length integers.
+struct Reader<'a> {
+	bytes: &'a [u8],
+	position: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn new(bytes: &'a [u8]) -> Self {
+		Self { bytes, position: 0 }
+	}
+
+	fn is_done(&self) -> bool {
+		self.position >= self.bytes.len()
+	}
+
+	fn take(&mut self, count: usize) -> Result<&'a [u8], RustyTubeError> {
+		let end = self.position.checked_add(count);
+		let bytes = end
+			.and_then(|end| self.bytes.get(self.position..end))
+			.ok_or(RustyTubeError::segment_index_parse("the index ended unexpectedly"))?;
+		self.position += count;
+		Ok(bytes)
+	}
+
+	/// Skips `count` bytes, which may be an element's unknown size of up to 2^56 - 1.
+	fn skip(&mut self, count: u64) -> Result<(), RustyTubeError> {
+		self.take(Self::to_usize(count)?).map(|_| ())
+	}
+
+	fn uint(&mut self, count: usize) -> Result<u64, RustyTubeError> {
+		Ok(self.take(count)?.iter().fold(0u64, |value, byte| value << 8 | *byte as u64))
+	}
+
+	fn float(&mut self, count: usize) -> Result<f64, RustyTubeError> {
+		let bits = self.uint(count)?;
+		match count {
+			4 => Ok(f32::from_bits(bits as u32) as f64),
+			8 => Ok(f64::from_bits(bits)),
+			_ => Err(RustyTubeError::segment_index_parse("invalid float size")),
+		}
+	}
+
+	/// A reader over the next `size` bytes, which are skipped in this reader.
+	fn child(&mut self, size: u64) -> Result<Reader<'a>, RustyTubeError> {
+		Ok(Reader::new(self.take(Self::to_usize(size)?)?))
+	}
+
+	/// Sizes past the address space, e.g. on wasm32, can't fit in the index anyway.
+	fn to_usize(size: u64) -> Result<usize, RustyTubeError> {
+		usize::try_from(size)
+			.map_err(|_| RustyTubeError::segment_index_parse("the index ended unexpectedly"))
+	}
+
+	/// Reads an element's ID, which keeps its length marker, and its data size, which doesn't.
+	fn element_header(&mut self) -> Result<(u32, u64), RustyTubeError> {
+		let (id, _) = self.vint()?;
+		let (size, length) = self.vint()?;
+		let marker = 1u64 << (7 * length);
+		Ok((id as u32, size & (marker - 1)))
+	}
+
+	/// Returns the raw value of a variable-length integer and its length in bytes.
+	fn vint(&mut self) -> Result<(u64, usize), RustyTubeError> {
+		let first = self.uint(1)?;
+		let length = (first as u8).leading_zeros() as usize + 1;
+		if length > 8 {
+			return Err(RustyTubeError::segment_index_parse("invalid variable-length integer"));
+		}
+		let rest = self.uint(length - 1)?;
+		Ok((first << (8 * (length - 1)) | rest, length))
+	}
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use futures::future::join_all;
+
+use crate::{
+	instance::{InstanceInfo, Instances},
+	transport::Transport,
+};
+
+const UPTIME_WEIGHT: f64 = 0.5;
+const LATENCY_WEIGHT: f64 = 0.4;
+const REGION_WEIGHT: f64 = 0.1;
+/// Latency at which an instance earns half of the latency score.
+const REFERENCE_LATENCY_MS: f64 = 400.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedInstance {
+	pub name: String,
+	pub info: InstanceInfo,
+	pub latency: Duration,
+	pub score: f64,
+}
+
+/// Pings `/api/v1/stats` on the server and returns how long it took to answer successfully.
+pub async fn probe_instance(transport: &impl Transport, server: &str) -> Option<Duration> {
+	let url = format!("{}/api/v1/stats", server.trim_end_matches('/'));
+	let start = now_millis();
+	let response = transport.get(&url).await.ok()?;
+	let latency = Duration::from_secs_f64((now_millis() - start).max(0.0) / 1000.0);
+	(200..300).contains(&response.status).then_some(latency)
+}
+
+/// Probes every usable HTTPS instance concurrently and returns the reachable ones, best first.
+///
+/// `region` is an ISO 3166-1 alpha-2 code; instances hosted there get a small bonus.
+pub async fn rank_instances(
+	transport: &impl Transport,
+	instances: Instances,
+	region: Option<&str>,
+) -> Vec<RankedInstance> {
+	let candidates: Instances = instances
+		.into_iter()
+		.filter(|(_, info)| info.is_usable() && info.type_field.eq_ignore_ascii_case("https"))
+		.collect();
+	let probes =
+		join_all(candidates.iter().map(|(_, info)| probe_instance(transport, &info.uri))).await;
+
+	let mut ranked: Vec<RankedInstance> = candidates
+		.into_iter()
+		.zip(probes)
+		.filter_map(|((name, info), latency)| {
+			let latency = latency?;
+			let score = health_score(&info, latency, region);
+			Some(RankedInstance { name, info, latency, score })
+		})
+		.collect();
+	ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+	ranked
+}
+
+/// Combines monitor uptime, measured latency and region into a score between 0 and 1.
+pub fn health_score(info: &InstanceInfo, latency: Duration, region: Option<&str>) -> f64 {
+	let uptime = info.uptime().unwrap_or(0.5);
+	let latency_ms = latency.as_secs_f64() * 1000.0;
+	let latency_score = REFERENCE_LATENCY_MS / (REFERENCE_LATENCY_MS + latency_ms);
+	let region_score = match region.is_some_and(|region| info.region.eq_ignore_ascii_case(region)) {
+		true => 1.0,
+		false => 0.0,
+	};
+
+	UPTIME_WEIGHT * uptime + LATENCY_WEIGHT * latency_score + REGION_WEIGHT * region_score
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_millis() -> f64 {
+	web_sys::window()
+		.and_then(|window| window.performance())
+		.map(|performance| performance.now())
+		.unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_millis() -> f64 {
+	use std::{sync::OnceLock, time::Instant};
+
+	static EPOCH: OnceLock<Instant> = OnceLock::new();
+	EPOCH.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
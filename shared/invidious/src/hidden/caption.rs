@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Caption {
 	pub label: String,
-	#[serde(rename = "language_code")]
+	#[serde(rename = "languageCode", alias = "language_code")]
 	#[serde(default)]
 	pub language: String,
 	pub url: String,
@@ -13,6 +13,16 @@ impl Caption {
 	pub fn url(&self, server: &str) -> String {
 		format!("{}{}", server, self.url)
 	}
+
+	/// The url of this track machine translated into `tlang`, e.g. `fr`.
+	pub fn translated_url(&self, server: &str, tlang: &str) -> String {
+		let separator = if self.url.contains('?') { '&' } else { '?' };
+		format!("{}{}{}tlang={}", server, self.url, separator, tlang)
+	}
+
+	pub fn is_auto_generated(&self) -> bool {
+		self.label.to_lowercase().contains("auto-generated")
+	}
 }
 
 impl PartialEq for Caption {
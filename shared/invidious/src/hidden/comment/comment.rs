@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{common::CommonImage, fetch};
+use crate::{common::CommonImage, fetch, transport::Transport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Comment {
@@ -68,6 +68,7 @@ pub struct Replies {
 }
 impl Replies {
 	pub async fn fetch_replies(
+		transport: &impl Transport,
 		continuation: &str,
 		server: &str,
 		id: &str,
@@ -75,7 +76,7 @@ impl Replies {
 	) -> Result<Replies, RustyTubeError> {
 		let comments_url =
 			format!("{}/api/v1/comments/{}?continuation={}&hl={}", server, id, continuation, lang);
-		let comments_json = fetch(&comments_url).await?;
+		let comments_json = fetch(transport, &comments_url).await?;
 		let replies: Replies = serde_json::from_str(&comments_json)?;
 		Ok(replies)
 	}
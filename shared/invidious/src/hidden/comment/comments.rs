@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch::fetch, Comment};
+use crate::{fetch::fetch, transport::Transport, Comment};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Comments {
@@ -19,6 +19,7 @@ impl Comments {
 	}
 
 	pub async fn fetch_comments(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
 		continuation: Option<&str>,
@@ -30,7 +31,7 @@ impl Comments {
 			}
 			None => format!("{server}/api/v1/comments/{id}"),
 		};
-		let comments_json = fetch(&comments_url).await?;
+		let comments_json = fetch(transport, &comments_url).await?;
 		let comments: Self = serde_json::from_str(&comments_json)?;
 		Ok(comments)
 	}
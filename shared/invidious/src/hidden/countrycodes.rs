@@ -1,9 +1,14 @@
 use std::{
 	fmt,
 	fmt::{Display, Formatter, Result},
+	str::FromStr,
 };
 
-use serde::{Deserialize, Serialize};
+use rustytube_error::RustyTubeError;
+use serde::{
+	de::{value::StrDeserializer, IntoDeserializer},
+	Deserialize, Serialize,
+};
 
 impl Display for CountryCode {
 	fn fmt(&self, f: &mut Formatter) -> Result {
@@ -11,6 +16,17 @@ impl Display for CountryCode {
 	}
 }
 
+impl FromStr for CountryCode {
+	type Err = RustyTubeError;
+
+	fn from_str(code: &str) -> std::result::Result<Self, Self::Err> {
+		let upper = code.to_uppercase();
+		let deserializer: StrDeserializer<serde::de::value::Error> =
+			upper.as_str().into_deserializer();
+		Self::deserialize(deserializer).map_err(|_| RustyTubeError::country_code_parse(code))
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum CountryCode {
 	AD,
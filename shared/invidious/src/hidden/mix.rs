@@ -2,18 +2,20 @@ use serde::{Deserialize, Serialize};
 
 use crate::common::CommonThumbnail;
 
+/// Video entry of a [`Mix`](crate::Mix), as specified in https://docs.invidious.io/api/#get-apiv1mixesrdid
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MixVideo {
-	title: String,
-	#[serde(rename = "mixId")]
-	id: String,
-	#[serde(rename = "audioId")]
-	audio_id: String,
-	author: String,
+	pub title: String,
+	#[serde(rename = "videoId")]
+	pub id: String,
+	pub author: String,
+	#[serde(rename = "authorId")]
+	pub author_id: String,
 	#[serde(rename = "authorUrl")]
-	author_url: String,
-	videos: Vec<CommonThumbnail>,
-	index: u32,
+	pub author_url: String,
+	#[serde(rename = "videoThumbnails")]
+	pub thumbnails: Vec<CommonThumbnail>,
+	pub index: u32,
 	#[serde(rename = "lengthSeconds")]
-	length: u32,
+	pub length: u32,
 }
@@ -12,3 +12,14 @@ pub enum SearchResult {
 	#[serde(rename = "channel")]
 	Channel(CommonChannel),
 }
+
+impl SearchResult {
+	/// The id of the video, playlist or channel found.
+	pub fn id(&self) -> &str {
+		match self {
+			SearchResult::Video(video) => &video.id,
+			SearchResult::Playlist(playlist) => &playlist.id,
+			SearchResult::Channel(channel) => &channel.id,
+		}
+	}
+}
@@ -17,3 +17,58 @@ pub struct Storyboard {
 	#[serde(default)]
 	pub storyboard_count: i16,
 }
+
+/// One frame of a storyboard, cropped out of a sprite sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoryboardTile {
+	pub sheet_url: String,
+	/// Offset of the tile from the sheet's top left corner in pixels.
+	pub x: u32,
+	pub y: u32,
+	pub width: u32,
+	pub height: u32,
+}
+
+impl Storyboard {
+	/// The storyboard with the largest frames, which gives the sharpest previews.
+	pub fn largest(storyboards: &[Storyboard]) -> Option<&Storyboard> {
+		storyboards.iter().max_by_key(|storyboard| storyboard.width * storyboard.height)
+	}
+
+	/// Finds the frame shown `time` seconds into a video lasting `duration` seconds.
+	///
+	/// Frames are laid out row by row, `storyboard_width` by `storyboard_height` per sheet, and
+	/// `$M` in the template url is replaced by the sheet number.
+	pub fn tile_at(&self, server: &str, time: f64, duration: f64) -> Option<StoryboardTile> {
+		let columns = self.storyboard_width as u32;
+		let rows = self.storyboard_height as u32;
+		if self.count <= 0 || columns == 0 || rows == 0 || self.template_url.is_empty() {
+			return None;
+		}
+		let count = self.count as u32;
+		// The lowest level has no interval, its frames are spread across the whole video.
+		let interval = match self.interval {
+			0 => duration * 1000f64 / count as f64,
+			interval => interval as f64,
+		};
+		if interval <= 0f64 {
+			return None;
+		}
+
+		let index = ((time.max(0f64) * 1000f64 / interval) as u32).min(count - 1);
+		let sheet = index / (columns * rows);
+		let position = index % (columns * rows);
+		let template_url = match self.template_url.starts_with('/') {
+			true => format!("{}{}", server, self.template_url),
+			false => self.template_url.clone(),
+		};
+
+		Some(StoryboardTile {
+			sheet_url: template_url.replace("$M", &sheet.to_string()),
+			x: position % columns * self.width,
+			y: position / columns * self.height,
+			width: self.width,
+			height: self.height,
+		})
+	}
+}
@@ -2,12 +2,12 @@ use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::fetch::fetch;
+use crate::{fetch::fetch, transport::Transport};
 
-pub const INSTANCES_API_LINK: &'static str = "https://api.invidious.io/instances.json";
+pub const INSTANCES_API_LINK: &str = "https://api.invidious.io/instances.json";
 
-pub async fn fetch_instance_info() -> Result<Instances, RustyTubeError> {
-	let instances_json = fetch(INSTANCES_API_LINK).await?;
+pub async fn fetch_instance_info(transport: &impl Transport) -> Result<Instances, RustyTubeError> {
+	let instances_json = fetch(transport, INSTANCES_API_LINK).await?;
 	let instances: Instances = serde_json::from_str(&instances_json)?;
 	Ok(instances)
 }
@@ -29,6 +29,29 @@ pub struct InstanceInfo {
 	pub monitor: Option<Monitor>,
 }
 
+impl InstanceInfo {
+	/// Whether the instance exposes a CORS-enabled API that the app can query directly.
+	pub fn is_usable(&self) -> bool {
+		self.api.unwrap_or_default() && self.cors.unwrap_or_default()
+	}
+
+	/// Uptime between 0 and 1, weighting the 30-day monitor ratio over the 90-day one.
+	pub fn uptime(&self) -> Option<f64> {
+		let monitor = self.monitor.as_ref()?;
+		let parse = |ratio: Option<&String>| ratio.and_then(|ratio| ratio.parse::<f64>().ok());
+		let monthly = parse(monitor.monthly_ratio.as_ref().and_then(|ratio| ratio.ratio.as_ref()));
+		let quarterly =
+			parse(monitor.quarterly_ratio.as_ref().and_then(|ratio| ratio.ratio.as_ref()));
+
+		let percentage = match (monthly, quarterly) {
+			(Some(monthly), Some(quarterly)) => 0.6 * monthly + 0.4 * quarterly,
+			(Some(ratio), None) | (None, Some(ratio)) => ratio,
+			(None, None) => return None,
+		};
+		Some((percentage / 100.0).clamp(0.0, 1.0))
+	}
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Stats {
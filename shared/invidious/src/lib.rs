@@ -1,22 +1,29 @@
 mod channel;
 mod common;
 mod dislikes;
+mod error;
 mod fetch;
 mod formats;
+mod health;
 mod hidden;
 mod instance;
 mod subs;
 mod tests;
+mod timer;
+mod transport;
 mod universal;
 mod video;
 
 pub use channel::*;
 pub use common::*;
 pub use dislikes::*;
+pub use error::*;
 pub use fetch::*;
 pub use formats::*;
+pub use health::*;
 pub use hidden::*;
 pub use instance::*;
 pub use subs::*;
+pub use transport::*;
 pub use universal::*;
 pub use video::*;
@@ -0,0 +1,73 @@
+use std::{future::Future, time::Duration};
+
+use futures::{stream, Stream, StreamExt};
+use rustytube_error::{ErrorKind, RustyTubeError};
+
+use crate::timer::sleep;
+
+/// How many channels are requested at once when loading subscriptions.
+pub const SUBS_CONCURRENCY: usize = 6;
+/// How many times a rate-limited request is retried before its error is returned.
+pub const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Runs `fetch` for every id with at most `limit` requests in flight, yielding results in the
+/// order they complete. Rate-limited requests are retried with exponential backoff.
+pub fn fetch_bounded<'a, T, F, Fut>(
+	ids: Vec<String>,
+	limit: usize,
+	fetch: F,
+) -> impl Stream<Item = Result<T, RustyTubeError>> + 'a
+where
+	T: 'a,
+	F: Fn(String) -> Fut + Clone + 'a,
+	Fut: Future<Output = Result<T, RustyTubeError>> + 'a,
+{
+	fetch_bounded_with_backoff(ids, limit, INITIAL_BACKOFF, fetch)
+}
+
+/// Like [`fetch_bounded`], waiting `initial_backoff` before the first retry of a rate-limited
+/// request and twice as long before each following one.
+pub fn fetch_bounded_with_backoff<'a, T, F, Fut>(
+	ids: Vec<String>,
+	limit: usize,
+	initial_backoff: Duration,
+	fetch: F,
+) -> impl Stream<Item = Result<T, RustyTubeError>> + 'a
+where
+	T: 'a,
+	F: Fn(String) -> Fut + Clone + 'a,
+	Fut: Future<Output = Result<T, RustyTubeError>> + 'a,
+{
+	stream::iter(ids)
+		.map(move |id| {
+			let fetch = fetch.clone();
+			async move { with_backoff(initial_backoff, || fetch(id.clone())).await }
+		})
+		.buffer_unordered(limit.max(1))
+}
+
+async fn with_backoff<T, Fut>(
+	initial_backoff: Duration,
+	fetch: impl Fn() -> Fut,
+) -> Result<T, RustyTubeError>
+where
+	Fut: Future<Output = Result<T, RustyTubeError>>,
+{
+	let mut backoff = initial_backoff;
+	let mut retries = 0;
+	loop {
+		match fetch().await {
+			Err(error)
+				if error.kind == ErrorKind::RateLimited && retries < MAX_RATE_LIMIT_RETRIES =>
+			{
+				if !backoff.is_zero() {
+					sleep(backoff).await;
+				}
+				backoff *= 2;
+				retries += 1;
+			}
+			result => return result,
+		}
+	}
+}
@@ -1,7 +1,9 @@
+mod fetcher;
 mod newpipe;
 mod subscriptions;
 mod youtube;
 
+pub use fetcher::*;
 pub use newpipe::*;
 pub use subscriptions::*;
 pub use youtube::*;
@@ -1,4 +1,4 @@
-use futures::future::join_all;
+use futures::{Stream, StreamExt};
 use gloo::{
 	file::{
 		futures::{read_as_bytes, read_as_text},
@@ -11,8 +11,9 @@ use serde::{Deserialize, Serialize};
 use utils::save_to_browser_storage;
 
 use crate::{
-	Channel, ChannelThumb, ChannelVideos, Feed, NewpipeSubscription, NewpipeSubscriptions,
-	YoutubeSubscription, YoutubeSubscriptions,
+	fetch_bounded, transport::Transport, Channel, ChannelThumb, ChannelVideos, Feed,
+	NewpipeSubscription, NewpipeSubscriptions, YoutubeSubscription, YoutubeSubscriptions,
+	SUBS_CONCURRENCY,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
@@ -58,46 +59,58 @@ impl Subscriptions {
 		}
 	}
 
-	pub async fn fetch_videos(&self, server: &str, rss: bool, lang: &str) -> SubsVideosResult {
-		let mut futures = Vec::new();
-
-		for channel in self.channels.clone() {
-			let id = channel.id.clone();
-			let future = async move {
-				match rss {
-					true => Feed::fetch_videos_from_feed(server, &id).await,
-					false => Channel::fetch_channel_videos(server, &id, None, lang).await,
-				}
-			};
-			futures.push(future)
-		}
-		let subs_videos = join_all(futures).await;
-
-		Ok(subs_videos)
+	/// Streams each channel's videos as soon as it arrives, limiting concurrent requests.
+	pub fn stream_videos<'a>(
+		&self,
+		transport: &'a impl Transport,
+		server: &'a str,
+		rss: bool,
+		lang: &'a str,
+	) -> impl Stream<Item = Result<ChannelVideos, RustyTubeError>> + 'a {
+		fetch_bounded(self.channel_ids(), SUBS_CONCURRENCY, move |id: String| async move {
+			match rss {
+				true => Feed::fetch_videos_from_feed(transport, server, &id).await,
+				false => Channel::fetch_channel_videos(transport, server, &id, None, lang).await,
+			}
+		})
 	}
 
-	pub async fn fetch_channels(&self, server: &str, lang: &str) -> SubsChannelsResult {
-		let mut futures = Vec::new();
+	pub async fn fetch_videos(
+		&self,
+		transport: &impl Transport,
+		server: &str,
+		rss: bool,
+		lang: &str,
+	) -> SubsVideosResult {
+		Ok(self.stream_videos(transport, server, rss, lang).collect().await)
+	}
 
-		for channel in self.channels.clone() {
-			let id = channel.id.clone();
-			let future = async move { Channel::fetch_channel(server, &id, lang).await };
-			futures.push(future)
-		}
-		let channels = join_all(futures).await;
-		Ok(channels)
+	pub async fn fetch_channels(
+		&self,
+		transport: &impl Transport,
+		server: &str,
+		lang: &str,
+	) -> SubsChannelsResult {
+		let channels =
+			fetch_bounded(self.channel_ids(), SUBS_CONCURRENCY, |id: String| async move {
+				Channel::fetch_channel(transport, server, &id, lang).await
+			});
+		Ok(channels.collect().await)
 	}
 
-	pub async fn fetch_channel_thumbs(&self, server: &str) -> SubsThumbsResult {
-		let mut futures = Vec::new();
+	pub async fn fetch_channel_thumbs(
+		&self,
+		transport: &impl Transport,
+		server: &str,
+	) -> SubsThumbsResult {
+		let thumbs = fetch_bounded(self.channel_ids(), SUBS_CONCURRENCY, |id: String| async move {
+			Channel::fetch_channel_thumb(transport, server, &id).await
+		});
+		Ok(thumbs.collect().await)
+	}
 
-		for channel in self.channels.clone() {
-			let id = channel.id.clone();
-			let future = async move { Channel::fetch_channel_thumb(server, &id).await };
-			futures.push(future)
-		}
-		let thumbs = join_all(futures).await;
-		Ok(thumbs)
+	fn channel_ids(&self) -> Vec<String> {
+		self.channels.iter().map(|channel| channel.id.clone()).collect()
 	}
 }
 
@@ -1,24 +1,57 @@
 #[cfg(test)]
 mod tests {
+	use std::{
+		cell::{Cell, RefCell},
+		collections::VecDeque,
+		future::Future,
+		pin::Pin,
+		rc::Rc,
+		task::{Context, Poll},
+		time::Duration,
+	};
+
+	use futures::StreamExt;
 	use gloo::file::Blob;
+	use rustytube_error::{ErrorKind, RustyTubeError};
+	use serde::{de::DeserializeOwned, Serialize};
 	use wasm_bindgen_test::{console_log, wasm_bindgen_test, wasm_bindgen_test_configure};
 
 	use crate::{
-		channel::Channel,
+		channel::{
+			Channel, ChannelLivestreams, ChannelPlaylists, ChannelSearch, ChannelShorts,
+			ChannelVideos, CommunityPosts, Feed, PostAttachment,
+		},
+		common::CommonVideo,
+		error::ApiError,
 		fetch::fetch,
 		formats::{
-			AdaptiveFormat, AudioFormat, Container, Formats, LegacyFormat, Resolution, VideoFormat,
+			AdaptiveFormat, AudioFormat, BandwidthEstimate, ByteRange, Container, DashManifest,
+			Formats, LegacyFormat, QualityLadder, Resolution, SegmentIndex, VideoFormat,
 			VideoQuality,
 		},
-		hidden::CountryCode,
-		instance::fetch_instance_info,
-		subs::{NewpipeSubscriptions, Subscriptions, YoutubeSubscriptions},
+		health::rank_instances,
+		hidden::{CountryCode, PopularItem, SearchResult, Storyboard},
+		instance::{
+			fetch_instance_info, InstanceInfo, Instances, Monitor, MonthlyRatio, INSTANCES_API_LINK,
+		},
+		subs::{
+			fetch_bounded_with_backoff, NewpipeSubscriptions, Subscriptions, YoutubeSubscriptions,
+			MAX_RATE_LIMIT_RETRIES, SUBS_CONCURRENCY,
+		},
+		transport::{
+			CachePolicy, CacheStore, CachedTransport, FailoverTransport, MemoryCacheStore,
+			MockTransport, Transport, TransportResponse,
+		},
 		universal::{
-			read_freetube_playlists, read_libretube_playlists, read_playlist_csv, LocalPlaylist,
-			Playlist, Popular, SearchArgs, Trending,
+			link_hashtags, read_freetube_playlists, read_libretube_playlists, read_playlist_csv,
+			Feature, Hashtag, LocalPlaylist, Mix, Playlist, PlaylistProgress, Popular,
+			ResponseType, SearchArgs, Sort, TimeSpan, Trending,
 			TrendingCategory::{Default, Gaming, Movies, Music},
 		},
-		video::Video,
+		video::{
+			AnnotationLink, AnnotationStyle, Annotations, CaptionTrack, Captions, Chapter,
+			Chapters, Video,
+		},
 		Comments, Replies, SearchResults,
 	};
 
@@ -28,22 +61,435 @@ mod tests {
 	const TEST_VIDEO_COMMENTS: &'static str = "sjC9rxq0LMc";
 	const TEST_PLAYLIST: &'static str = "PLMogWd-g0jAM34EC316Y7UT9-xp_mcAke";
 	const TEST_REGION: CountryCode = CountryCode::IE;
+	const TEST_LANG: &'static str = "en-US";
+	const TEST_ANNOTATED_VIDEO: &'static str = "9bZkp7q19f0";
+	const TEST_HASHTAG: &'static str = "privacy";
 
 	wasm_bindgen_test_configure!(run_in_browser);
 
+	/// Serves the recorded responses in `./files` for every endpoint the tests below fetch.
+	fn fixture_transport() -> MockTransport {
+		let api = format!("{}/api/v1", TEST_SERVER);
+		let region = TEST_REGION.to_string();
+		let channel_tab =
+			|tab: &str| format!("{}/channels/{}/{}?hl={}", api, TEST_CHANNEL, tab, TEST_LANG);
+		let channel_videos: ChannelVideos =
+			serde_json::from_str(include_str!("./files/channel_videos.json")).unwrap();
+		let comments: Comments =
+			serde_json::from_str(include_str!("./files/comments.json")).unwrap();
+		let replies_info = comments.comments.first().unwrap().replies_info.clone().unwrap();
+
+		MockTransport::new()
+			.with_response(INSTANCES_API_LINK, include_str!("./files/instances.json"))
+			.with_response(
+				format!("{}/videos/{}?hl={}", api, TEST_VIDEO, TEST_LANG),
+				include_str!("./files/video.json"),
+			)
+			.with_response(
+				format!("{}/channels/{}/", api, TEST_CHANNEL),
+				include_str!("./files/channel.json"),
+			)
+			.with_response(channel_tab("videos"), include_str!("./files/channel_videos.json"))
+			.with_response(
+				format!(
+					"{}/channels/{}/videos?continuation={}&hl={}",
+					api,
+					TEST_CHANNEL,
+					channel_videos.continuation.unwrap(),
+					TEST_LANG
+				),
+				r#"{"videos": [], "continuation": null}"#,
+			)
+			.with_response(channel_tab("shorts"), include_str!("./files/channel_shorts.json"))
+			.with_response(channel_tab("streams"), include_str!("./files/channel_streams.json"))
+			.with_response(channel_tab("playlists"), include_str!("./files/channel_playlists.json"))
+			.with_response(channel_tab("community"), include_str!("./files/community.json"))
+			.with_response(
+				format!("{}/captions/{}", api, TEST_VIDEO),
+				include_str!("./files/captions.json"),
+			)
+			.with_response(
+				format!("{}/captions/{}?label=English", api, TEST_VIDEO),
+				include_str!("./files/captions_en.vtt"),
+			)
+			.with_response(
+				format!("{}/captions/{}?label=English+%28auto-generated%29", api, TEST_VIDEO),
+				include_str!("./files/captions_auto.vtt"),
+			)
+			.with_response(
+				format!("{}/captions/{}?label=English&tlang=fr", api, TEST_VIDEO),
+				include_str!("./files/captions_en_fr.vtt"),
+			)
+			.with_response(
+				format!("{}/annotations/{}", api, TEST_ANNOTATED_VIDEO),
+				include_str!("./files/annotations.xml"),
+			)
+			.with_response(
+				format!(
+					"{}/channels/search/{}?q=privacy%20law&page=1&hl={}",
+					api, TEST_CHANNEL, TEST_LANG
+				),
+				include_str!("./files/channel_search.json"),
+			)
+			.with_response(
+				format!(
+					"{}/channels/search/{}?q=privacy%20law&page=2&hl={}",
+					api, TEST_CHANNEL, TEST_LANG
+				),
+				"[]",
+			)
+			.with_response(
+				format!(
+					"{}/channels/{}/community?continuation={}&hl={}",
+					api, TEST_CHANNEL, "Egljb21tdW5pdHnyBgQKAkoA", TEST_LANG
+				),
+				format!(
+					r#"{{"authorId": "{}", "comments": [], "continuation": null}}"#,
+					TEST_CHANNEL
+				),
+			)
+			.with_response(
+				format!("{}/comments/{}", api, TEST_VIDEO_COMMENTS),
+				include_str!("./files/comments.json"),
+			)
+			.with_response(
+				format!(
+					"{}/comments/{}?continuation={}&hl={}",
+					api, TEST_VIDEO_COMMENTS, replies_info.continuation, TEST_LANG
+				),
+				include_str!("./files/replies.json"),
+			)
+			.with_response(
+				format!("{}/search?q=test&page=1&hl={}", api, TEST_LANG),
+				include_str!("./files/search.json"),
+			)
+			.with_response(
+				format!("{}/trending/?region={}&hl={}", api, region, TEST_LANG),
+				include_str!("./files/trending.json"),
+			)
+			.with_response(
+				format!("{}/trending?type=music&region={}&hl={}", api, region, TEST_LANG),
+				include_str!("./files/trending.json"),
+			)
+			.with_response(
+				format!("{}/trending?type=gaming&region={}&hl={}", api, region, TEST_LANG),
+				include_str!("./files/trending.json"),
+			)
+			.with_response(
+				format!("{}/trending?type=movies&hl={}", api, TEST_LANG),
+				include_str!("./files/trending.json"),
+			)
+			.with_response(
+				format!("{}/hashtag/{}?page=1&hl={}", api, TEST_HASHTAG, TEST_LANG),
+				include_str!("./files/hashtag.json"),
+			)
+			.with_response(
+				format!("{}/hashtag/{}?page=2&hl={}", api, TEST_HASHTAG, TEST_LANG),
+				r#"{"results": []}"#,
+			)
+			.with_response(
+				format!("{}/popular?hl={}", api, TEST_LANG),
+				include_str!("./files/popular.json"),
+			)
+			.with_response(
+				format!("{}/playlists/{}", api, TEST_PLAYLIST),
+				include_str!("./files/playlist.json"),
+			)
+			.with_response(
+				format!("{}/mixes/{}?hl={}", api, Mix::radio_id(TEST_VIDEO), TEST_LANG),
+				include_str!("./files/mix.json"),
+			)
+			.with_response(
+				format!("{}/feed/playlist/{}/", TEST_SERVER, TEST_CHANNEL.replace("UC", "UULF")),
+				include_str!("./files/feed.xml"),
+			)
+	}
+
+	/// Fails if a fixture no longer survives a deserialise, serialise, deserialise cycle.
+	fn assert_round_trip<T: Serialize + DeserializeOwned>(json: &str) {
+		let parsed: T = serde_json::from_str(json).unwrap();
+		let serialised = serde_json::to_value(&parsed).unwrap();
+		let reparsed: T = serde_json::from_value(serialised.clone()).unwrap();
+
+		assert_eq!(serde_json::to_value(&reparsed).unwrap(), serialised);
+	}
+
 	#[wasm_bindgen_test]
 	async fn can_fetch_api_data() {
-		fetch(&format!("{}/api/v1/videos/{}", TEST_SERVER, TEST_VIDEO)).await.unwrap();
+		let transport = fixture_transport();
+		let video_url = format!("{}/api/v1/videos/{}?hl={}", TEST_SERVER, TEST_VIDEO, TEST_LANG);
+
+		fetch(&transport, &video_url).await.unwrap();
 	}
 
 	#[wasm_bindgen_test]
 	async fn can_fetch_instance_data() {
-		fetch_instance_info().await.unwrap();
+		let instances = fetch_instance_info(&fixture_transport()).await.unwrap();
+		let usable: Vec<&str> = instances
+			.iter()
+			.filter(|(_, info)| info.is_usable())
+			.map(|(name, _)| name.as_str())
+			.collect();
+
+		assert_eq!(instances.len(), 3);
+		assert_eq!(usable, vec!["iv.nboeck.de"]);
 	}
 
 	#[wasm_bindgen_test]
 	async fn get_video() {
-		let video = Video::fetch_video(TEST_SERVER, TEST_VIDEO).await.unwrap();
+		let video = Video::fetch_video(&fixture_transport(), TEST_SERVER, TEST_VIDEO, TEST_LANG)
+			.await
+			.unwrap();
+
+		assert_eq!(video.id, TEST_VIDEO);
+	}
+
+	#[wasm_bindgen_test]
+	async fn get_video_from_mock_transport() {
+		let video_url = format!("{}/api/v1/videos/{}?hl={}", TEST_SERVER, TEST_VIDEO, TEST_LANG);
+		let transport =
+			MockTransport::new().with_response(&video_url, include_str!("./files/video.json"));
+
+		let video =
+			Video::fetch_video(&transport, TEST_SERVER, TEST_VIDEO, TEST_LANG).await.unwrap();
+
+		assert_eq!(video.id, TEST_VIDEO);
+		assert_eq!(transport.requests(), vec![video_url]);
+	}
+
+	#[wasm_bindgen_test]
+	async fn mock_transport_rejects_unknown_urls() {
+		let transport = MockTransport::new();
+		let channel =
+			Channel::fetch_channel(&transport, TEST_SERVER, TEST_CHANNEL, TEST_LANG).await;
+
+		assert!(channel.is_err());
+	}
+
+	#[wasm_bindgen_test]
+	async fn failover_transport_switches_instance() {
+		const FALLBACK_SERVER: &'static str = "https://invidious.fdn.fr";
+		let video_path = format!("/api/v1/videos/{}?hl={}", TEST_VIDEO, TEST_LANG);
+		let mock = MockTransport::new()
+			.with_status(&format!("{}{}", TEST_SERVER, video_path), 502, "Bad Gateway")
+			.with_response(
+				&format!("{}{}", FALLBACK_SERVER, video_path),
+				include_str!("./files/video.json"),
+			);
+		let transport = FailoverTransport::new(&mock, vec![FALLBACK_SERVER.to_string()]);
+
+		let video =
+			Video::fetch_video(&transport, TEST_SERVER, TEST_VIDEO, TEST_LANG).await.unwrap();
+
+		assert_eq!(video.id, TEST_VIDEO);
+		assert_eq!(transport.active_server().as_deref(), Some(FALLBACK_SERVER));
+		assert_eq!(mock.requests().len(), 2);
+	}
+
+	#[wasm_bindgen_test]
+	async fn failover_transport_skips_stalled_instance() {
+		const FALLBACK_SERVER: &str = "https://invidious.fdn.fr";
+		let video_path = format!("/api/v1/videos/{}?hl={}", TEST_VIDEO, TEST_LANG);
+		let mock = MockTransport::new().with_response(
+			format!("{}{}", FALLBACK_SERVER, video_path),
+			include_str!("./files/video.json"),
+		);
+		let stalled = StalledTransport { server: TEST_SERVER, inner: mock };
+		let transport = FailoverTransport::new(&stalled, vec![FALLBACK_SERVER.to_string()])
+			.with_attempt_timeout(Duration::from_millis(10));
+
+		let video =
+			Video::fetch_video(&transport, TEST_SERVER, TEST_VIDEO, TEST_LANG).await.unwrap();
+
+		assert_eq!(video.id, TEST_VIDEO);
+		assert_eq!(transport.active_server().as_deref(), Some(FALLBACK_SERVER));
+	}
+
+	#[wasm_bindgen_test]
+	async fn rank_instances_by_health() {
+		let instance = |uri: &str, region: &str, ratio: &str| {
+			let monthly_ratio = MonthlyRatio { ratio: Some(ratio.to_string()), label: None };
+			let monitor = Monitor { monthly_ratio: Some(monthly_ratio), ..Monitor::default() };
+			let info = InstanceInfo {
+				uri: uri.to_string(),
+				region: region.to_string(),
+				api: Some(true),
+				cors: Some(true),
+				type_field: "https".to_string(),
+				monitor: Some(monitor),
+				..InstanceInfo::default()
+			};
+			(uri.to_string(), info)
+		};
+		let instances = vec![
+			instance("https://flaky.example", "DE", "80.00"),
+			instance("https://stable.example", "DE", "99.90"),
+			instance("https://local.example", "IE", "99.90"),
+			instance("https://offline.example", "IE", "100.00"),
+		];
+		let transport = MockTransport::new()
+			.with_response("https://flaky.example/api/v1/stats", "{}")
+			.with_response("https://stable.example/api/v1/stats", "{}")
+			.with_response("https://local.example/api/v1/stats", "{}");
+
+		let region = TEST_REGION.to_string();
+		let ranked = rank_instances(&transport, instances, Some(&region)).await;
+		let names: Vec<&str> = ranked.iter().map(|instance| instance.name.as_str()).collect();
+
+		assert_eq!(
+			names,
+			vec!["https://local.example", "https://stable.example", "https://flaky.example"]
+		);
+	}
+
+	#[wasm_bindgen_test]
+	async fn decode_api_error_bodies() {
+		let video_url = format!("{}/api/v1/videos/{}?hl={}", TEST_SERVER, TEST_VIDEO, TEST_LANG);
+		let unavailable = r#"{"error": "This video is unavailable"}"#;
+		let transport = MockTransport::new().with_status(&video_url, 500, unavailable);
+
+		let error =
+			Video::fetch_video(&transport, TEST_SERVER, TEST_VIDEO, TEST_LANG).await.unwrap_err();
+
+		assert_eq!(error.kind, ErrorKind::Unavailable);
+		assert!(!error.is_retryable());
+		assert_eq!(
+			error,
+			ApiError::VideoUnavailable("This video is unavailable".to_string()).into_error(500)
+		);
+		assert_eq!(error.source.as_deref(), Some(&RustyTubeError::instance_error(500, "")));
+	}
+
+	#[wasm_bindgen_test]
+	async fn classify_api_error_statuses() {
+		let rate_limited = TransportResponse::new(429, "");
+		let api_disabled = TransportResponse::new(403, "<html></html>");
+		let region_error =
+			r#"{"error": "The uploader has not made this available in your country"}"#;
+		let region_blocked = TransportResponse::new(500, region_error);
+
+		assert_eq!(ApiError::from_response(&rate_limited), Some(ApiError::RateLimited));
+		assert_eq!(ApiError::from_response(&api_disabled), Some(ApiError::ApiDisabled));
+		assert!(matches!(
+			ApiError::from_response(&region_blocked),
+			Some(ApiError::RegionBlocked(_))
+		));
+		assert_eq!(ApiError::from_response(&TransportResponse::ok("{}")), None);
+
+		let outages = [
+			TransportResponse::new(503, r#"{"error": "This video is unavailable"}"#),
+			TransportResponse::new(500, r#"{"error": "Service Unavailable"}"#),
+			TransportResponse::new(502, "Bad Gateway"),
+		];
+		for outage in outages {
+			let api_error = ApiError::from_response(&outage).unwrap();
+			assert!(matches!(api_error, ApiError::Instance { .. }), "{:?}", api_error);
+			assert!(api_error.is_retryable());
+		}
+	}
+
+	#[wasm_bindgen_test]
+	async fn api_errors_agree_on_retrying() {
+		let api_errors = [
+			ApiError::VideoUnavailable(String::new()),
+			ApiError::AgeRestricted(String::new()),
+			ApiError::RegionBlocked(String::new()),
+			ApiError::RateLimited,
+			ApiError::ApiDisabled,
+			ApiError::Instance { status: 404, message: String::new() },
+			ApiError::Instance { status: 502, message: String::new() },
+		];
+
+		for api_error in api_errors {
+			let error: RustyTubeError = api_error.clone().into();
+			assert_eq!(error.is_retryable(), api_error.is_retryable(), "{:?}", api_error);
+		}
+	}
+
+	#[wasm_bindgen_test]
+	async fn cached_transport_serves_fresh_entries() {
+		let video_url = format!("{}/api/v1/videos/{}?hl={}", TEST_SERVER, TEST_VIDEO, TEST_LANG);
+		let mock = Rc::new(
+			MockTransport::new().with_response(&video_url, include_str!("./files/video.json")),
+		);
+		let transport = CachedTransport::new(mock.clone(), CachePolicy::default());
+
+		let first =
+			Video::fetch_video(&transport, TEST_SERVER, TEST_VIDEO, TEST_LANG).await.unwrap();
+		let second =
+			Video::fetch_video(&transport, TEST_SERVER, TEST_VIDEO, TEST_LANG).await.unwrap();
+
+		assert_eq!(first.id, second.id);
+		assert_eq!(mock.requests().len(), 1);
+	}
+
+	#[wasm_bindgen_test]
+	async fn cached_transport_refetches_stale_entries() {
+		let video_url = format!("{}/api/v1/videos/{}?hl={}", TEST_SERVER, TEST_VIDEO, TEST_LANG);
+		let mock = Rc::new(
+			MockTransport::new().with_response(&video_url, include_str!("./files/video.json")),
+		);
+		let policy =
+			CachePolicy::new(Duration::from_secs(60)).with_rule("/api/v1/videos/", Duration::ZERO);
+		let transport = CachedTransport::new(mock.clone(), policy);
+
+		transport.get(&video_url).await.unwrap();
+		transport.get(&video_url).await.unwrap();
+
+		assert_eq!(mock.requests().len(), 2);
+	}
+
+	#[wasm_bindgen_test]
+	async fn cached_transport_never_serves_stale_videos() {
+		let policy = CachePolicy::default();
+		let video_url = format!("{}/api/v1/videos/{}?hl={}", TEST_SERVER, TEST_VIDEO, TEST_LANG);
+		let channel_url = format!("{}/api/v1/channels/{}", TEST_SERVER, TEST_CHANNEL);
+
+		assert_eq!(policy.max_stale(&video_url), Duration::ZERO);
+		assert_eq!(policy.max_stale(&channel_url), policy.max_stale);
+	}
+
+	#[wasm_bindgen_test]
+	async fn cached_transport_bounds_its_store() {
+		let urls = (0..5)
+			.map(|index| format!("{}/api/v1/channels/{}", TEST_SERVER, index))
+			.collect::<Vec<String>>();
+		let large_body = "a".repeat(700_000);
+		let mock = urls
+			.iter()
+			.fold(MockTransport::new(), |mock, url| mock.with_response(url, large_body.clone()));
+		let store = MemoryCacheStore::default();
+		let transport =
+			CachedTransport::new(mock, CachePolicy::default()).with_store(store.clone());
+
+		for url in &urls {
+			transport.get(url).await.unwrap();
+		}
+		let stored = store.keys();
+
+		assert!(stored.contains(&urls[4]));
+		assert!(!stored.contains(&urls[0]));
+		let stored_bytes = stored
+			.iter()
+			.filter(|key| urls.contains(key))
+			.map(|key| store.load(key).unwrap().len())
+			.sum::<usize>();
+		assert!(stored_bytes <= 2 * 1024 * 1024);
+
+		transport.clear();
+		assert!(store.keys().iter().all(|key| !urls.contains(key)));
+	}
+
+	#[wasm_bindgen_test]
+	async fn cached_transport_drops_unindexed_entries() {
+		let orphan = format!("{}/api/v1/channels/{}", TEST_SERVER, TEST_CHANNEL);
+		let store = MemoryCacheStore::default();
+		store.save(&orphan, "{}");
+
+		let _transport = CachedTransport::new(MockTransport::new(), CachePolicy::default())
+			.with_store(store.clone());
+
+		assert!(store.load(&orphan).is_none());
 	}
 
 	#[wasm_bindgen_test]
@@ -66,6 +512,183 @@ mod tests {
 		assert_eq!(formats.legacy_formats.len(), 3);
 	}
 
+	#[wasm_bindgen_test]
+	async fn generate_dash_manifest() {
+		let video: Video = serde_json::from_str(include_str!("./files/video.json")).unwrap();
+		let manifest: DashManifest = video.dash_manifest();
+		let xml = manifest.to_xml();
+
+		assert_eq!(xml, include_str!("./files/video_manifest.mpd"));
+
+		let families: Vec<(&str, &str)> = manifest
+			.adaptation_sets
+			.iter()
+			.map(|set| (set.mime_type.as_str(), set.codec_family.as_str()))
+			.collect();
+		assert_eq!(
+			families,
+			vec![
+				("audio/mp4", "mp4a"),
+				("audio/webm", "opus"),
+				("video/mp4", "avc1"),
+				("video/webm", "vp9"),
+				("video/mp4", "av01"),
+			]
+		);
+
+		let mpd: TestMpd = serde_xml_rs::from_str(&xml).unwrap();
+		assert_eq!(mpd.duration, "PT433S");
+		let representations: Vec<&TestRepresentation> =
+			mpd.period.sets.iter().flat_map(|set| &set.representations).collect();
+		assert_eq!(representations.len(), video.adaptive_formats.len());
+
+		let aac = representations.iter().find(|representation| representation.id == "140").unwrap();
+		assert_eq!(aac.codecs, "mp4a.40.2");
+		assert_eq!(aac.segment_base.index_range, "632-1191");
+		assert_eq!(aac.segment_base.initialization.range, "0-631");
+		let format = video.adaptive_formats.iter().find(|format| format.itag == "140").unwrap();
+		assert_eq!(aac.base_url, format.url);
+
+		let h264 =
+			representations.iter().find(|representation| representation.id == "137").unwrap();
+		assert_eq!(h264.height, Some(1080));
+	}
+
+	#[wasm_bindgen_test]
+	async fn step_through_qualities() {
+		let video: Video = serde_json::from_str(include_str!("./files/video.json")).unwrap();
+		let formats = Formats::from((video.adaptive_formats, video.format_streams));
+		let itag = |itag: &str| formats.video_formats.iter().find(|format| format.itag == itag);
+		let current = itag("135").unwrap();
+
+		let ladder = QualityLadder::new(&formats.video_formats, current, &VideoQuality::_720p);
+		let itags: Vec<&str> = ladder.formats.iter().map(|format| format.itag.as_str()).collect();
+
+		assert_eq!(itags, vec!["160", "133", "134", "135", "136"]);
+		assert_eq!(ladder.next(current, 10_000_000f64, 20f64), itag("136"));
+		assert_eq!(ladder.next(itag("136").unwrap(), 10_000_000f64, 20f64), itag("136"));
+		assert_eq!(ladder.next(current, 10_000_000f64, 10f64), itag("135"));
+		assert_eq!(ladder.next(current, 60_000f64, 3f64), itag("133"));
+		assert_eq!(ladder.next(current, 60_000f64, 12f64), itag("135"));
+
+		let mut estimate = BandwidthEstimate::default();
+		assert_eq!(estimate.bits_per_second(), None);
+
+		estimate.sample(500_000, 1f64);
+		let steady = estimate.bits_per_second().unwrap();
+		assert!((steady - 4_000_000f64).abs() < 1f64);
+
+		estimate.sample(50_000, 1f64);
+		let dipped = estimate.bits_per_second().unwrap();
+		assert!(dipped < steady && dipped > 400_000f64);
+	}
+
+	#[wasm_bindgen_test]
+	async fn parse_segment_indexes() {
+		let references: [(u32, u32); 3] = [(1000, 5000), (2000, 5000), (1500, 2500)];
+		let mut sidx: Vec<u8> = vec![];
+		sidx.extend((32 + 12 * references.len() as u32).to_be_bytes());
+		sidx.extend(b"sidx");
+		sidx.extend([0, 0, 0, 0]);
+		sidx.extend(1u32.to_be_bytes());
+		sidx.extend(1000u32.to_be_bytes());
+		sidx.extend([0u8; 8]);
+		sidx.extend([0, 0]);
+		sidx.extend((references.len() as u16).to_be_bytes());
+		references.iter().for_each(|(size, duration)| {
+			sidx.extend(size.to_be_bytes());
+			sidx.extend(duration.to_be_bytes());
+			sidx.extend(0x9000_0000u32.to_be_bytes());
+		});
+		let mut mp4 = vec![0u8; 741];
+		mp4.extend(&sidx);
+		let index = ByteRange::new(741, 741 + sidx.len() as u64 - 1);
+
+		let mp4_index =
+			SegmentIndex::parse("video/mp4; codecs=\"avc1.4d401f\"", &mp4, index, 6000).unwrap();
+
+		assert_eq!(mp4_index.segments.len(), 3);
+		assert_eq!(mp4_index.segments[0].bytes, ByteRange::new(809, 1808));
+		assert_eq!(mp4_index.segments[2].bytes, ByteRange::new(3809, 5308));
+		assert_eq!((mp4_index.segments[1].start, mp4_index.segments[1].end), (5.0, 10.0));
+		assert_eq!(mp4_index.duration(), 12.5);
+		assert_eq!(mp4_index.segment_at(10.0), Some(2));
+		assert_eq!(mp4_index.segment_at(12.5), None);
+
+		let mut webm: Vec<u8> = vec![0x1A, 0x45, 0xDF, 0xA3, 0x80];
+		webm.extend([0x18, 0x53, 0x80, 0x67, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+		webm.extend([0x15, 0x49, 0xA9, 0x66, 0x92]);
+		webm.extend([0x2A, 0xD7, 0xB1, 0x83, 0x0F, 0x42, 0x40]);
+		webm.extend([0x44, 0x89, 0x88]);
+		webm.extend(12500f64.to_be_bytes());
+		let cues_start = webm.len() as u64;
+		webm.extend([0x1C, 0x53, 0xBB, 0x6B, 0x9D]);
+		webm.extend([0xBB, 0x8C, 0xB3, 0x81, 0x00]);
+		webm.extend([0xB7, 0x87, 0xF7, 0x81, 0x01, 0xF1, 0x82, 0x00, 0x64]);
+		webm.extend([0xBB, 0x8D, 0xB3, 0x82, 0x13, 0x88]);
+		webm.extend([0xB7, 0x87, 0xF7, 0x81, 0x01, 0xF1, 0x82, 0x07, 0xD0]);
+		let index = ByteRange::new(cues_start, webm.len() as u64 - 1);
+
+		let webm_index =
+			SegmentIndex::parse("video/webm; codecs=\"vp9\"", &webm, index, 10000).unwrap();
+
+		assert_eq!(webm_index.segments.len(), 2);
+		assert_eq!(webm_index.segments[0].bytes, ByteRange::new(117, 2016));
+		assert_eq!(webm_index.segments[1].bytes, ByteRange::new(2017, 9999));
+		assert_eq!((webm_index.segments[1].start, webm_index.segments[1].end), (5.0, 12.5));
+		assert!(SegmentIndex::parse("video/mp4", &webm, index, 10000).is_err());
+		assert!(SegmentIndex::parse("video/webm; codecs=\"vp9\"", &webm, index, 0).is_err());
+
+		// An element of unknown size before the `Segment` can't be skipped.
+		let mut unknown_size: Vec<u8> = vec![0x1A, 0x45, 0xDF, 0xA3];
+		unknown_size.extend([0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+		assert!(SegmentIndex::parse_cues(&unknown_size, index, 10000).is_err());
+	}
+
+	#[derive(serde::Deserialize)]
+	struct TestMpd {
+		#[serde(rename = "mediaPresentationDuration")]
+		duration: String,
+		#[serde(rename = "Period")]
+		period: TestPeriod,
+	}
+
+	#[derive(serde::Deserialize)]
+	struct TestPeriod {
+		#[serde(rename = "AdaptationSet")]
+		sets: Vec<TestAdaptationSet>,
+	}
+
+	#[derive(serde::Deserialize)]
+	struct TestAdaptationSet {
+		#[serde(rename = "Representation")]
+		representations: Vec<TestRepresentation>,
+	}
+
+	#[derive(serde::Deserialize)]
+	struct TestRepresentation {
+		id: String,
+		codecs: String,
+		height: Option<u32>,
+		#[serde(rename = "BaseURL")]
+		base_url: String,
+		#[serde(rename = "SegmentBase")]
+		segment_base: TestSegmentBase,
+	}
+
+	#[derive(serde::Deserialize)]
+	struct TestSegmentBase {
+		#[serde(rename = "indexRange")]
+		index_range: String,
+		#[serde(rename = "Initialization")]
+		initialization: TestInitialization,
+	}
+
+	#[derive(serde::Deserialize)]
+	struct TestInitialization {
+		range: String,
+	}
+
 	#[wasm_bindgen_test]
 	async fn parse_video_format() {
 		let video_formats: Vec<VideoFormat> =
@@ -115,37 +738,167 @@ mod tests {
 
 	#[wasm_bindgen_test]
 	async fn get_trending() {
-		Trending::fetch_trending(TEST_SERVER, Default, TEST_REGION).await.unwrap();
-		Trending::fetch_trending(TEST_SERVER, Music, TEST_REGION).await.unwrap();
-		Trending::fetch_trending(TEST_SERVER, Gaming, TEST_REGION).await.unwrap();
-		Trending::fetch_trending(TEST_SERVER, Movies, TEST_REGION).await.unwrap();
+		let transport = fixture_transport();
+		let region = TEST_REGION.to_string();
+		for category in [Default, Music, Gaming, Movies] {
+			let trending =
+				Trending::fetch_trending(&transport, TEST_SERVER, &category, &region, TEST_LANG)
+					.await
+					.unwrap();
+			assert_eq!(trending.videos.len(), 5);
+		}
 	}
 
 	#[wasm_bindgen_test]
 	async fn get_popular() {
-		Popular::fetch_popular(TEST_SERVER).await.unwrap();
+		let popular =
+			Popular::fetch_popular(&fixture_transport(), TEST_SERVER, TEST_LANG).await.unwrap();
+
+		assert_eq!(popular.items.len(), 4);
 	}
 
 	#[wasm_bindgen_test]
 	async fn search() {
-		let args = SearchArgs::from_str("test".to_string());
-		let search = SearchResults::fetch_search_results(TEST_SERVER, args, 1).await.unwrap();
-		assert_ne!(0, search.items.len());
+		let args = SearchArgs::from_query("test".to_string());
+		let search = SearchResults::fetch_search_results(
+			&fixture_transport(),
+			TEST_SERVER,
+			&args,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(search.items.len(), 5);
+		assert!(matches!(search.items[0], SearchResult::Video(_)));
+		assert!(matches!(search.items[1], SearchResult::Channel(_)));
+		assert!(matches!(search.items[2], SearchResult::Playlist(_)));
+	}
+
+	#[wasm_bindgen_test]
+	async fn append_search_pages() {
+		let args = SearchArgs::from_query("test".to_string());
+		let mut results = SearchResults::fetch_search_results(
+			&fixture_transport(),
+			TEST_SERVER,
+			&args,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+		let repeated = results.clone();
+		let mut next_page = results.clone();
+		next_page.items.push(SearchResult::Video(CommonVideo {
+			id: String::from("unseen"),
+			..CommonVideo::default()
+		}));
+
+		assert_eq!(results.append(repeated), 0);
+		assert_eq!(results.append(next_page), 1);
+		assert_eq!(results.items.len(), 6);
+		assert_eq!(results.items[5].id(), "unseen");
+	}
+
+	#[wasm_bindgen_test]
+	async fn search_args_round_trip() {
+		let args = SearchArgs::new(
+			"rust & wasm".to_string(),
+			Some(Sort::Views),
+			Some(TimeSpan::Week),
+			Some(crate::universal::Duration::Long),
+			Some(ResponseType::Video),
+			Some(vec![Feature::HighDefinition, Feature::Subtitles]),
+			Some(CountryCode::IE),
+		)
+		.with_page(3);
+
+		let url = args.to_url();
+		assert_eq!(
+			url,
+			"?q=rust%20%26%20wasm&sort=views&date=week&duration=long&type=video\
+			 &features=hd,subtitles&region=IE&page=3"
+		);
+		assert_eq!(url.parse::<SearchArgs>().unwrap(), args);
+
+		let shared = "/search?q=rust+wasm&region=gb&utm=1".parse::<SearchArgs>().unwrap();
+		assert_eq!(shared.query, "rust wasm");
+		assert_eq!(shared.region, Some(CountryCode::GB));
+		assert!("?q=rust&sort=newest".parse::<SearchArgs>().is_err());
+
+		assert_eq!(TimeSpan::Day.to_string(), "today");
+		assert_eq!("today".parse::<TimeSpan>().unwrap(), TimeSpan::Day);
+		assert_eq!("day".parse::<TimeSpan>().unwrap(), TimeSpan::Day);
+	}
+
+	#[wasm_bindgen_test]
+	async fn get_hashtag() {
+		let transport = fixture_transport();
+		let hashtag = Hashtag::fetch_hashtag(&transport, TEST_SERVER, TEST_HASHTAG, 1, TEST_LANG)
+			.await
+			.unwrap();
+		let next_page = hashtag.fetch_next_page(&transport, TEST_SERVER, TEST_LANG).await.unwrap();
+
+		assert_eq!(hashtag.videos.len(), 3);
+		assert_eq!(next_page.page, 2);
+		assert!(next_page.videos.is_empty());
+	}
+
+	#[wasm_bindgen_test]
+	async fn link_description_hashtags() {
+		let description = "Rust &amp; Wasm #rust #WebAssembly2024 #1\n\
+			<a href=\"/hashtag/privacy\">#privacy</a> \
+			<a href=\"https://www.youtube.com/hashtag/caf%C3%A9\">#café</a> \
+			<a href=\"https://example.com/#top\">example.com/#top</a> issue#4 &#39;#quoted&#39;";
+
+		assert_eq!(
+			link_hashtags(description),
+			"Rust &amp; Wasm <a href=\"/hashtag?tag=rust\">#rust</a> \
+			 <a href=\"/hashtag?tag=WebAssembly2024\">#WebAssembly2024</a> #1\n\
+			 <a href=\"/hashtag?tag=privacy\">#privacy</a> \
+			 <a href=\"/hashtag?tag=caf%C3%A9\">#café</a> \
+			 <a href=\"https://example.com/#top\">example.com/#top</a> issue#4 \
+			 &#39;<a href=\"/hashtag?tag=quoted\">#quoted</a>&#39;"
+		);
+	}
+
+	#[wasm_bindgen_test]
+	async fn parse_description_chapters() {
+		let video: Video = serde_json::from_str(include_str!("./files/video.json")).unwrap();
+		assert!(video.chapters().is_empty());
+
+		let description = "Timestamps mentioned in passing, like 2:30, are not chapters.\n\n\
+			Chapters:\n\
+			0:00 Intro\n\
+			(1:05) - Building the parser\n\
+			Testing | 12:40\n\
+			1:02:03 – Q&A: your questions\n\n\
+			Song used at 0:45 in the intro";
+		let chapters = Chapters::from_description(description, 4000f64);
 
-		// Search::search(TEST_SERVER, &args).await.unwrap();
-		// args.timespan = Some(TimeSpan::Year);
-		// Search::search(TEST_SERVER, &args).await.unwrap();
-		// args.duration = Some(Duration::Long);
-		// Search::search(TEST_SERVER, &args).await.unwrap();
-		// args.response_type = Some(ResponseType::All);
-		// Search::search(TEST_SERVER, &args).await.unwrap();
-		// args.features = Some(vec![Feature::_4K, Feature::Subtitles, Feature::HighDynamicRange]);
-		// Search::search(TEST_SERVER, &args).await.unwrap();
+		assert_eq!(
+			chapters.chapters,
+			vec![
+				Chapter { title: String::from("Intro"), start: 0f64, end: 65f64 },
+				Chapter { title: String::from("Building the parser"), start: 65f64, end: 760f64 },
+				Chapter { title: String::from("Testing"), start: 760f64, end: 3723f64 },
+				Chapter {
+					title: String::from("Q&A: your questions"),
+					start: 3723f64,
+					end: 4000f64
+				},
+			]
+		);
+		assert_eq!(chapters.chapter_at(700f64).unwrap().title, "Building the parser");
+		assert_eq!(chapters.chapter_at(3723f64).unwrap().title, "Q&A: your questions");
+		assert!(Chapters::from_description("0:00 Intro\n1:00 Outro", 120f64).is_empty());
 	}
 
 	#[wasm_bindgen_test]
 	async fn get_channel() {
-		let channel = Channel::fetch_channel(TEST_SERVER, TEST_CHANNEL).await.unwrap();
+		let channel =
+			Channel::fetch_channel(&fixture_transport(), TEST_SERVER, TEST_CHANNEL, TEST_LANG)
+				.await
+				.unwrap();
 
 		let local_json = include_str!("./files/channel.json");
 		let local: Channel = serde_json::from_str(local_json).unwrap();
@@ -153,26 +906,311 @@ mod tests {
 		assert_eq!(local, channel)
 	}
 
+	#[wasm_bindgen_test]
+	async fn get_channel_tabs() {
+		let transport = fixture_transport();
+
+		let videos =
+			Channel::fetch_channel_videos(&transport, TEST_SERVER, TEST_CHANNEL, None, TEST_LANG)
+				.await
+				.unwrap();
+		let shorts =
+			Channel::fetch_channel_shorts(&transport, TEST_SERVER, TEST_CHANNEL, None, TEST_LANG)
+				.await
+				.unwrap();
+		let livestreams = Channel::fetch_channel_livestreams(
+			&transport,
+			TEST_SERVER,
+			TEST_CHANNEL,
+			None,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+		let playlists = Channel::fetch_channel_playlists(
+			&transport,
+			TEST_SERVER,
+			TEST_CHANNEL,
+			None,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(videos.videos.len(), 6);
+		assert_eq!(shorts.shorts.len(), 3);
+		assert_eq!(livestreams.livestreams.len(), 2);
+		assert_eq!(playlists.playlists.first().unwrap().author_id, TEST_CHANNEL);
+	}
+
+	#[wasm_bindgen_test]
+	async fn get_channel_videos_continuation() {
+		let transport = fixture_transport();
+		let first_page =
+			Channel::fetch_channel_videos(&transport, TEST_SERVER, TEST_CHANNEL, None, TEST_LANG)
+				.await
+				.unwrap();
+
+		let continuation = first_page.continuation.unwrap();
+		let last_page = Channel::fetch_channel_videos(
+			&transport,
+			TEST_SERVER,
+			TEST_CHANNEL,
+			Some(&continuation),
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+
+		assert!(last_page.videos.is_empty());
+		assert!(last_page.continuation.is_none());
+	}
+
+	#[wasm_bindgen_test]
+	async fn get_channel_community() {
+		let transport = fixture_transport();
+		let first_page = Channel::fetch_channel_community(
+			&transport,
+			TEST_SERVER,
+			TEST_CHANNEL,
+			None,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(first_page.author_id, TEST_CHANNEL);
+		assert_eq!(first_page.posts.len(), 6);
+		let attachments: Vec<Option<&PostAttachment>> =
+			first_page.posts.iter().map(|post| post.attachment.as_ref()).collect();
+		assert!(
+			matches!(attachments[0], Some(PostAttachment::Video(video)) if video.length == 612)
+		);
+		assert!(matches!(
+			attachments[1],
+			Some(PostAttachment::Poll { total_votes: 48211, choices }) if choices.len() == 4
+		));
+		assert!(matches!(attachments[2], Some(PostAttachment::Image { .. })));
+		assert!(
+			matches!(attachments[3], Some(PostAttachment::MultiImage { images }) if images.len() == 3)
+		);
+		assert!(attachments[4].is_none());
+		assert!(matches!(attachments[5], Some(PostAttachment::Unknown)));
+
+		let continuation = first_page.continuation.unwrap();
+		let last_page = Channel::fetch_channel_community(
+			&transport,
+			TEST_SERVER,
+			TEST_CHANNEL,
+			Some(&continuation),
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+
+		assert!(last_page.posts.is_empty());
+		assert!(last_page.continuation.is_none());
+	}
+
+	#[wasm_bindgen_test]
+	async fn search_channel() {
+		let transport = fixture_transport();
+		let first_page = ChannelSearch::search(
+			&transport,
+			TEST_SERVER,
+			TEST_CHANNEL,
+			"privacy law",
+			1,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+		let last_page = ChannelSearch::search(
+			&transport,
+			TEST_SERVER,
+			TEST_CHANNEL,
+			"privacy law",
+			2,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(first_page.items.len(), 4);
+		assert!(first_page.items.iter().all(|item| match item {
+			SearchResult::Video(video) => video.author_id == TEST_CHANNEL,
+			SearchResult::Playlist(playlist) => playlist.author_id == TEST_CHANNEL,
+			SearchResult::Channel(_) => false,
+		}));
+		assert!(last_page.items.is_empty());
+	}
+
+	#[wasm_bindgen_test]
+	async fn get_annotations() {
+		let annotations =
+			Annotations::fetch_annotations(&fixture_transport(), TEST_SERVER, TEST_ANNOTATED_VIDEO)
+				.await
+				.unwrap();
+		let styles: Vec<AnnotationStyle> =
+			annotations.items.iter().map(|annotation| annotation.style).collect();
+		let popup = annotations.items.first().unwrap();
+
+		assert_eq!(
+			styles,
+			vec![AnnotationStyle::Popup, AnnotationStyle::Speech, AnnotationStyle::Highlight]
+		);
+		assert_eq!((popup.start, popup.end), (5.0, 12.5));
+		assert_eq!(popup.region.x, 65.0);
+		assert_eq!(popup.appearance.background.as_deref(), Some("#ffffff"));
+		assert_eq!(popup.appearance.background_css().as_deref(), Some("rgba(255, 255, 255, 0.8)"));
+		assert_eq!(
+			popup.link,
+			Some(AnnotationLink::Video { id: String::from("wcLNteez3c4"), start: Some(30) })
+		);
+		assert_eq!(
+			annotations.items[2].link,
+			Some(AnnotationLink::Url(String::from("https://www.officialpsy.com")))
+		);
+		assert_eq!(annotations.active_at(6.0).count(), 1);
+		assert_eq!(annotations.active_at(72.0).next().unwrap().style, AnnotationStyle::Speech);
+		assert_eq!(annotations.active_at(190.0).next().unwrap().start, 181.5);
+		assert!(Annotations::parse("").unwrap().items.is_empty());
+	}
+
+	#[wasm_bindgen_test]
+	async fn get_caption_tracks() {
+		let transport = fixture_transport();
+		let captions =
+			Captions::fetch_captions(&transport, TEST_SERVER, TEST_VIDEO).await.unwrap().captions;
+		let english = captions.first().unwrap();
+		let auto_generated = captions.get(1).unwrap();
+
+		assert_eq!(english.language, "en");
+		assert!(!english.is_auto_generated());
+		assert!(auto_generated.is_auto_generated());
+
+		let track =
+			CaptionTrack::fetch_track(&transport, TEST_SERVER, english, None).await.unwrap();
+		let intro = track.cues.first().unwrap();
+		let styled = track.cues.get(1).unwrap().lines.get(1).unwrap();
+
+		assert_eq!(track.cues.len(), 4);
+		assert_eq!(intro.id.as_deref(), Some("intro"));
+		assert_eq!(intro.settings.line.as_deref(), Some("90%"));
+		assert_eq!(intro.lines[0].spans[0].voice.as_deref(), Some("Kenny"));
+		assert_eq!(styled.text(), "UK wants to outlaw encryption.");
+		assert_eq!(styled.spans[0].classes, vec![String::from("yellow")]);
+		assert!(styled.spans[2].bold && !styled.spans[3].bold);
+		assert_eq!(track.cues[2].text(), r#"Spoiler: it's "for the children" & it won't work."#);
+		assert_eq!((track.cues[3].start, track.cues[3].end), (62.0, 65.25));
+		assert!(track.cues[3].lines[0].spans[0].underline);
+		assert_eq!(track.active_at(4.0).next().unwrap().start, 3.2);
+		assert_eq!(CaptionTrack::parse(&track.to_vtt()).unwrap().cues.len(), 4);
+
+		let auto_track =
+			CaptionTrack::fetch_track(&transport, TEST_SERVER, auto_generated, None).await.unwrap();
+		let auto_text: Vec<String> = auto_track.cues.iter().map(|cue| cue.text()).collect();
+
+		assert_eq!(
+			auto_text,
+			vec!["hey guys welcome back", "to the channel", "today we are looking"]
+		);
+		assert_eq!(auto_track.cues[0].lines[0].spans[1].timestamp, Some(0.48));
+
+		let translated =
+			CaptionTrack::fetch_track(&transport, TEST_SERVER, english, Some("fr")).await.unwrap();
+
+		assert_eq!(
+			translated.cues[1].text().lines().next(),
+			Some("Aujourd'hui, nous examinons pourquoi le")
+		);
+		assert!(CaptionTrack::parse("not a caption file").is_err());
+	}
+
+	#[wasm_bindgen_test]
+	async fn find_storyboard_tiles() {
+		let storyboards: Vec<Storyboard> = serde_json::from_str(
+			r#"[
+				{
+					"url": "/api/v1/storyboards/wsmHCfSZM70?width=48&height=27",
+					"templateUrl": "https://i.ytimg.com/sb/wsmHCfSZM70/storyboard3_L0/default.jpg",
+					"width": 48, "height": 27, "count": 100, "interval": 0,
+					"storyboardWidth": 10, "storyboardHeight": 10, "storyboardCount": 1
+				},
+				{
+					"url": "/api/v1/storyboards/wsmHCfSZM70?width=160&height=90",
+					"templateUrl": "/sb/wsmHCfSZM70/storyboard3_L2/M$M.jpg",
+					"width": 160, "height": 90, "count": 300, "interval": 2000,
+					"storyboardWidth": 5, "storyboardHeight": 5, "storyboardCount": 12
+				}
+			]"#,
+		)
+		.unwrap();
+		let largest = Storyboard::largest(&storyboards).unwrap();
+		let tile = largest.tile_at(TEST_SERVER, 61.0, 600.0).unwrap();
+
+		assert_eq!(largest.width, 160);
+		assert_eq!(tile.sheet_url, format!("{}/sb/wsmHCfSZM70/storyboard3_L2/M1.jpg", TEST_SERVER));
+		assert_eq!((tile.x, tile.y), (0, 90));
+		assert!(largest
+			.tile_at(TEST_SERVER, 6000.0, 600.0)
+			.unwrap()
+			.sheet_url
+			.ends_with("M11.jpg"));
+
+		let overview = storyboards[0].tile_at(TEST_SERVER, 300.0, 600.0).unwrap();
+
+		assert_eq!(overview.sheet_url, storyboards[0].template_url);
+		assert_eq!((overview.x, overview.y), (0, 135));
+	}
+
+	#[wasm_bindgen_test]
+	async fn get_channel_feed() {
+		let videos = Feed::fetch_videos_from_feed(&fixture_transport(), TEST_SERVER, TEST_CHANNEL)
+			.await
+			.unwrap();
+		let first = videos.videos.first().unwrap();
+
+		assert_eq!(videos.videos.len(), 3);
+		assert_eq!(first.author_id, TEST_CHANNEL);
+		assert_eq!(first.views, 93261);
+	}
+
 	#[wasm_bindgen_test]
 	async fn get_comments() {
-		let comments =
-			Comments::fetch_comments(TEST_SERVER, TEST_VIDEO_COMMENTS, None).await.unwrap();
+		let comments = Comments::fetch_comments(
+			&fixture_transport(),
+			TEST_SERVER,
+			TEST_VIDEO_COMMENTS,
+			None,
+			TEST_LANG,
+		)
+		.await
+		.unwrap();
 
 		let local_json = include_str!("./files/comments.json");
 		let local: Comments = serde_json::from_str(local_json).unwrap();
+
+		assert_eq!(comments.id, TEST_VIDEO_COMMENTS);
+		assert_eq!(comments.comments, local.comments);
 	}
 
 	#[wasm_bindgen_test]
 	async fn get_comment_replies() {
+		let transport = fixture_transport();
 		let comments =
-			Comments::fetch_comments(TEST_SERVER, TEST_VIDEO_COMMENTS, None).await.unwrap();
+			Comments::fetch_comments(&transport, TEST_SERVER, TEST_VIDEO_COMMENTS, None, TEST_LANG)
+				.await
+				.unwrap();
 
 		let first_comment = comments.comments.first().unwrap();
 		let first_comment_replies_info = first_comment.replies_info.clone().unwrap();
 		let replies = Replies::fetch_replies(
+			&transport,
 			&first_comment_replies_info.continuation,
 			TEST_SERVER,
-			&first_comment.id,
+			TEST_VIDEO_COMMENTS,
+			TEST_LANG,
 		)
 		.await
 		.unwrap();
@@ -182,7 +1220,10 @@ mod tests {
 
 	#[wasm_bindgen_test]
 	async fn get_playlist() {
-		let playlist = Playlist::fetch_playlist(TEST_SERVER, TEST_PLAYLIST, None).await.unwrap();
+		let playlist =
+			Playlist::fetch_playlist(&fixture_transport(), TEST_SERVER, TEST_PLAYLIST, None)
+				.await
+				.unwrap();
 
 		let local_json = include_str!("./files/playlist.json");
 		let local: Playlist = serde_json::from_str(local_json).unwrap();
@@ -191,6 +1232,78 @@ mod tests {
 		assert_eq!(playlist.author_id, local.author_id);
 	}
 
+	#[wasm_bindgen_test]
+	async fn get_video_radio() {
+		let mix = Mix::fetch_radio(&fixture_transport(), TEST_SERVER, TEST_VIDEO, TEST_LANG)
+			.await
+			.unwrap();
+		let last = mix.videos.last().unwrap();
+
+		assert_eq!(mix.id, Mix::radio_id(TEST_VIDEO));
+		assert_eq!(mix.position(TEST_VIDEO), Some(0));
+		assert_eq!(mix.next_after(TEST_VIDEO).unwrap().id, "-Hdn8EBFwNg");
+		assert!(mix.next_after(&last.id).is_none());
+		assert_eq!(mix.next_after("unknown").unwrap().id, TEST_VIDEO);
+	}
+
+	#[wasm_bindgen_test]
+	async fn round_trip_fixtures() {
+		assert_round_trip::<Video>(include_str!("./files/video.json"));
+		assert_round_trip::<Channel>(include_str!("./files/channel.json"));
+		assert_round_trip::<ChannelVideos>(include_str!("./files/channel_videos.json"));
+		assert_round_trip::<ChannelShorts>(include_str!("./files/channel_shorts.json"));
+		assert_round_trip::<ChannelLivestreams>(include_str!("./files/channel_streams.json"));
+		assert_round_trip::<ChannelPlaylists>(include_str!("./files/channel_playlists.json"));
+		assert_round_trip::<CommunityPosts>(include_str!("./files/community.json"));
+		assert_round_trip::<Vec<SearchResult>>(include_str!("./files/channel_search.json"));
+		assert_round_trip::<Comments>(include_str!("./files/comments.json"));
+		assert_round_trip::<Replies>(include_str!("./files/replies.json"));
+		assert_round_trip::<Vec<SearchResult>>(include_str!("./files/search.json"));
+		assert_round_trip::<Vec<CommonVideo>>(include_str!("./files/trending.json"));
+		assert_round_trip::<Vec<PopularItem>>(include_str!("./files/popular.json"));
+		assert_round_trip::<Playlist>(include_str!("./files/playlist.json"));
+		assert_round_trip::<Instances>(include_str!("./files/instances.json"));
+		assert_round_trip::<Mix>(include_str!("./files/mix.json"));
+	}
+
+	#[wasm_bindgen_test]
+	async fn parse_feed_xml() {
+		let feed: Feed = serde_xml_rs::from_str(include_str!("./files/feed.xml")).unwrap();
+		let videos: ChannelVideos = feed.clone().into();
+
+		assert_eq!(feed.channel_id, TEST_CHANNEL);
+		assert_eq!(feed.entries.len(), videos.videos.len());
+		assert_eq!(feed.entries.first().unwrap().group.thumbnail.width, 480);
+	}
+
+	#[wasm_bindgen_test]
+	async fn fetch_all_playlist_pages() {
+		let page_url =
+			|page: u32| format!("{}/api/v1/playlists/{}?page={}", TEST_SERVER, TEST_PLAYLIST, page);
+		let transport = MockTransport::new()
+			.with_response(page_url(1), include_str!("./files/playlist_page_1.json"))
+			.with_response(page_url(2), include_str!("./files/playlist_page_2.json"))
+			.with_response(page_url(3), include_str!("./files/playlist_page_3.json"));
+		let progress = std::cell::RefCell::new(vec![]);
+
+		let playlist = Playlist::fetch_all(&transport, TEST_SERVER, TEST_PLAYLIST, |update| {
+			progress.borrow_mut().push(update)
+		})
+		.await
+		.unwrap();
+		let indices: Vec<u32> = playlist.videos.iter().map(|video| video.index).collect();
+
+		assert_eq!(indices, (0..200).collect::<Vec<u32>>());
+		assert_eq!(
+			progress.into_inner(),
+			vec![
+				PlaylistProgress { fetched: 100, total: 202 },
+				PlaylistProgress { fetched: 200, total: 202 }
+			]
+		);
+		assert_eq!(transport.requests(), vec![page_url(1), page_url(2), page_url(3)]);
+	}
+
 	#[wasm_bindgen_test]
 	async fn read_playlists() {
 		let playlist_bytes = include_bytes!("files/playlist.csv");
@@ -240,6 +1353,13 @@ mod tests {
 		assert_eq!(subs.channels.len(), 54);
 	}
 
+	/// Keeps the subscriptions to channels with recorded fixtures.
+	fn fixture_subs(mut subs: Subscriptions) -> Subscriptions {
+		subs.channels.retain(|channel| channel.id == TEST_CHANNEL);
+		assert!(!subs.channels.is_empty(), "{} is no longer subscribed to", TEST_CHANNEL);
+		subs
+	}
+
 	#[wasm_bindgen_test]
 	async fn can_fetch_subs() {
 		let mut fail = 0;
@@ -248,8 +1368,9 @@ mod tests {
 		let subs_json: &[u8] = include_bytes!("./files/subscriptions.csv");
 		let yt_subs: YoutubeSubscriptions =
 			YoutubeSubscriptions::read_subs_from_csv(subs_json).unwrap();
-		let subs: Subscriptions = yt_subs.into();
-		let subs_videos = subs.fetch_videos(TEST_SERVER, false).await.unwrap();
+		let subs = fixture_subs(yt_subs.into());
+		let subs_videos =
+			subs.fetch_videos(&fixture_transport(), TEST_SERVER, false, TEST_LANG).await.unwrap();
 
 		subs_videos.into_iter().for_each(|sub_videos| match sub_videos {
 			Ok(_) => success = success + 1,
@@ -258,6 +1379,8 @@ mod tests {
 
 		console_log!("JSON Sub Fetch Fail: {}", fail);
 		console_log!("JSON Sub Fetch Success: {}", success);
+		assert_eq!(success, 1);
+		assert_eq!(fail, 0);
 	}
 
 	#[wasm_bindgen_test]
@@ -268,9 +1391,10 @@ mod tests {
 		let subs_json: &[u8] = include_bytes!("./files/subscriptions.csv");
 		let yt_subs: YoutubeSubscriptions =
 			YoutubeSubscriptions::read_subs_from_csv(subs_json).unwrap();
-		let subs: Subscriptions = yt_subs.into();
+		let subs = fixture_subs(yt_subs.into());
 
-		let subs_videos = subs.fetch_videos(TEST_SERVER, true).await.unwrap();
+		let subs_videos =
+			subs.fetch_videos(&fixture_transport(), TEST_SERVER, true, TEST_LANG).await.unwrap();
 		subs_videos.into_iter().for_each(|sub_videos| match sub_videos {
 			Ok(videos) => success = success + 1,
 			Err(_) => fail = fail + 1,
@@ -278,5 +1402,141 @@ mod tests {
 
 		console_log!("RSS Sub Fetch Fail: {}", fail);
 		console_log!("RSS Sub Fetch Success: {}", success);
+		assert_eq!(success, 1);
+		assert_eq!(fail, 0);
+	}
+
+	/// Answers each request with the next of its responses, repeating the last one.
+	struct SequenceTransport {
+		responses: RefCell<VecDeque<TransportResponse>>,
+		requests: Cell<usize>,
+	}
+
+	impl SequenceTransport {
+		fn new(responses: Vec<TransportResponse>) -> Self {
+			Self { responses: RefCell::new(responses.into()), requests: Cell::new(0) }
+		}
+	}
+
+	impl Transport for SequenceTransport {
+		async fn get(&self, _url: &str) -> Result<TransportResponse, RustyTubeError> {
+			self.requests.set(self.requests.get() + 1);
+			let mut responses = self.responses.borrow_mut();
+			match responses.len() {
+				1 => Ok(responses[0].clone()),
+				_ => Ok(responses.pop_front().unwrap()),
+			}
+		}
+	}
+
+	/// Never answers requests to `server`, like an instance that accepts connections and hangs.
+	struct StalledTransport {
+		server: &'static str,
+		inner: MockTransport,
+	}
+
+	impl Transport for StalledTransport {
+		async fn get(&self, url: &str) -> Result<TransportResponse, RustyTubeError> {
+			if url.starts_with(self.server) {
+				std::future::pending::<()>().await;
+			}
+			self.inner.get(url).await
+		}
+	}
+
+	/// Counts the requests in flight at once, letting every request wait a turn before answering.
+	#[derive(Default)]
+	struct InFlightTransport {
+		in_flight: Cell<usize>,
+		max_in_flight: Cell<usize>,
+	}
+
+	impl Transport for InFlightTransport {
+		async fn get(&self, _url: &str) -> Result<TransportResponse, RustyTubeError> {
+			self.in_flight.set(self.in_flight.get() + 1);
+			self.max_in_flight.set(self.max_in_flight.get().max(self.in_flight.get()));
+			YieldNow(false).await;
+			self.in_flight.set(self.in_flight.get() - 1);
+			Ok(TransportResponse::ok("{}"))
+		}
+	}
+
+	struct YieldNow(bool);
+
+	impl Future for YieldNow {
+		type Output = ();
+
+		fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+			if self.0 {
+				return Poll::Ready(());
+			}
+			self.0 = true;
+			cx.waker().wake_by_ref();
+			Poll::Pending
+		}
+	}
+
+	fn channel_ids(count: usize) -> Vec<String> {
+		(0..count).map(|index| format!("channel{}", index)).collect()
+	}
+
+	#[wasm_bindgen_test]
+	async fn fetch_bounded_limits_requests_in_flight() {
+		let transport = InFlightTransport::default();
+		let fetch_channel = |id: String| {
+			let transport = &transport;
+			async move { fetch(transport, &format!("{}/api/v1/channels/{}", TEST_SERVER, id)).await }
+		};
+
+		let results = fetch_bounded_with_backoff(
+			channel_ids(20),
+			SUBS_CONCURRENCY,
+			Duration::ZERO,
+			fetch_channel,
+		)
+		.collect::<Vec<Result<String, RustyTubeError>>>()
+		.await;
+
+		assert_eq!(results.len(), 20);
+		assert!(results.iter().all(Result::is_ok));
+		assert_eq!(transport.max_in_flight.get(), SUBS_CONCURRENCY);
+		assert_eq!(transport.in_flight.get(), 0);
+	}
+
+	#[wasm_bindgen_test]
+	async fn fetch_bounded_retries_rate_limited_requests() {
+		let transport = SequenceTransport::new(vec![
+			TransportResponse::new(429, ""),
+			TransportResponse::ok("{}"),
+		]);
+		let fetch_channel = |id: String| {
+			let transport = &transport;
+			async move { fetch(transport, &format!("{}/api/v1/channels/{}", TEST_SERVER, id)).await }
+		};
+
+		let backoff = Duration::from_millis(1);
+		let results = fetch_bounded_with_backoff(channel_ids(1), 1, backoff, fetch_channel)
+			.collect::<Vec<Result<String, RustyTubeError>>>()
+			.await;
+
+		assert_eq!(results, vec![Ok("{}".to_string())]);
+		assert_eq!(transport.requests.get(), 2);
+	}
+
+	#[wasm_bindgen_test]
+	async fn fetch_bounded_gives_up_after_retry_cap() {
+		let transport = SequenceTransport::new(vec![TransportResponse::new(429, "")]);
+		let fetch_channel = |id: String| {
+			let transport = &transport;
+			async move { fetch(transport, &format!("{}/api/v1/channels/{}", TEST_SERVER, id)).await }
+		};
+
+		let results = fetch_bounded_with_backoff(channel_ids(1), 1, Duration::ZERO, fetch_channel)
+			.collect::<Vec<Result<String, RustyTubeError>>>()
+			.await;
+
+		assert_eq!(results.len(), 1);
+		assert_eq!(results[0].as_ref().unwrap_err().kind, ErrorKind::RateLimited);
+		assert_eq!(transport.requests.get(), 1 + MAX_RATE_LIMIT_RETRIES as usize);
 	}
 }
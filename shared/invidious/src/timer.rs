@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+/// Waits for `duration` without blocking the executor.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+	gloo::timers::future::sleep(duration).await
+}
+
+/// Waits for `duration` without blocking the executor, on whichever runtime polls it.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+	let (sender, receiver) = futures::channel::oneshot::channel();
+	std::thread::spawn(move || {
+		std::thread::sleep(duration);
+		let _ = sender.send(());
+	});
+	let _ = receiver.await;
+}
@@ -0,0 +1,22 @@
+use reqwasm::http::{Method, Request, RequestMode};
+use rustytube_error::RustyTubeError;
+use web_sys::RequestCache;
+
+use super::{Transport, TransportResponse};
+
+/// Transport backed by the browser's `fetch` API through `reqwasm`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BrowserTransport;
+
+impl Transport for BrowserTransport {
+	async fn get(&self, url: &str) -> Result<TransportResponse, RustyTubeError> {
+		let request = Request::new(url)
+			.mode(RequestMode::Cors)
+			.method(Method::GET)
+			.cache(RequestCache::Default);
+		let response = request.send().await?;
+		let status = response.status();
+		let body = response.text().await?;
+		Ok(TransportResponse { status, body })
+	}
+}
@@ -0,0 +1,432 @@
+use std::{
+	cell::RefCell,
+	collections::{HashMap, HashSet},
+	rc::Rc,
+	time::Duration,
+};
+
+use chrono::Utc;
+use futures::future::LocalBoxFuture;
+use gloo::storage::{LocalStorage, Storage};
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+
+use super::{Transport, TransportResponse};
+
+const CACHE_KEY_PREFIX: &str = "rustytube_cache_";
+/// Where the store keeps track of the entries it holds. Urls never collide with it.
+const INDEX_KEY: &str = "index";
+const MAX_ENTRIES: usize = 256;
+/// The most the store may hold, leaving local storage room for the config, playlists and
+/// subscriptions. Measured in bytes of serialised entries.
+const MAX_PERSISTED_BYTES: usize = 2 * 1024 * 1024;
+
+/// How long responses stay fresh, chosen by the first rule whose pattern appears in the url.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachePolicy {
+	rules: Vec<CacheRule>,
+	/// How long past its TTL an entry may still be served while it is revalidated.
+	pub max_stale: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct CacheRule {
+	pattern: String,
+	ttl: Duration,
+	/// Overrides [`CachePolicy::max_stale`] for the urls of this rule.
+	max_stale: Option<Duration>,
+}
+
+impl CachePolicy {
+	pub fn new(max_stale: Duration) -> Self {
+		Self { rules: vec![], max_stale }
+	}
+
+	pub fn with_rule(mut self, pattern: &str, ttl: Duration) -> Self {
+		self.rules.push(CacheRule { pattern: pattern.to_string(), ttl, max_stale: None });
+		self
+	}
+
+	/// Like [`CachePolicy::with_rule`], for responses that go bad sooner than
+	/// [`CachePolicy::max_stale`].
+	pub fn with_rule_max_stale(
+		mut self,
+		pattern: &str,
+		ttl: Duration,
+		max_stale: Duration,
+	) -> Self {
+		let max_stale = Some(max_stale);
+		self.rules.push(CacheRule { pattern: pattern.to_string(), ttl, max_stale });
+		self
+	}
+
+	/// Returns `None` for urls that should never be cached.
+	pub fn ttl(&self, url: &str) -> Option<Duration> {
+		self.rule(url).map(|rule| rule.ttl)
+	}
+
+	/// How long past its TTL the response for `url` may still be served.
+	pub fn max_stale(&self, url: &str) -> Duration {
+		self.rule(url).and_then(|rule| rule.max_stale).unwrap_or(self.max_stale)
+	}
+
+	fn rule(&self, url: &str) -> Option<&CacheRule> {
+		self.rules.iter().find(|rule| url.contains(rule.pattern.as_str()))
+	}
+}
+
+impl Default for CachePolicy {
+	fn default() -> Self {
+		let minutes = |minutes: u64| Duration::from_secs(minutes * 60);
+
+		// Videos hold googlevideo stream urls, which expire after a few hours.
+		CachePolicy::new(minutes(60 * 24 * 7))
+			.with_rule_max_stale("/api/v1/videos/", minutes(10), Duration::ZERO)
+			.with_rule("/api/v1/comments/", minutes(10))
+			.with_rule("/api/v1/trending", minutes(30))
+			.with_rule("/api/v1/popular", minutes(30))
+			.with_rule("/api/v1/search/suggestions", minutes(60))
+			.with_rule("/api/v1/search", minutes(10))
+			.with_rule("/api/v1/channels/", minutes(60))
+			.with_rule("/api/v1/playlists/", minutes(60))
+			.with_rule("/api/v1/mixes/", minutes(60))
+			.with_rule("/api/v1/annotations/", minutes(60 * 24))
+			.with_rule("/feed/channel/", minutes(15))
+			.with_rule("instances.json", minutes(60 * 24))
+	}
+}
+
+/// Somewhere to keep cached responses between sessions.
+pub trait CacheStore {
+	fn load(&self, key: &str) -> Option<String>;
+	fn save(&self, key: &str, value: &str);
+	fn remove(&self, key: &str);
+	/// Every key held, including those of earlier sessions.
+	fn keys(&self) -> Vec<String>;
+}
+
+/// Persists cached responses in the browser's local storage.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BrowserCacheStore;
+
+impl CacheStore for BrowserCacheStore {
+	fn load(&self, key: &str) -> Option<String> {
+		LocalStorage::get(format!("{}{}", CACHE_KEY_PREFIX, key)).ok()
+	}
+
+	fn save(&self, key: &str, value: &str) {
+		// A full storage quota only costs us persistence, the memory cache still works.
+		let _ = LocalStorage::set(format!("{}{}", CACHE_KEY_PREFIX, key), value);
+	}
+
+	fn remove(&self, key: &str) {
+		LocalStorage::delete(format!("{}{}", CACHE_KEY_PREFIX, key));
+	}
+
+	fn keys(&self) -> Vec<String> {
+		let storage = LocalStorage::raw();
+		(0..storage.length().unwrap_or_default())
+			.filter_map(|index| storage.key(index).ok().flatten())
+			.filter_map(|key| key.strip_prefix(CACHE_KEY_PREFIX).map(str::to_string))
+			.collect()
+	}
+}
+
+/// Keeps "persisted" responses in memory, for platforms without local storage and for tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCacheStore(Rc<RefCell<HashMap<String, String>>>);
+
+impl CacheStore for MemoryCacheStore {
+	fn load(&self, key: &str) -> Option<String> {
+		self.0.borrow().get(key).cloned()
+	}
+
+	fn save(&self, key: &str, value: &str) {
+		self.0.borrow_mut().insert(key.to_string(), value.to_string());
+	}
+
+	fn remove(&self, key: &str) {
+		self.0.borrow_mut().remove(key);
+	}
+
+	fn keys(&self) -> Vec<String> {
+		self.0.borrow().keys().cloned().collect()
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+	status: u16,
+	body: String,
+	fetched_at: i64,
+}
+
+impl CacheEntry {
+	fn age(&self) -> Duration {
+		Duration::from_millis((now_millis() - self.fetched_at).max(0) as u64)
+	}
+}
+
+/// What the store's index knows of a persisted entry.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct PersistedEntry {
+	used_at: i64,
+	size: usize,
+}
+
+type Revalidator = Rc<dyn Fn(LocalBoxFuture<'static, ()>)>;
+
+struct CacheState {
+	policy: CachePolicy,
+	entries: RefCell<HashMap<String, CacheEntry>>,
+	/// The entries in the store, evicted least recently used first.
+	persisted: RefCell<HashMap<String, PersistedEntry>>,
+	revalidating: RefCell<HashSet<String>>,
+	store: Option<Rc<dyn CacheStore>>,
+	revalidator: Option<Revalidator>,
+}
+
+impl CacheState {
+	fn lookup(&self, url: &str) -> Option<CacheEntry> {
+		if let Some(entry) = self.entries.borrow().get(url) {
+			self.touch(url);
+			return Some(entry.clone());
+		}
+		let store = self.store.as_ref()?;
+		let Some(entry) =
+			store.load(url).and_then(|stored| serde_json::from_str::<CacheEntry>(&stored).ok())
+		else {
+			self.forget(url);
+			return None;
+		};
+		self.touch(url);
+		self.entries.borrow_mut().insert(url.to_string(), entry.clone());
+		Some(entry)
+	}
+
+	fn insert(&self, url: &str, response: &TransportResponse) {
+		if !(200..300).contains(&response.status) {
+			return;
+		}
+		let entry = CacheEntry {
+			status: response.status,
+			body: response.body.clone(),
+			fetched_at: now_millis(),
+		};
+		if let Ok(serialised) = serde_json::to_string(&entry) {
+			self.persist(url, &serialised);
+		}
+
+		let mut entries = self.entries.borrow_mut();
+		entries.insert(url.to_string(), entry);
+		if entries.len() > MAX_ENTRIES {
+			let oldest = entries
+				.iter()
+				.min_by_key(|(_, entry)| entry.fetched_at)
+				.map(|(url, _)| url.clone());
+			if let Some(oldest) = oldest {
+				entries.remove(&oldest);
+			}
+		}
+	}
+
+	fn remove(&self, url: &str) {
+		self.entries.borrow_mut().remove(url);
+		self.forget(url);
+	}
+
+	/// Reads the store's index, dropping entries it doesn't know of, e.g. those saved before it.
+	fn load_index(&self) {
+		let Some(store) = &self.store else {
+			return;
+		};
+		let index: HashMap<String, PersistedEntry> = store
+			.load(INDEX_KEY)
+			.and_then(|index| serde_json::from_str(&index).ok())
+			.unwrap_or_default();
+		store
+			.keys()
+			.into_iter()
+			.filter(|key| key != INDEX_KEY && !index.contains_key(key))
+			.for_each(|key| store.remove(&key));
+		*self.persisted.borrow_mut() = index;
+	}
+
+	/// Saves an entry to the store, evicting the least recently used ones to keep it within
+	/// [`MAX_ENTRIES`] and [`MAX_PERSISTED_BYTES`].
+	fn persist(&self, url: &str, serialised: &str) {
+		let Some(store) = &self.store else {
+			return;
+		};
+		if serialised.len() > MAX_PERSISTED_BYTES {
+			self.forget(url);
+			return;
+		}
+
+		let mut persisted = self.persisted.borrow_mut();
+		let entry = PersistedEntry { used_at: now_millis(), size: serialised.len() };
+		persisted.insert(url.to_string(), entry);
+		while persisted.len() > MAX_ENTRIES
+			|| persisted.values().map(|entry| entry.size).sum::<usize>() > MAX_PERSISTED_BYTES
+		{
+			let oldest = persisted
+				.iter()
+				.filter(|(key, _)| key.as_str() != url)
+				.min_by_key(|(_, entry)| entry.used_at)
+				.map(|(key, _)| key.clone());
+			let Some(oldest) = oldest else {
+				break;
+			};
+			persisted.remove(&oldest);
+			store.remove(&oldest);
+		}
+		store.save(url, serialised);
+		drop(persisted);
+		self.save_index();
+	}
+
+	/// Marks an entry as just used. Only the in-memory index is updated, so reads never write to
+	/// the store; the new time is saved with the index on the next insert or eviction.
+	fn touch(&self, url: &str) {
+		if let Some(entry) = self.persisted.borrow_mut().get_mut(url) {
+			entry.used_at = now_millis();
+		}
+	}
+
+	fn forget(&self, url: &str) {
+		if let Some(store) = &self.store {
+			store.remove(url);
+			if self.persisted.borrow_mut().remove(url).is_some() {
+				self.save_index();
+			}
+		}
+	}
+
+	fn save_index(&self) {
+		if let (Some(store), Ok(index)) =
+			(&self.store, serde_json::to_string(&*self.persisted.borrow()))
+		{
+			store.save(INDEX_KEY, &index);
+		}
+	}
+}
+
+/// Wraps another [`Transport`] with a response cache keyed by url.
+///
+/// Fresh entries are served without touching the network. Entries past their TTL but within
+/// [`CachePolicy::max_stale`] are served immediately while a revalidator refreshes them in the
+/// background; without a revalidator they are refetched, falling back to the stale copy if the
+/// request fails.
+#[derive(Clone)]
+pub struct CachedTransport<T> {
+	inner: T,
+	state: Rc<CacheState>,
+}
+
+impl<T> CachedTransport<T> {
+	pub fn new(inner: T, policy: CachePolicy) -> Self {
+		let state = CacheState {
+			policy,
+			entries: RefCell::new(HashMap::new()),
+			persisted: RefCell::new(HashMap::new()),
+			revalidating: RefCell::new(HashSet::new()),
+			store: None,
+			revalidator: None,
+		};
+		Self { inner, state: Rc::new(state) }
+	}
+
+	/// Persists cached responses so they survive reloads. Must be set before the cache is shared.
+	pub fn with_store(mut self, store: impl CacheStore + 'static) -> Self {
+		if let Some(state) = Rc::get_mut(&mut self.state) {
+			state.store = Some(Rc::new(store));
+			state.load_index();
+		}
+		self
+	}
+
+	/// Runs background revalidations, e.g. with `spawn_local`. Must be set before the cache is
+	/// shared.
+	pub fn with_revalidator(
+		mut self,
+		revalidator: impl Fn(LocalBoxFuture<'static, ()>) + 'static,
+	) -> Self {
+		if let Some(state) = Rc::get_mut(&mut self.state) {
+			state.revalidator = Some(Rc::new(revalidator));
+		}
+		self
+	}
+
+	pub fn inner(&self) -> &T {
+		&self.inner
+	}
+
+	pub fn invalidate(&self, url: &str) {
+		self.state.remove(url);
+	}
+
+	pub fn clear(&self) {
+		let mut urls: HashSet<String> = self.state.entries.borrow().keys().cloned().collect();
+		urls.extend(self.state.persisted.borrow().keys().cloned());
+		urls.iter().for_each(|url| self.state.remove(url));
+	}
+}
+
+impl<T: Transport + Clone + 'static> CachedTransport<T> {
+	fn revalidate(&self, url: &str, revalidator: &Revalidator) {
+		if !self.state.revalidating.borrow_mut().insert(url.to_string()) {
+			return;
+		}
+		let inner = self.inner.clone();
+		let state = self.state.clone();
+		let url = url.to_string();
+		revalidator(Box::pin(async move {
+			if let Ok(response) = inner.get(&url).await {
+				state.insert(&url, &response);
+			}
+			state.revalidating.borrow_mut().remove(&url);
+		}));
+	}
+}
+
+impl<T: Transport + Clone + 'static> Transport for CachedTransport<T> {
+	async fn get(&self, url: &str) -> Result<TransportResponse, RustyTubeError> {
+		let Some(ttl) = self.state.policy.ttl(url) else {
+			return self.inner.get(url).await;
+		};
+
+		let max_stale = self.state.policy.max_stale(url);
+		let cached = self.state.lookup(url).filter(|entry| {
+			let expired = entry.age() > ttl + max_stale;
+			if expired {
+				self.state.remove(url);
+			}
+			!expired
+		});
+		let Some(entry) = cached else {
+			let response = self.inner.get(url).await?;
+			self.state.insert(url, &response);
+			return Ok(response);
+		};
+
+		let stale = TransportResponse::new(entry.status, entry.body.clone());
+		if entry.age() < ttl {
+			return Ok(stale);
+		}
+		if let Some(revalidator) = &self.state.revalidator {
+			self.revalidate(url, revalidator);
+			return Ok(stale);
+		}
+		match self.inner.get(url).await {
+			Ok(response) if (200..300).contains(&response.status) => {
+				self.state.insert(url, &response);
+				Ok(response)
+			}
+			_ => Ok(stale),
+		}
+	}
+}
+
+fn now_millis() -> i64 {
+	Utc::now().timestamp_millis()
+}
@@ -0,0 +1,159 @@
+use std::{cell::RefCell, pin::pin, rc::Rc, time::Duration};
+
+use futures::future::{select, Either};
+use rustytube_error::RustyTubeError;
+
+use super::{Transport, TransportResponse};
+use crate::{error::ApiError, timer::sleep};
+
+/// How long an instance may take to answer before the request moves on to the next one.
+pub const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Emitted by [`FailoverTransport`] whenever it starts serving requests from another instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceSwitch {
+	pub from: String,
+	pub to: String,
+}
+
+#[derive(Default)]
+struct FailoverState {
+	servers: Vec<String>,
+	origin: Option<String>,
+	active: Option<String>,
+	on_switch: Option<Rc<dyn Fn(InstanceSwitch)>>,
+}
+
+/// Wraps another [`Transport`] and retries failed Invidious API requests on other instances.
+///
+/// A request fails over when the inner transport errors, the instance doesn't answer within the
+/// attempt timeout or it answers with a retryable [`ApiError`], such as a 5xx status or rate
+/// limiting. The first instance that answers
+/// is remembered and tried first until the requested instance changes, e.g. because the user
+/// picked another one. Only `/api/v1/` and `/feed/` requests are rewritten, so third-party APIs
+/// are never redirected.
+#[derive(Clone)]
+pub struct FailoverTransport<T> {
+	inner: T,
+	state: Rc<RefCell<FailoverState>>,
+	attempt_timeout: Duration,
+}
+
+impl<T> FailoverTransport<T> {
+	pub fn new(inner: T, servers: Vec<String>) -> Self {
+		let state = FailoverState { servers, ..Default::default() };
+		Self { inner, state: Rc::new(RefCell::new(state)), attempt_timeout: ATTEMPT_TIMEOUT }
+	}
+
+	/// Replaces [`ATTEMPT_TIMEOUT`] as the time each instance gets to answer.
+	pub fn with_attempt_timeout(mut self, attempt_timeout: Duration) -> Self {
+		self.attempt_timeout = attempt_timeout;
+		self
+	}
+
+	pub fn set_servers(&self, servers: Vec<String>) {
+		self.state.borrow_mut().servers = servers;
+	}
+
+	pub fn servers(&self) -> Vec<String> {
+		self.state.borrow().servers.clone()
+	}
+
+	pub fn active_server(&self) -> Option<String> {
+		self.state.borrow().active.clone()
+	}
+
+	pub fn on_switch(&self, handler: impl Fn(InstanceSwitch) + 'static) {
+		self.state.borrow_mut().on_switch = Some(Rc::new(handler));
+	}
+
+	fn candidates(&self, origin: &str) -> Vec<String> {
+		let mut state = self.state.borrow_mut();
+		if state.origin.as_deref() != Some(origin) {
+			if state.active.as_deref() != Some(origin) {
+				state.active = None;
+			}
+			state.origin = Some(origin.to_owned());
+		}
+
+		let mut candidates: Vec<String> = Vec::new();
+		state
+			.active
+			.iter()
+			.map(String::as_str)
+			.chain(std::iter::once(origin))
+			.chain(state.servers.iter().map(String::as_str))
+			.map(|server| server.trim_end_matches('/').to_owned())
+			.for_each(|server| {
+				if !server.is_empty() && !candidates.contains(&server) {
+					candidates.push(server);
+				}
+			});
+		candidates
+	}
+
+	fn remember(&self, origin: &str, server: &str) {
+		let previous = self.active_server().unwrap_or(origin.to_owned());
+		if previous.eq(server) {
+			return;
+		}
+		let handler = {
+			let mut state = self.state.borrow_mut();
+			state.active = Some(server.to_owned());
+			state.on_switch.clone()
+		};
+		if let Some(handler) = handler {
+			handler(InstanceSwitch { from: previous, to: server.to_owned() });
+		}
+	}
+}
+
+impl<T: Transport> Transport for FailoverTransport<T> {
+	async fn get(&self, url: &str) -> Result<TransportResponse, RustyTubeError> {
+		let Some((origin, path)) = split_api_url(url) else {
+			return self.inner.get(url).await;
+		};
+		let origin = origin.trim_end_matches('/');
+
+		let mut last_result = None;
+		for server in self.candidates(origin) {
+			let result = self.attempt(&format!("{}{}", server, path)).await;
+			match result {
+				Ok(response) if !is_retryable(&response) => {
+					self.remember(origin, &server);
+					return Ok(response);
+				}
+				_ => last_result = Some(result),
+			}
+		}
+		match last_result {
+			Some(result) => result,
+			None => self.attempt(url).await,
+		}
+	}
+}
+
+impl<T: Transport> FailoverTransport<T> {
+	/// Requests `url` from the inner transport, giving up once the attempt timeout passes.
+	async fn attempt(&self, url: &str) -> Result<TransportResponse, RustyTubeError> {
+		let request = pin!(self.inner.get(url));
+		let timeout = pin!(sleep(self.attempt_timeout));
+		match select(request, timeout).await {
+			Either::Left((result, _)) => result,
+			Either::Right(_) => Err(RustyTubeError::request_timeout(url, self.attempt_timeout)),
+		}
+	}
+}
+
+fn is_retryable(response: &TransportResponse) -> bool {
+	ApiError::from_response(response).is_some_and(|api_error| api_error.is_retryable())
+}
+
+/// Splits an Invidious API url into its instance origin and the path that follows it.
+fn split_api_url(url: &str) -> Option<(&str, &str)> {
+	let scheme_end = url.find("://")? + 3;
+	let path_start = url[scheme_end..].find('/').map(|index| index + scheme_end)?;
+	let (origin, path) = url.split_at(path_start);
+	let is_api = path.starts_with("/api/v1/") || path.starts_with("/feed/");
+	is_api.then_some((origin, path))
+}
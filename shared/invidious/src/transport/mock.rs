@@ -0,0 +1,45 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use rustytube_error::RustyTubeError;
+
+use super::{Transport, TransportResponse};
+
+/// In-memory transport that serves canned responses keyed by URL.
+///
+/// Every requested URL is recorded so tests can assert on what was fetched.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+	responses: HashMap<String, TransportResponse>,
+	requests: RefCell<Vec<String>>,
+}
+
+impl MockTransport {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_response(self, url: impl Into<String>, body: impl Into<String>) -> Self {
+		self.with_status(url, 200, body)
+	}
+
+	pub fn with_status(
+		mut self,
+		url: impl Into<String>,
+		status: u16,
+		body: impl Into<String>,
+	) -> Self {
+		self.responses.insert(url.into(), TransportResponse::new(status, body));
+		self
+	}
+
+	pub fn requests(&self) -> Vec<String> {
+		self.requests.borrow().clone()
+	}
+}
+
+impl Transport for MockTransport {
+	async fn get(&self, url: &str) -> Result<TransportResponse, RustyTubeError> {
+		self.requests.borrow_mut().push(url.to_owned());
+		self.responses.get(url).cloned().ok_or(RustyTubeError::no_mock_response(url))
+	}
+}
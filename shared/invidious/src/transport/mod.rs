@@ -0,0 +1,53 @@
+mod browser;
+mod cache;
+mod failover;
+mod mock;
+#[cfg(feature = "native")]
+mod native;
+
+use std::{future::Future, rc::Rc};
+
+pub use browser::*;
+pub use cache::*;
+pub use failover::*;
+pub use mock::*;
+#[cfg(feature = "native")]
+pub use native::*;
+use rustytube_error::RustyTubeError;
+
+/// Raw response returned by a [`Transport`] before any deserialisation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportResponse {
+	pub status: u16,
+	pub body: String,
+}
+
+impl TransportResponse {
+	pub fn new(status: u16, body: impl Into<String>) -> Self {
+		Self { status, body: body.into() }
+	}
+
+	pub fn ok(body: impl Into<String>) -> Self {
+		Self::new(200, body)
+	}
+}
+
+/// The HTTP layer every endpoint in this crate is fetched through.
+///
+/// [`BrowserTransport`] is used in the frontend, [`NativeTransport`] (behind the `native`
+/// feature) outside of a browser and [`MockTransport`] in tests.
+pub trait Transport {
+	fn get(&self, url: &str) -> impl Future<Output = Result<TransportResponse, RustyTubeError>>;
+}
+
+impl<T: Transport + ?Sized> Transport for &T {
+	fn get(&self, url: &str) -> impl Future<Output = Result<TransportResponse, RustyTubeError>> {
+		(**self).get(url)
+	}
+}
+
+impl<T: Transport + ?Sized> Transport for Rc<T> {
+	fn get(&self, url: &str) -> impl Future<Output = Result<TransportResponse, RustyTubeError>> {
+		(**self).get(url)
+	}
+}
@@ -0,0 +1,32 @@
+use rustytube_error::{ErrorKind, RustyTubeError};
+
+use super::{Transport, TransportResponse};
+
+/// Transport for running the client outside of a browser, e.g. in native tools.
+#[derive(Debug, Clone, Default)]
+pub struct NativeTransport {
+	client: reqwest::Client,
+}
+
+impl NativeTransport {
+	pub fn new(client: reqwest::Client) -> Self {
+		Self { client }
+	}
+}
+
+impl Transport for NativeTransport {
+	async fn get(&self, url: &str) -> Result<TransportResponse, RustyTubeError> {
+		let response = self.client.get(url).send().await.map_err(network_error)?;
+		let status = response.status().as_u16();
+		let body = response.text().await.map_err(network_error)?;
+		Ok(TransportResponse { status, body })
+	}
+}
+
+fn network_error(reqwest_error: reqwest::Error) -> RustyTubeError {
+	RustyTubeError::new(
+		ErrorKind::Network,
+		String::from("Network Error"),
+		reqwest_error.to_string(),
+	)
+}
@@ -0,0 +1,115 @@
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+
+use crate::{common::CommonVideo, fetch::fetch, transport::Transport};
+
+/// A page of the videos tagged with a hashtag.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Hashtag {
+	pub tag: String,
+	pub page: u32,
+	pub videos: Vec<CommonVideo>,
+}
+
+#[derive(Deserialize)]
+struct HashtagResults {
+	results: Vec<CommonVideo>,
+}
+
+impl Hashtag {
+	/// Fetches a page of the videos tagged `tag`, given without the leading `#`. Pages start at 1
+	/// and the first empty one marks the end.
+	pub async fn fetch_hashtag(
+		transport: &impl Transport,
+		server: &str,
+		tag: &str,
+		page: u32,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
+		let url =
+			format!("{server}/api/v1/hashtag/{}?page={page}&hl={lang}", urlencoding::encode(tag));
+		let hashtag_json = fetch(transport, &url).await?;
+		let results: HashtagResults = serde_json::from_str(&hashtag_json)?;
+		Ok(Self { tag: tag.to_string(), page, videos: results.results })
+	}
+
+	pub async fn fetch_next_page(
+		&self,
+		transport: &impl Transport,
+		server: &str,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
+		Self::fetch_hashtag(transport, server, &self.tag, self.page + 1, lang).await
+	}
+}
+
+/// Points the hashtags of a description or comment at the `/hashtag` page: links to Invidious or
+/// YouTube hashtag pages are redirected and hashtags left as plain text are linked.
+pub fn link_hashtags(html: &str) -> String {
+	let mut linked = String::with_capacity(html.len());
+	let mut in_anchor = false;
+	let mut rest = html;
+
+	while let Some(start) = rest.find(['<', '#']) {
+		let (text, from) = rest.split_at(start);
+		linked.push_str(text);
+
+		if from.starts_with('<') {
+			let end = from.find('>').map_or(from.len(), |end| end + 1);
+			let element = &from[..end];
+			if element.starts_with("<a ") {
+				in_anchor = true;
+				linked.push_str(&redirect_hashtag_anchor(element));
+			} else {
+				in_anchor &= !element.starts_with("</a");
+				linked.push_str(element);
+			}
+			rest = &from[end..];
+			continue;
+		}
+
+		let tag_len = from[1..]
+			.find(|char: char| !char.is_alphanumeric() && char != '_')
+			.unwrap_or(from.len() - 1);
+		let tag = &from[1..1 + tag_len];
+		let follows_word = linked
+			.chars()
+			.last()
+			.is_some_and(|char| char.is_alphanumeric() || matches!(char, '&' | '/' | '_'));
+		if in_anchor || follows_word || !tag.chars().any(char::is_alphabetic) {
+			linked.push('#');
+			rest = &from[1..];
+			continue;
+		}
+		linked.push_str(&format!("<a href=\"{}\">#{}</a>", hashtag_url(tag), tag));
+		rest = &from[1 + tag_len..];
+	}
+	linked.push_str(rest);
+	linked
+}
+
+fn redirect_hashtag_anchor(element: &str) -> String {
+	let Some(href_start) = element.find("href=\"").map(|start| start + "href=\"".len()) else {
+		return element.to_string();
+	};
+	let href_len = element[href_start..].find('"').unwrap_or(element.len() - href_start);
+	let href = &element[href_start..href_start + href_len];
+
+	match href.split_once("/hashtag/") {
+		Some((_, tag)) => {
+			let tag = tag.split(['?', '/']).next().unwrap_or_default();
+			let tag = urlencoding::decode(tag).map(|tag| tag.into_owned()).unwrap_or_default();
+			format!(
+				"{}{}{}",
+				&element[..href_start],
+				hashtag_url(&tag),
+				&element[href_start + href_len..]
+			)
+		}
+		None => element.to_string(),
+	}
+}
+
+fn hashtag_url(tag: &str) -> String {
+	format!("/hashtag?tag={}", urlencoding::encode(tag))
+}
@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch::fetch, hidden::MixVideo};
+use crate::{fetch::fetch, hidden::MixVideo, transport::Transport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Mix {
@@ -12,10 +12,51 @@ pub struct Mix {
 }
 
 impl Mix {
-	async fn fetch_mix(server: &str, args: &str, lang: &str) -> Result<Self, RustyTubeError> {
-		let mix_url: String = format!("{}/api/v1/mixes/{}", server, args);
-		let mix_json: String = fetch(&mix_url).await?;
+	fn url(server: &str, id: &str, lang: &str) -> String {
+		format!("{}/api/v1/mixes/{}?hl={}", server, id, lang)
+	}
+
+	/// Id of the radio mix YouTube generates for a video.
+	pub fn radio_id(video_id: &str) -> String {
+		format!("RD{}", video_id)
+	}
+
+	pub async fn fetch_mix(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
+		let mix_url: String = Self::url(server, id, lang);
+		let mix_json: String = fetch(transport, &mix_url).await?;
 		let mix: Self = serde_json::from_str(&mix_json)?;
 		Ok(mix)
 	}
+
+	pub async fn fetch_radio(
+		transport: &impl Transport,
+		server: &str,
+		video_id: &str,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
+		Self::fetch_mix(transport, server, &Self::radio_id(video_id), lang).await
+	}
+
+	pub fn position(&self, video_id: &str) -> Option<usize> {
+		self.videos.iter().position(|video| video.id == video_id)
+	}
+
+	/// The video played after `video_id`, or the start of the mix if it isn't part of it.
+	pub fn next_after(&self, video_id: &str) -> Option<&MixVideo> {
+		match self.position(video_id) {
+			Some(position) => self.videos.get(position + 1),
+			None => self.videos.first(),
+		}
+	}
+}
+
+impl PartialEq for Mix {
+	fn eq(&self, other: &Self) -> bool {
+		self.id == other.id
+	}
 }
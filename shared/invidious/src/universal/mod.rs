@@ -1,3 +1,4 @@
+mod hashtag;
 mod mixes;
 mod playlists;
 mod popular;
@@ -6,6 +7,7 @@ mod stats;
 mod suggestions;
 mod trending;
 
+pub use hashtag::*;
 pub use mixes::*;
 pub use playlists::*;
 pub use popular::*;
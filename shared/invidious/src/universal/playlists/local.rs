@@ -8,7 +8,7 @@ use utils::{get_current_time_rfc, load_all_from_browser_storage, save_to_browser
 
 use crate::universal::{
 	playlists::{freetube::read_freetube_playlists, libretube::read_libretube_playlists},
-	read_playlist_csv,
+	read_playlist_csv, Playlist,
 };
 
 pub const LOCAL_PLAYLIST_PREFIX: &'static str = "rt_playlist_";
@@ -30,6 +30,20 @@ pub struct LocalPlaylistItem {
 	pub id: String,
 }
 
+impl Into<LocalPlaylist> for Playlist {
+	fn into(self) -> LocalPlaylist {
+		let title = self.title;
+		let video_count = self.videos.len() as u32;
+		let updated = utils::get_current_time();
+		let created = updated;
+
+		let mut videos: Vec<LocalPlaylistItem> = Vec::new();
+		self.videos.into_iter().for_each(|video| videos.push(LocalPlaylistItem { id: video.id }));
+
+		LocalPlaylist { title, video_count, updated, created, videos }
+	}
+}
+
 impl LocalPlaylist {
 	pub async fn create(title: &str) -> Result<Self, RustyTubeError> {
 		let title = title.to_string();
@@ -3,14 +3,14 @@ mod freetube;
 mod libretube;
 mod local;
 
-pub use csv_playlist::*;
-pub use freetube::*;
-pub use libretube::*;
-pub use local::*;
+use std::collections::HashSet;
+
+use futures::{stream, Stream, StreamExt};
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{common::CommonImage, fetch::fetch, hidden::PlaylistItem};
+pub use self::{csv_playlist::*, freetube::*, libretube::*, local::*};
+use crate::{common::CommonImage, fetch::fetch, hidden::PlaylistItem, transport::Transport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Playlist {
@@ -40,19 +40,80 @@ pub struct Playlist {
 	pub videos: Vec<PlaylistItem>,
 }
 
+/// How far [`Playlist::fetch_all`] has got through a playlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaylistProgress {
+	pub fetched: usize,
+	pub total: u32,
+}
+
 impl Playlist {
-	fn url(server: &str, args: &str) -> String {
-		format!("{}/api/v1/playlists/{}", server, args)
+	fn url(server: &str, id: &str, page: Option<u32>) -> String {
+		match page {
+			Some(page) => format!("{}/api/v1/playlists/{}?page={}", server, id, page),
+			None => format!("{}/api/v1/playlists/{}", server, id),
+		}
 	}
 
+	/// Fetches a single page of the playlist, or the first page when `page` is `None`.
 	pub async fn fetch_playlist(
+		transport: &impl Transport,
 		server: &str,
 		id: &str,
-		args: Option<&str>,
+		page: Option<u32>,
 	) -> Result<Self, RustyTubeError> {
-		let url = Self::url(server, id);
-		let playlist_json = fetch(&url).await?;
+		let url = Self::url(server, id, page);
+		let playlist_json = fetch(transport, &url).await?;
 		let playlist: Self = serde_json::from_str(&playlist_json)?;
 		Ok(playlist)
 	}
+
+	/// Yields the playlist page by page, each holding only videos not seen on earlier pages.
+	///
+	/// The first page is always yielded. Paging stops once `video_count` videos have been seen or
+	/// a page brings nothing new, as hidden and deleted videos count towards `video_count`.
+	pub fn stream_pages<'a>(
+		transport: &'a impl Transport,
+		server: &'a str,
+		id: &'a str,
+	) -> impl Stream<Item = Result<Self, RustyTubeError>> + 'a {
+		stream::unfold(Some((1, HashSet::new())), move |state| async move {
+			let (page, mut seen) = state?;
+			match Self::fetch_playlist(transport, server, id, Some(page)).await {
+				Ok(mut playlist) => {
+					playlist.videos.retain(|video| seen.insert(video.index));
+					if playlist.videos.is_empty() && page > 1 {
+						return None;
+					}
+					let complete =
+						playlist.videos.is_empty() || seen.len() >= playlist.video_count as usize;
+					let next = (!complete).then_some((page + 1, seen));
+					Some((Ok(playlist), next))
+				}
+				Err(err) => Some((Err(err), None)),
+			}
+		})
+	}
+
+	/// Fetches every page of the playlist, reporting progress after each one.
+	pub async fn fetch_all(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+		on_progress: impl Fn(PlaylistProgress),
+	) -> Result<Self, RustyTubeError> {
+		let mut pages = Box::pin(Self::stream_pages(transport, server, id));
+		let mut playlist = pages.next().await.ok_or(RustyTubeError::parse_playlist_fail())??;
+		on_progress(playlist.progress());
+
+		while let Some(page) = pages.next().await {
+			playlist.videos.extend(page?.videos);
+			on_progress(playlist.progress());
+		}
+		Ok(playlist)
+	}
+
+	pub fn progress(&self) -> PlaylistProgress {
+		PlaylistProgress { fetched: self.videos.len(), total: self.video_count }
+	}
 }
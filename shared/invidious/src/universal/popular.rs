@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch::fetch, hidden::PopularItem};
+use crate::{fetch::fetch, hidden::PopularItem, transport::Transport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Popular {
@@ -9,9 +9,13 @@ pub struct Popular {
 }
 
 impl Popular {
-	pub async fn fetch_popular(server: &str, lang: &str) -> Result<Self, RustyTubeError> {
+	pub async fn fetch_popular(
+		transport: &impl Transport,
+		server: &str,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
 		let url = format!("{server}/api/v1/popular?hl={lang}");
-		let popular_json = fetch(&url).await?;
+		let popular_json = fetch(transport, &url).await?;
 		let items: Vec<PopularItem> = serde_json::from_str(&popular_json)?;
 		Ok(Popular { items })
 	}
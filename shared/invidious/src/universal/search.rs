@@ -3,7 +3,11 @@ use std::str::FromStr;
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch::fetch, hidden::SearchResult};
+use crate::{
+	fetch::fetch,
+	hidden::{CountryCode, SearchResult},
+	transport::Transport,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SearchResults {
@@ -12,18 +16,18 @@ pub struct SearchResults {
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct SearchArgs {
-	page: Option<u32>,
+	pub page: Option<u32>,
 	pub query: String,
 	pub sort: Option<Sort>,
 	pub timespan: Option<TimeSpan>,
 	pub duration: Option<Duration>,
 	pub response_type: Option<ResponseType>,
 	pub features: Option<Vec<Feature>>,
-	// pub region: Option<CountryCode>,
+	pub region: Option<CountryCode>,
 }
 
 impl SearchArgs {
-	pub fn from_str(query: String) -> Self {
+	pub fn from_query(query: String) -> Self {
 		Self { query, ..Default::default() }
 	}
 
@@ -34,45 +38,115 @@ impl SearchArgs {
 		duration: Option<Duration>,
 		response_type: Option<ResponseType>,
 		features: Option<Vec<Feature>>,
-		// region: Option<CountryCode>
+		region: Option<CountryCode>,
 	) -> Self {
 		let page = Some(1);
 
-		Self { page, query, sort, timespan, duration, response_type, features }
+		Self { page, query, sort, timespan, duration, response_type, features, region }
 	}
 
+	/// The same search, on another page of results.
+	pub fn with_page(&self, page: u32) -> Self {
+		Self { page: Some(page), ..self.clone() }
+	}
+
+	/// The query string of this search, as understood by Invidious and by [`SearchArgs::from_str`].
 	pub fn to_url(&self) -> String {
-		let mut url: String = format!("?q={}", self.query);
-
-		self.timespan.map(|timespan| url.push_str(&format!("&date={}", timespan.to_string())));
-		self.duration.map(|duration| url.push_str(&format!("&duration={}", duration.to_string())));
-		self.response_type
-			.map(|response_type| url.push_str(&format!("&type={}", response_type.to_string())));
-		self.features.clone().map(|features| {
-			let mut features_string = String::from("&features=");
-			features.iter().enumerate().for_each(|(index, feature)| match index < features.len() {
-				true => features_string.push_str(&format!("{},", feature.to_string())),
-				false => features_string.push_str(&format!("{}", feature.to_string())),
-			});
-			url.push_str(&features_string);
-		});
+		let mut url: String = format!("?q={}", urlencoding::encode(&self.query));
+
+		if let Some(sort) = self.sort {
+			url.push_str(&format!("&sort={}", sort.to_string()));
+		}
+		if let Some(timespan) = self.timespan {
+			url.push_str(&format!("&date={}", timespan.to_string()));
+		}
+		if let Some(duration) = self.duration {
+			url.push_str(&format!("&duration={}", duration.to_string()));
+		}
+		if let Some(response_type) = self.response_type {
+			url.push_str(&format!("&type={}", response_type.to_string()));
+		}
+		if let Some(features) = self.features.as_ref().filter(|features| !features.is_empty()) {
+			let features =
+				features.iter().map(|feature| feature.to_string()).collect::<Vec<String>>();
+			url.push_str(&format!("&features={}", features.join(",")));
+		}
+		if let Some(region) = self.region {
+			url.push_str(&format!("&region={}", region));
+		}
+		if let Some(page) = self.page {
+			url.push_str(&format!("&page={}", page));
+		}
 		url
 	}
 }
 
+impl FromStr for SearchArgs {
+	type Err = RustyTubeError;
+
+	/// Parses a query string such as `?q=rust&sort=views&features=hd,subtitles`. Parameters
+	/// Invidious does not know are ignored.
+	fn from_str(url: &str) -> Result<Self, Self::Err> {
+		let query_string = url.split_once('?').map_or(url, |(_, query_string)| query_string);
+		let mut args = Self::default();
+
+		for param in query_string.split('&').filter(|param| !param.is_empty()) {
+			let (key, value) = param.split_once('=').unwrap_or((param, ""));
+			let value = urlencoding::decode(&value.replace('+', " "))
+				.map_err(|_| RustyTubeError::search_url_parse())?
+				.into_owned();
+			match key {
+				"q" => args.query = value,
+				"page" => {
+					args.page = Some(value.parse().map_err(|_| RustyTubeError::search_url_parse())?)
+				}
+				"sort" => args.sort = Some(Sort::from_str(&value)?),
+				"date" => args.timespan = Some(TimeSpan::from_str(&value)?),
+				"duration" => args.duration = Some(Duration::from_str(&value)?),
+				"type" => args.response_type = Some(ResponseType::from_str(&value)?),
+				"features" => {
+					args.features = Some(
+						value
+							.split(',')
+							.filter(|feature| !feature.is_empty())
+							.map(Feature::from_str)
+							.collect::<Result<Vec<Feature>, RustyTubeError>>()?,
+					)
+				}
+				"region" => args.region = Some(CountryCode::from_str(&value)?),
+				_ => (),
+			}
+		}
+		Ok(args)
+	}
+}
+
 impl SearchResults {
+	/// Fetches the page of results `args` points to, the first one if it has none.
 	pub async fn fetch_search_results(
+		transport: &impl Transport,
 		server: &str,
-		args: SearchArgs,
-		page_number: u32,
+		args: &SearchArgs,
 		lang: &str,
 	) -> Result<Self, RustyTubeError> {
-		let url =
-			format!("{}/api/v1/search{}&page={}&hl={}", server, args.to_url(), page_number, lang);
-		let search_json = fetch(&url).await?;
+		let args = args.with_page(args.page.unwrap_or(1));
+		let url = format!("{}/api/v1/search{}&hl={}", server, args.to_url(), lang);
+		let search_json = fetch(transport, &url).await?;
 		let items: Vec<SearchResult> = serde_json::from_str(&search_json)?;
 		Ok(Self { items })
 	}
+
+	/// Adds the results of a further page, skipping those already listed as Invidious often
+	/// repeats items across pages. Returns how many results were added.
+	pub fn append(&mut self, page: SearchResults) -> usize {
+		let before = self.items.len();
+		for item in page.items {
+			if !self.items.iter().any(|listed| listed.id() == item.id()) {
+				self.items.push(item);
+			}
+		}
+		self.items.len() - before
+	}
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
@@ -121,7 +195,7 @@ impl ToString for TimeSpan {
 	fn to_string(&self) -> String {
 		match self {
 			TimeSpan::Hour => String::from("hour"),
-			TimeSpan::Day => String::from("day"),
+			TimeSpan::Day => String::from("today"),
 			TimeSpan::Week => String::from("week"),
 			TimeSpan::Month => String::from("month"),
 			TimeSpan::Year => String::from("year"),
@@ -135,7 +209,7 @@ impl FromStr for TimeSpan {
 	fn from_str(duration_str: &str) -> Result<Self, Self::Err> {
 		match duration_str {
 			"hour" => Ok(TimeSpan::Hour),
-			"day" => Ok(TimeSpan::Day),
+			"today" | "day" => Ok(TimeSpan::Day),
 			"week" => Ok(TimeSpan::Week),
 			"month" => Ok(TimeSpan::Month),
 			"year" => Ok(TimeSpan::Year),
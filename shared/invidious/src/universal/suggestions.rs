@@ -2,7 +2,7 @@ use html_escape::decode_html_entities;
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::fetch;
+use crate::{fetch, transport::Transport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -13,12 +13,13 @@ pub struct Suggestions {
 
 impl Suggestions {
 	pub async fn fetch_suggestions(
+		transport: &impl Transport,
 		query: &str,
 		server: &str,
 		lang: &str,
 	) -> Result<Suggestions, RustyTubeError> {
 		let url = format!("{}/api/v1/search/suggestions?q={}&hl={}", server, query, lang);
-		let suggestions_json = fetch(&url).await?;
+		let suggestions_json = fetch(transport, &url).await?;
 		let mut suggestions = serde_json::from_str::<Suggestions>(&suggestions_json)?;
 		let decoded_suggestions = suggestions
 			.suggestions
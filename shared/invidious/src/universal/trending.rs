@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{common::CommonVideo, fetch::fetch};
+use crate::{common::CommonVideo, fetch::fetch, transport::Transport};
 
 #[derive(PartialEq, Clone)]
 pub enum TrendingCategory {
@@ -33,13 +33,14 @@ impl Trending {
 	}
 
 	pub async fn fetch_trending(
+		transport: &impl Transport,
 		server: &str,
 		category: &TrendingCategory,
 		region: &str,
 		lang: &str,
 	) -> Result<Self, RustyTubeError> {
 		let url = Self::url(server, category, region, lang);
-		let trending_json = fetch(&url).await?;
+		let trending_json = fetch(transport, &url).await?;
 		let videos: Vec<CommonVideo> = serde_json::from_str(&trending_json)?;
 		Ok(Trending { videos })
 	}
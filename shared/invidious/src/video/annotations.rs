@@ -1,28 +1,264 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::fetch::fetch;
+use crate::{fetch::fetch, transport::Transport};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Legacy YouTube annotations for a video, parsed from the XML archive served by Invidious.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct Annotations {
-	pub inner: String,
+	pub items: Vec<Annotation>,
 }
 
-impl From<String> for Annotations {
-	fn from(inner: String) -> Self {
-		Self { inner }
-	}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Annotation {
+	pub id: String,
+	pub style: AnnotationStyle,
+	pub text: Option<String>,
+	/// Seconds into the video at which the annotation appears.
+	pub start: f64,
+	/// Seconds into the video at which the annotation disappears.
+	pub end: f64,
+	pub region: AnnotationRegion,
+	pub appearance: AnnotationAppearance,
+	pub link: Option<AnnotationLink>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AnnotationStyle {
+	Popup,
+	Speech,
+	Note,
+	Title,
+	Label,
+	/// A clickable box drawn over part of the video, usually without any text.
+	Highlight,
+}
+
+/// Position and size of an annotation as percentages of the video's width and height.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct AnnotationRegion {
+	pub x: f64,
+	pub y: f64,
+	pub width: f64,
+	pub height: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct AnnotationAppearance {
+	/// CSS hex colour, e.g. `#ffffff`.
+	pub background: Option<String>,
+	pub background_alpha: Option<f64>,
+	pub foreground: Option<String>,
+	/// Font size as a percentage of the video's height.
+	pub text_size: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum AnnotationLink {
+	/// Another YouTube video, optionally starting at the given second.
+	Video {
+		id: String,
+		start: Option<u32>,
+	},
+	Url(String),
 }
 
 impl Annotations {
-	fn url(server: &str, args: &str) -> String {
-		format!("{server}/api/v1/annotations/{args}")
+	fn url(server: &str, id: &str) -> String {
+		format!("{server}/api/v1/annotations/{id}")
+	}
+
+	pub async fn fetch_annotations(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+	) -> Result<Self, RustyTubeError> {
+		let annotations_url: String = Self::url(server, id);
+		let annotations_xml: String = fetch(transport, &annotations_url).await?;
+		Self::parse(&annotations_xml)
+	}
+
+	/// Parses an annotations document, skipping annotations that are never shown or have a type
+	/// we can't display, such as branding watermarks and cards.
+	pub fn parse(xml: &str) -> Result<Self, RustyTubeError> {
+		if xml.trim().is_empty() {
+			return Ok(Self::default());
+		}
+		let document: AnnotationsDocument = serde_xml_rs::from_str(xml)?;
+		let items =
+			document.annotations.items.into_iter().filter_map(Annotation::from_raw).collect();
+		Ok(Self { items })
 	}
 
-	async fn fetch(server: &str, args: &str) -> Result<Self, RustyTubeError> {
-		let annotations_url: String = Self::url(server, args);
-		let annotations_json: String = fetch(&annotations_url).await?;
-		let annotations: Self = serde_json::from_str(&annotations_json)?;
-		Ok(annotations)
+	pub fn active_at(&self, time: f64) -> impl Iterator<Item = &Annotation> {
+		self.items.iter().filter(move |annotation| annotation.is_active(time))
 	}
 }
+
+impl Annotation {
+	pub fn is_active(&self, time: f64) -> bool {
+		self.start <= time && time < self.end
+	}
+
+	fn from_raw(raw: RawAnnotation) -> Option<Self> {
+		let style = match (raw.kind.as_str(), raw.style.as_deref()) {
+			("highlight", _) => AnnotationStyle::Highlight,
+			("text", Some("speech")) => AnnotationStyle::Speech,
+			("text", Some("note")) => AnnotationStyle::Note,
+			("text", Some("title")) => AnnotationStyle::Title,
+			("text", Some("label")) | ("text", Some("highlightText")) => AnnotationStyle::Label,
+			("text", _) => AnnotationStyle::Popup,
+			_ => return None,
+		};
+
+		let moving_region = raw.segment?.moving_region?;
+		let regions = match moving_region.rect_regions.is_empty() {
+			true => moving_region.anchored_regions,
+			false => moving_region.rect_regions,
+		};
+		let first = regions.first()?;
+		let last = regions.last()?;
+		let start = parse_timestamp(&first.t)?;
+		let end = parse_timestamp(&last.t)?;
+		let region = AnnotationRegion { x: first.x, y: first.y, width: first.w, height: first.h };
+
+		let appearance = raw
+			.appearance
+			.map(|appearance| AnnotationAppearance {
+				background: appearance.bg_color.as_deref().and_then(parse_colour),
+				background_alpha: appearance.bg_alpha,
+				foreground: appearance.fg_color.as_deref().and_then(parse_colour),
+				text_size: appearance.text_size,
+			})
+			.unwrap_or_default();
+
+		let link = raw
+			.action
+			.and_then(|action| action.url)
+			.and_then(|url| url.value)
+			.map(|url| AnnotationLink::from_url(&url));
+		let text = raw.text.map(|text| text.trim().to_string()).filter(|text| !text.is_empty());
+
+		Some(Self { id: raw.id, style, text, start, end, region, appearance, link })
+	}
+}
+
+impl AnnotationAppearance {
+	/// The background as a CSS `rgba` colour, so the text drawn on top stays opaque.
+	pub fn background_css(&self) -> Option<String> {
+		let hex = self.background.as_deref()?.trim_start_matches('#');
+		let colour = u32::from_str_radix(hex, 16).ok()?;
+		let (red, green, blue) = (colour >> 16 & 0xff, colour >> 8 & 0xff, colour & 0xff);
+		let alpha = self.background_alpha.unwrap_or(1f64);
+		Some(format!("rgba({}, {}, {}, {})", red, green, blue, alpha))
+	}
+}
+
+impl AnnotationLink {
+	fn from_url(url: &str) -> Self {
+		let is_youtube = url.contains("youtube.com/watch") || url.contains("youtu.be/");
+		let query = url.split_once('?').map(|(_, query)| query).unwrap_or_default();
+		let param = |name: &str| {
+			query.split('&').find_map(|pair| {
+				let (key, value) = pair.split_once('=')?;
+				(key == name).then(|| value.to_string())
+			})
+		};
+
+		let id = match url.split_once("youtu.be/") {
+			Some((_, path)) => path.split(['?', '&', '#']).next().map(str::to_string),
+			None => param("v"),
+		};
+		match id.filter(|_| is_youtube) {
+			Some(id) => {
+				let start = param("t").and_then(|t| t.trim_end_matches('s').parse().ok());
+				AnnotationLink::Video { id, start }
+			}
+			None => AnnotationLink::Url(url.to_string()),
+		}
+	}
+}
+
+/// Parses `h:mm:ss.f`, `m:ss.f` or `s.f` into seconds. Returns `None` for `never`.
+fn parse_timestamp(timestamp: &str) -> Option<f64> {
+	timestamp
+		.split(':')
+		.try_fold(0f64, |seconds, part| Some(seconds * 60f64 + part.parse::<f64>().ok()?))
+}
+
+/// Annotation colours are stored as decimal RGB integers.
+fn parse_colour(colour: &str) -> Option<String> {
+	let colour = colour.parse::<u32>().ok()?;
+	Some(format!("#{:06x}", colour & 0xffffff))
+}
+
+#[derive(Deserialize)]
+struct AnnotationsDocument {
+	#[serde(default)]
+	annotations: RawAnnotationList,
+}
+
+#[derive(Deserialize, Default)]
+struct RawAnnotationList {
+	#[serde(rename = "annotation", default)]
+	items: Vec<RawAnnotation>,
+}
+
+#[derive(Deserialize)]
+struct RawAnnotation {
+	#[serde(default)]
+	id: String,
+	#[serde(rename = "type", default)]
+	kind: String,
+	style: Option<String>,
+	#[serde(rename = "TEXT")]
+	text: Option<String>,
+	segment: Option<RawSegment>,
+	appearance: Option<RawAppearance>,
+	action: Option<RawAction>,
+}
+
+#[derive(Deserialize)]
+struct RawSegment {
+	#[serde(rename = "movingRegion")]
+	moving_region: Option<RawMovingRegion>,
+}
+
+#[derive(Deserialize)]
+struct RawMovingRegion {
+	#[serde(rename = "rectRegion", default)]
+	rect_regions: Vec<RawRegion>,
+	#[serde(rename = "anchoredRegion", default)]
+	anchored_regions: Vec<RawRegion>,
+}
+
+#[derive(Deserialize)]
+struct RawRegion {
+	t: String,
+	x: f64,
+	y: f64,
+	w: f64,
+	h: f64,
+}
+
+#[derive(Deserialize)]
+struct RawAppearance {
+	#[serde(rename = "bgColor")]
+	bg_color: Option<String>,
+	#[serde(rename = "bgAlpha")]
+	bg_alpha: Option<f64>,
+	#[serde(rename = "fgColor")]
+	fg_color: Option<String>,
+	#[serde(rename = "textSize")]
+	text_size: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct RawAction {
+	url: Option<RawUrl>,
+}
+
+#[derive(Deserialize)]
+struct RawUrl {
+	value: Option<String>,
+}
@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch::fetch, hidden::*};
+use crate::{fetch::fetch, hidden::*, transport::Transport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Captions {
@@ -9,10 +9,309 @@ pub struct Captions {
 }
 
 impl Captions {
-	async fn fetch_captions(server: &str, id: &str) -> Result<Self, RustyTubeError> {
+	pub async fn fetch_captions(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+	) -> Result<Self, RustyTubeError> {
 		let captions_url: String = format!("{server}/api/v1/captions/{id}");
-		let captions_json: String = fetch(&captions_url).await?;
+		let captions_json: String = fetch(transport, &captions_url).await?;
 		let captions: Captions = serde_json::from_str(&captions_json)?;
 		Ok(captions)
 	}
 }
+
+/// A caption track parsed from WebVTT.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CaptionTrack {
+	pub cues: Vec<Cue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Cue {
+	pub id: Option<String>,
+	/// Seconds into the video at which the cue is shown.
+	pub start: f64,
+	/// Seconds into the video at which the cue is hidden.
+	pub end: f64,
+	pub settings: CueSettings,
+	pub lines: Vec<CueLine>,
+}
+
+/// Positioning from the cue's timing line, e.g. `align:start position:0%`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CueSettings {
+	pub vertical: Option<String>,
+	pub line: Option<String>,
+	pub position: Option<String>,
+	pub size: Option<String>,
+	pub align: Option<String>,
+	pub region: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CueLine {
+	pub spans: Vec<CueSpan>,
+}
+
+/// A run of cue text sharing the same styling.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CueSpan {
+	pub text: String,
+	pub italic: bool,
+	pub bold: bool,
+	pub underline: bool,
+	/// Classes from `<c.class>` tags, such as the colour classes used by YouTube.
+	pub classes: Vec<String>,
+	/// The speaker from a `<v Speaker>` tag.
+	pub voice: Option<String>,
+	/// When this span starts being spoken, from word timings in auto-generated captions.
+	pub timestamp: Option<f64>,
+}
+
+impl CueLine {
+	pub fn text(&self) -> String {
+		self.spans.iter().map(|span| span.text.as_str()).collect()
+	}
+}
+
+impl Cue {
+	/// The cue's text without styling, one line per caption line.
+	pub fn text(&self) -> String {
+		self.lines.iter().map(CueLine::text).collect::<Vec<String>>().join("\n")
+	}
+
+	pub fn is_active(&self, time: f64) -> bool {
+		self.start <= time && time < self.end
+	}
+}
+
+impl CaptionTrack {
+	/// Fetches and parses a caption track, machine translated into `tlang` when given.
+	pub async fn fetch_track(
+		transport: &impl Transport,
+		server: &str,
+		caption: &Caption,
+		tlang: Option<&str>,
+	) -> Result<Self, RustyTubeError> {
+		let track_url = match tlang {
+			Some(tlang) => caption.translated_url(server, tlang),
+			None => caption.url(server),
+		};
+		let vtt = fetch(transport, &track_url).await?;
+		let track = Self::parse(&vtt)?;
+		Ok(match caption.is_auto_generated() {
+			true => track.collapse_rolling(),
+			false => track,
+		})
+	}
+
+	pub fn parse(vtt: &str) -> Result<Self, RustyTubeError> {
+		let vtt = vtt.trim_start_matches('\u{feff}').replace("\r\n", "\n").replace('\r', "\n");
+		let lines: Vec<&str> = vtt.lines().collect();
+		if !lines.first().is_some_and(|header| header.starts_with("WEBVTT")) {
+			return Err(RustyTubeError::webvtt_parse(1));
+		}
+
+		let mut cues = vec![];
+		let mut index = 1;
+		while index < lines.len() {
+			// Blocks are separated by empty lines, a line of spaces is still part of a cue.
+			while index < lines.len() && lines[index].is_empty() {
+				index += 1;
+			}
+			let block_start = index;
+			while index < lines.len() && !lines[index].is_empty() {
+				index += 1;
+			}
+			let block = &lines[block_start..index];
+			let Some(first) = block.first() else {
+				break;
+			};
+
+			let (id, timing_offset) = match first.contains("-->") {
+				true => (None, 0),
+				false => (Some(first.to_string()), 1),
+			};
+			let Some(timing) = block.get(timing_offset).filter(|line| line.contains("-->")) else {
+				// The header's metadata and NOTE, STYLE and REGION blocks have no timing line.
+				continue;
+			};
+			let line_number = block_start + timing_offset + 1;
+			let (start, end, settings) =
+				parse_timing(timing).ok_or(RustyTubeError::webvtt_parse(line_number))?;
+			let lines = parse_cue_text(&block[timing_offset + 1..]);
+
+			cues.push(Cue { id, start, end, settings, lines });
+		}
+
+		Ok(Self { cues })
+	}
+
+	/// YouTube's auto-generated captions roll: each cue repeats the previous line above the new
+	/// words, and a few milliseconds long cue freezes the text in between. This keeps only the
+	/// new words so every phrase appears once.
+	pub fn collapse_rolling(self) -> Self {
+		let mut previous_line = String::new();
+		let cues = self
+			.cues
+			.into_iter()
+			.filter(|cue| cue.end - cue.start > 0.05)
+			.filter_map(|mut cue| {
+				cue.lines.retain(|line| {
+					let text = line.text();
+					!text.trim().is_empty() && text.trim() != previous_line.trim()
+				});
+				previous_line = cue.lines.last()?.text();
+				Some(cue)
+			})
+			.collect();
+		Self { cues }
+	}
+
+	pub fn active_at(&self, time: f64) -> impl Iterator<Item = &Cue> {
+		self.cues.iter().filter(move |cue| cue.is_active(time))
+	}
+
+	/// Serialises the track back into WebVTT. Styling is dropped, only the text is kept.
+	pub fn to_vtt(&self) -> String {
+		let mut vtt = String::from("WEBVTT\n");
+		self.cues.iter().for_each(|cue| {
+			let text = cue.text().replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+			vtt.push_str(&format!(
+				"\n{} --> {}\n{}\n",
+				format_timestamp(cue.start),
+				format_timestamp(cue.end),
+				text
+			));
+		});
+		vtt
+	}
+}
+
+fn parse_timing(line: &str) -> Option<(f64, f64, CueSettings)> {
+	let (start, rest) = line.split_once("-->")?;
+	let mut rest = rest.split_whitespace();
+	let start = parse_timestamp(start.trim())?;
+	let end = parse_timestamp(rest.next()?)?;
+
+	let mut settings = CueSettings::default();
+	rest.filter_map(|setting| setting.split_once(':')).for_each(|(key, value)| {
+		let value = Some(value.to_string());
+		match key {
+			"vertical" => settings.vertical = value,
+			"line" => settings.line = value,
+			"position" => settings.position = value,
+			"size" => settings.size = value,
+			"align" => settings.align = value,
+			"region" => settings.region = value,
+			_ => {}
+		}
+	});
+	Some((start, end, settings))
+}
+
+/// Parses `hh:mm:ss.ttt` or `mm:ss.ttt` into seconds.
+fn parse_timestamp(timestamp: &str) -> Option<f64> {
+	let parts: Vec<&str> = timestamp.split(':').collect();
+	if !(2..=3).contains(&parts.len()) || !timestamp.contains('.') {
+		return None;
+	}
+	parts.iter().try_fold(0f64, |seconds, part| Some(seconds * 60f64 + part.parse::<f64>().ok()?))
+}
+
+fn format_timestamp(seconds: f64) -> String {
+	let millis = (seconds * 1000f64).round() as u64;
+	format!(
+		"{:02}:{:02}:{:02}.{:03}",
+		millis / 3_600_000,
+		millis / 60_000 % 60,
+		millis / 1000 % 60,
+		millis % 1000
+	)
+}
+
+#[derive(Clone, Default)]
+struct SpanStyle {
+	italic: u32,
+	bold: u32,
+	underline: u32,
+	classes: Vec<Vec<String>>,
+	voice: Option<String>,
+	timestamp: Option<f64>,
+}
+
+impl SpanStyle {
+	fn span(&self, text: &str) -> CueSpan {
+		CueSpan {
+			text: html_escape::decode_html_entities(text).to_string(),
+			italic: self.italic > 0,
+			bold: self.bold > 0,
+			underline: self.underline > 0,
+			classes: self.classes.concat(),
+			voice: self.voice.clone(),
+			timestamp: self.timestamp,
+		}
+	}
+}
+
+/// Splits cue text into styled spans. Styling carries over line breaks, like in a browser.
+fn parse_cue_text(lines: &[&str]) -> Vec<CueLine> {
+	let mut style = SpanStyle::default();
+
+	lines
+		.iter()
+		.map(|line| {
+			let mut spans = vec![];
+			let mut rest = *line;
+			while !rest.is_empty() {
+				let Some(tag_start) = rest.find('<') else {
+					spans.push(style.span(rest));
+					break;
+				};
+				if tag_start > 0 {
+					spans.push(style.span(&rest[..tag_start]));
+				}
+				let Some(tag_end) = rest[tag_start..].find('>') else {
+					spans.push(style.span(&rest[tag_start..]));
+					break;
+				};
+				apply_tag(&mut style, &rest[tag_start + 1..tag_start + tag_end]);
+				rest = &rest[tag_start + tag_end + 1..];
+			}
+			CueLine { spans }
+		})
+		.collect()
+}
+
+fn apply_tag(style: &mut SpanStyle, tag: &str) {
+	if let Some(timestamp) = parse_timestamp(tag) {
+		style.timestamp = Some(timestamp);
+		return;
+	}
+
+	let (closing, tag) = match tag.strip_prefix('/') {
+		Some(tag) => (true, tag),
+		None => (false, tag),
+	};
+	let (name, annotation) = tag.split_once(' ').unwrap_or((tag, ""));
+	let mut name_and_classes = name.split('.');
+	let name = name_and_classes.next().unwrap_or_default();
+	let toggle = |count: &mut u32| match closing {
+		true => *count = count.saturating_sub(1),
+		false => *count += 1,
+	};
+
+	match name {
+		"i" => toggle(&mut style.italic),
+		"b" => toggle(&mut style.bold),
+		"u" => toggle(&mut style.underline),
+		"c" if closing => {
+			style.classes.pop();
+		}
+		"c" => style.classes.push(name_and_classes.map(str::to_string).collect()),
+		"v" if closing => style.voice = None,
+		"v" => style.voice = Some(annotation.trim().to_string()),
+		_ => {}
+	}
+}
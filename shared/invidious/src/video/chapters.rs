@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::video::Video;
+
+/// Fewer timestamps than this are more likely mentions of moments than a chapter list.
+const MIN_CHAPTERS: usize = 3;
+
+/// The chapters of a video, as listed by timestamps in its description.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Chapters {
+	pub chapters: Vec<Chapter>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Chapter {
+	pub title: String,
+	/// Seconds into the video at which the chapter starts.
+	pub start: f64,
+	/// Seconds into the video at which the next chapter starts, or the video ends.
+	pub end: f64,
+}
+
+impl Chapters {
+	/// Reads the chapters from the lines of `description` holding a timestamp, such as
+	/// `0:00 Intro` or `Setup - 1:02:30`. Like YouTube, the list has to start at `0:00` and
+	/// count up; a description without such a list has no chapters.
+	pub fn from_description(description: &str, length: f64) -> Self {
+		let mut starts: Vec<(f64, String)> = vec![];
+
+		for (start, title) in description.lines().filter_map(parse_chapter_line) {
+			match starts.last() {
+				None if start == 0f64 => starts.push((start, title)),
+				None => (),
+				Some((last_start, _)) if start > *last_start && start < length => {
+					starts.push((start, title))
+				}
+				// The list ended and the timestamp belongs to something else.
+				Some(_) => break,
+			}
+		}
+		if starts.len() < MIN_CHAPTERS {
+			return Self::default();
+		}
+
+		let ends = starts.iter().skip(1).map(|(start, _)| *start).chain([length]);
+		let chapters = starts
+			.iter()
+			.zip(ends)
+			.map(|((start, title), end)| Chapter { title: title.clone(), start: *start, end })
+			.collect();
+		Self { chapters }
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.chapters.is_empty()
+	}
+
+	/// The chapter playing `time` seconds into the video.
+	pub fn chapter_at(&self, time: f64) -> Option<&Chapter> {
+		self.chapters.iter().rev().find(|chapter| chapter.start <= time)
+	}
+}
+
+impl Video {
+	pub fn chapters(&self) -> Chapters {
+		Chapters::from_description(&self.description, self.length as f64)
+	}
+}
+
+/// Splits a line into the first timestamp it holds and the text around it.
+fn parse_chapter_line(line: &str) -> Option<(f64, String)> {
+	let is_separator = |char: char| {
+		char.is_whitespace()
+			|| matches!(char, '-' | '–' | '—' | ':' | '|' | '•' | '(' | ')' | '[' | ']')
+	};
+
+	let (token, start) = line
+		.split_whitespace()
+		.map(|word| word.trim_matches(is_separator))
+		.find_map(|word| parse_timestamp(word).map(|start| (word, start)))?;
+	let token_start = line.find(token)?;
+	let title = format!("{} {}", &line[..token_start], &line[token_start + token.len()..]);
+	let title =
+		title.trim_matches(is_separator).split_whitespace().collect::<Vec<&str>>().join(" ");
+
+	Some((start, title))
+}
+
+/// Parses `m:ss` or `h:mm:ss` into seconds.
+fn parse_timestamp(timestamp: &str) -> Option<f64> {
+	let parts = timestamp.split(':').collect::<Vec<&str>>();
+	if !(2..=3).contains(&parts.len())
+		|| parts
+			.iter()
+			.any(|part| part.is_empty() || !part.chars().all(|char| char.is_ascii_digit()))
+		|| parts.iter().skip(1).any(|part| part.len() != 2)
+	{
+		return None;
+	}
+
+	let mut seconds = 0u64;
+	for (index, part) in parts.iter().enumerate() {
+		let value: u64 = part.parse().ok()?;
+		if index > 0 && value >= 60 {
+			return None;
+		}
+		seconds = seconds * 60 + value;
+	}
+	Some(seconds as f64)
+}
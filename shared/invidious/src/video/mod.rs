@@ -1,6 +1,9 @@
 mod annotations;
 mod captions;
+mod chapters;
 mod video;
 
+pub use annotations::*;
 pub use captions::*;
+pub use chapters::*;
 pub use video::*;
@@ -4,8 +4,9 @@ use serde::{Deserialize, Serialize};
 use crate::{
 	common::*,
 	fetch::fetch,
-	formats::{AdaptiveFormat, LegacyFormat},
+	formats::{AdaptiveFormat, DashManifest, LegacyFormat},
 	hidden::*,
+	transport::Transport,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -80,12 +81,22 @@ pub struct Video {
 }
 
 impl Video {
-	pub async fn fetch_video(server: &str, id: &str, lang: &str) -> Result<Self, RustyTubeError> {
+	pub async fn fetch_video(
+		transport: &impl Transport,
+		server: &str,
+		id: &str,
+		lang: &str,
+	) -> Result<Self, RustyTubeError> {
 		let video_url = format!("{server}/api/v1/videos/{id}?hl={lang}");
-		let video_json = fetch(&video_url).await?;
+		let video_json = fetch(transport, &video_url).await?;
 		let video = serde_json::from_str(&video_json)?;
 		Ok(video)
 	}
+
+	/// A DASH manifest for the video's adaptive formats, for players that support MPEG-DASH.
+	pub fn dash_manifest(&self) -> DashManifest {
+		DashManifest::new(&self.adaptive_formats, self.length as f64)
+	}
 }
 
 impl PartialEq for Video {
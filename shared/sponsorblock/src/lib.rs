@@ -1,13 +1,18 @@
 pub static SPONSORBLOCK_API: &'static str = "https://sponsor.ajay.app/api";
 
-use std::{error::Error, future::Future};
-
+use invidious::{BrowserTransport, Transport};
+use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// How many hex characters of the video ID's hash a hash-prefix lookup sends, as the API suggests.
+pub const HASH_PREFIX_LENGTH: usize = 4;
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Query {
 	#[serde(rename = "videoID")]
 	video_id: String,
+	lookup: Lookup,
 	#[serde(rename = "requiredSegments")]
 	required_segments: Option<Vec<String>>,
 	categories: Option<Vec<Category>>,
@@ -16,7 +21,18 @@ pub struct Query {
 	service: Option<String>,
 }
 
-#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+/// How a query names its video to the API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Lookup {
+	/// Sends the first characters of the SHA-256 hash of the video ID, so the API can't tell which
+	/// of the videos sharing the prefix is watched. The matching video is picked out locally.
+	#[default]
+	HashPrefix,
+	/// Sends the video ID itself.
+	VideoId,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Category {
 	#[default]
 	Sponsor,
@@ -29,6 +45,24 @@ pub enum Category {
 	Filler,
 }
 
+impl Category {
+	pub const ALL: [Category; 8] = [
+		Category::Sponsor,
+		Category::SelfPromotion,
+		Category::Interaction,
+		Category::Intro,
+		Category::Outro,
+		Category::Preview,
+		Category::OffTopicMusic,
+		Category::Filler,
+	];
+
+	/// Reads a category as named by the API, e.g. `selfpromo`.
+	pub fn from_api_name(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|category| category.to_string() == name)
+	}
+}
+
 impl ToString for Category {
 	fn to_string(&self) -> String {
 		match self {
@@ -44,7 +78,7 @@ impl ToString for Category {
 	}
 }
 
-#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Action {
 	#[default]
 	Skip,
@@ -54,6 +88,15 @@ pub enum Action {
 	Chapter,
 }
 
+impl Action {
+	/// Reads an action type as named by the API, e.g. `mute`.
+	pub fn from_api_name(name: &str) -> Option<Self> {
+		[Action::Skip, Action::Mute, Action::Full, Action::PointOfInterest, Action::Chapter]
+			.into_iter()
+			.find(|action| action.to_string() == name)
+	}
+}
+
 impl ToString for Action {
 	fn to_string(&self) -> String {
 		match self {
@@ -74,11 +117,24 @@ impl Query {
 		actions: Option<Vec<Action>>,
 		service: Option<String>,
 	) -> Self {
-		Self { video_id, required_segments, categories, actions, service }
+		let lookup = Lookup::default();
+		Self { video_id, lookup, required_segments, categories, actions, service }
 	}
 
 	pub fn build(video_id: String) -> Self {
-		Self { video_id, required_segments: None, categories: None, actions: None, service: None }
+		Self {
+			video_id,
+			lookup: Lookup::default(),
+			required_segments: None,
+			categories: None,
+			actions: None,
+			service: None,
+		}
+	}
+
+	pub fn set_lookup(&mut self, lookup: Lookup) -> Self {
+		self.lookup = lookup;
+		self.clone()
 	}
 
 	pub fn set_required_segments(&mut self, required_segments: Option<Vec<String>>) -> Self {
@@ -99,49 +155,73 @@ impl Query {
 	}
 
 	pub fn url(&self) -> String {
-		let required_segments = self
-			.required_segments
-			.as_ref()
-			.map(|required_segments| format!("requiredSegments=[{}]", required_segments.join(",")));
+		let json_list = |items: Vec<String>| {
+			items.iter().map(|item| format!("\"{}\"", item)).collect::<Vec<String>>().join(",")
+		};
+
+		let (path, video_id) = match self.lookup {
+			Lookup::HashPrefix => (format!("/{}", hash_prefix(&self.video_id)), None),
+			Lookup::VideoId => (String::new(), Some(format!("videoID={}", self.video_id))),
+		};
+
+		let required_segments = self.required_segments.as_ref().map(|required_segments| {
+			format!("requiredSegments=[{}]", json_list(required_segments.clone()))
+		});
 
 		let categories = self.categories.as_ref().map(|categories| {
 			format!(
 				"categories=[{}]",
-				categories.iter().map(|cat| cat.to_string()).collect::<Vec<String>>().join(",")
+				json_list(categories.iter().map(|cat| cat.to_string()).collect())
 			)
 		});
 
 		let actions = self.actions.as_ref().map(|actions| {
 			format!(
 				"actionTypes=[{}]",
-				actions.iter().map(|cat| cat.to_string()).collect::<Vec<String>>().join(",")
+				json_list(actions.iter().map(|action| action.to_string()).collect())
 			)
 		});
 
-		let service = self.service.as_ref().map(|service| format!("&service={}", service));
-
-		format!(
-			"{}/skipSegments?videoID={}{}{}{}{}",
-			SPONSORBLOCK_API,
-			self.video_id,
-			required_segments.unwrap_or_default(),
-			categories.unwrap_or_default(),
-			actions.unwrap_or_default(),
-			service.unwrap_or_default()
-		)
-	}
+		let service = self.service.as_ref().map(|service| format!("service={}", service));
 
-	pub async fn send_query(&self) -> Result<Option<Response>, Box<dyn Error>> {
-		let response = gloo::net::http::Request::get(&self.url()).send().await?;
+		let params = [video_id, required_segments, categories, actions, service]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<String>>();
 
-		match response.status() == 404 {
-			true => Ok(None),
-			false => {
-				let response_text = response.text().await?;
-				Ok(Some(serde_json::from_str::<Response>(&response_text)?))
-			}
+		match params.is_empty() {
+			true => format!("{}/skipSegments{}", SPONSORBLOCK_API, path),
+			false => format!("{}/skipSegments{}?{}", SPONSORBLOCK_API, path, params.join("&")),
 		}
 	}
+
+	/// Fetches the segments of the video through `transport`, e.g. a `MockTransport` in tests.
+	pub async fn fetch(
+		&self,
+		transport: &impl Transport,
+	) -> Result<Option<Response>, RustyTubeError> {
+		let response = transport.get(&self.url()).await?;
+		let segments = match self.lookup {
+			Lookup::HashPrefix => Response::from_hash_prefix_response(
+				response.status,
+				&response.body,
+				&self.video_id,
+			)?,
+			Lookup::VideoId => Response::from_query_response(response.status, &response.body)?,
+		};
+		Ok(segments)
+	}
+
+	pub async fn send_query(&self) -> Result<Option<Response>, RustyTubeError> {
+		self.fetch(&BrowserTransport).await
+	}
+}
+
+/// The first [`HASH_PREFIX_LENGTH`] hex characters of the SHA-256 hash of `video_id`.
+pub fn hash_prefix(video_id: &str) -> String {
+	let hash = Sha256::digest(video_id.as_bytes());
+	hash.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()[..HASH_PREFIX_LENGTH]
+		.to_string()
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -159,109 +239,236 @@ pub struct Segment {
 	pub votes: i64,
 	pub description: String,
 }
+impl Segment {
+	pub fn start(&self) -> f64 {
+		self.timeframe.0
+	}
+
+	pub fn end(&self) -> f64 {
+		self.timeframe.1
+	}
+
+	pub fn duration(&self) -> f64 {
+		self.timeframe.1 - self.timeframe.0
+	}
+
+	pub fn category(&self) -> Option<Category> {
+		Category::from_api_name(&self.category)
+	}
+
+	pub fn action(&self) -> Option<Action> {
+		Action::from_api_name(&self.action)
+	}
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct Response {
 	pub segments: Vec<Segment>,
 }
 
+impl Response {
+	/// Parses the body of a `skipSegments` reply. The API answers 404 for videos without segments.
+	pub fn from_query_response(status: u16, body: &str) -> Result<Option<Self>, serde_json::Error> {
+		match status == 404 {
+			true => Ok(None),
+			false => Ok(Some(serde_json::from_str::<Response>(body)?)),
+		}
+	}
+
+	/// Parses the body of a hash-prefix `skipSegments` reply, which lists the segments of every
+	/// video sharing the prefix, keeping those of `video_id`.
+	pub fn from_hash_prefix_response(
+		status: u16,
+		body: &str,
+		video_id: &str,
+	) -> Result<Option<Self>, serde_json::Error> {
+		if status == 404 {
+			return Ok(None);
+		}
+		let videos = serde_json::from_str::<Vec<HashPrefixVideo>>(body)?;
+		Ok(videos
+			.into_iter()
+			.find(|video| video.video_id == video_id)
+			.map(|video| Self { segments: video.segments }))
+	}
+}
+
+#[derive(Deserialize)]
+struct HashPrefixVideo {
+	#[serde(rename = "videoID")]
+	video_id: String,
+	segments: Vec<Segment>,
+}
+
 #[cfg(test)]
 mod tests {
-	use std::{error::Error, future::Future};
+	use invidious::MockTransport;
 
-	use crate::{Query, Response};
+	use crate::{
+		hash_prefix, Action, Category, Lookup, Query, Response, HASH_PREFIX_LENGTH,
+		SPONSORBLOCK_API,
+	};
 
 	use wasm_bindgen_test::*;
 
-	pub static TEST_VIDEOS: &[&'static str; 61] = &[
-		"wsmHCfSZM70",
-		"RE0f4ed5N24",
-		"Bu6PxzNR3dY",
-		"r22tyT77vOw",
-		"SKIXCPn2xB0",
-		"HEbaKDkzomI",
-		"yLy3ygqA5yg",
-		"s1fxZ-VWs2U",
-		"gIMOtNzjHL4",
-		"69dCWRvIzyo",
-		"bc8Okr4cgL4",
-		"1EIlcYCfEIE",
-		"sB1XQYDbzOE",
-		"QsM6b5yix0U",
-		"seoaDLWuHtU",
-		"Al93JD5GExY",
-		"kaf3pdJ_Cow",
-		"i9TJWsuzBLU",
-		"CcHevgjAnV0",
-		"8BxVi6YiicQ",
-		"DNfj2BxGIxA",
-		"X5OIucMnw7M",
-		"n3XTZde8ZvQ",
-		"FwdDAZruMKk",
-		"-duJtlw394U",
-		"AfzwEF5yr3k",
-		"YjkEVrJP7jI",
-		"fpayOqZNWUo",
-		"TNZk-xnxIYE",
-		"iQr1EZ3rLOM",
-		"WXV-zB3EfNw",
-		"qz7NHaCspzg",
-		"YE431SYO2Is",
-		"YUMtJ6K43K8",
-		"2fpZbH4BNsI",
-		"8V8uQbIFlh0",
-		"tVvbLS2Bm8c",
-		"gMULrCIT6QY",
-		"1r82NBk3aKM",
-		"JoaIoctknLk",
-		"jv3zuQ-forQ",
-		"M42qWWi4y6k",
-		"JAKm3-ijEBo",
-		"5kJv-oSajto",
-		"mtaQroi75M0",
-		"nCS4BtJ34-o",
-		"8ZrNRk9OUGA",
-		"j6Gf482ZjSg",
-		"Qa6y_CiyAMA",
-		"mY-Yc1B6vdk",
-		"aeifzxaDOVo",
-		"4QY6ADlspTQ",
-		"3wCexOqw-h4",
-		"VvMjFXwL9Uw",
-		"BH5ghaSLVUc",
-		"MuP-9O7gNIc",
-		"9vjVT-Pp4R8",
-		"hDWeJnH4Wys",
-		"msiKpFV8HNY",
-		"FufN3c68nPg",
-		"CW1CLcT83as",
+	macro_rules! recorded_response {
+		($video:literal) => {
+			($video, include_str!(concat!("../test_files/", $video, ".json")))
+		};
+	}
+
+	/// Responses recorded from the live API, so the tests never hit it.
+	pub static RECORDED_RESPONSES: &[(&str, &str)] = &[
+		recorded_response!("-duJtlw394U"),
+		recorded_response!("1EIlcYCfEIE"),
+		recorded_response!("69dCWRvIzyo"),
+		recorded_response!("8BxVi6YiicQ"),
+		recorded_response!("AfzwEF5yr3k"),
+		recorded_response!("Al93JD5GExY"),
+		recorded_response!("Bu6PxzNR3dY"),
+		recorded_response!("CcHevgjAnV0"),
+		recorded_response!("DNfj2BxGIxA"),
+		recorded_response!("FwdDAZruMKk"),
+		recorded_response!("HEbaKDkzomI"),
+		recorded_response!("QsM6b5yix0U"),
+		recorded_response!("RE0f4ed5N24"),
+		recorded_response!("SKIXCPn2xB0"),
+		recorded_response!("TNZk-xnxIYE"),
+		recorded_response!("WXV-zB3EfNw"),
+		recorded_response!("X5OIucMnw7M"),
+		recorded_response!("YjkEVrJP7jI"),
+		recorded_response!("bc8Okr4cgL4"),
+		recorded_response!("fpayOqZNWUo"),
+		recorded_response!("gIMOtNzjHL4"),
+		recorded_response!("i9TJWsuzBLU"),
+		recorded_response!("iQr1EZ3rLOM"),
+		recorded_response!("kaf3pdJ_Cow"),
+		recorded_response!("n3XTZde8ZvQ"),
+		recorded_response!("r22tyT77vOw"),
+		recorded_response!("s1fxZ-VWs2U"),
+		recorded_response!("sB1XQYDbzOE"),
+		recorded_response!("seoaDLWuHtU"),
+		recorded_response!("yLy3ygqA5yg"),
 	];
 
-	// #[cfg(not(target_arch = "wasm-32"))]
-	// #[test]
-	// pub fn collect_query_responses() {
-	// 	let client = reqwest::blocking::Client::new();
-	// 	for test_video in TEST_VIDEOS {
-	// 		let resp = client
-	// 			.get(Query::build(test_video.to_string()).url())
-	// 			.send()
-	// 			.unwrap()
-	// 			.text()
-	// 			.unwrap();
+	wasm_bindgen_test_configure!(run_in_browser);
 
-	// 		serde_json::from_str::<Response>(&resp).unwrap();
+	/// Serves every recorded response at the url a video ID lookup of its video requests.
+	fn recorded_transport() -> MockTransport {
+		RECORDED_RESPONSES.iter().fold(MockTransport::new(), |transport, (video, body)| {
+			transport.with_response(video_id_query(video).url(), *body)
+		})
+	}
 
-	// 		std::fs::write(format!("test_files/{}.json", test_video), resp).unwrap();
-	// 	}
-	// }
+	fn video_id_query(video: &str) -> Query {
+		Query::build(video.to_string()).set_lookup(Lookup::VideoId)
+	}
 
-	wasm_bindgen_test_configure!(run_in_browser);
+	#[wasm_bindgen_test]
+	pub async fn parse_recorded_responses() {
+		let transport = recorded_transport();
+
+		for (video, _) in RECORDED_RESPONSES {
+			let response = video_id_query(video).fetch(&transport).await.unwrap().unwrap();
+
+			assert!(!response.segments.is_empty(), "{} has no segments", video);
+			response.segments.iter().for_each(|segment| {
+				assert!(segment.timeframe.0 <= segment.timeframe.1, "{} {}", video, segment.uuid);
+				assert_eq!(segment.category(), Some(Category::Sponsor), "{}", video);
+				assert_eq!(segment.action(), Some(Action::Skip), "{}", video);
+			});
+		}
+		assert_eq!(transport.requests().len(), RECORDED_RESPONSES.len());
+	}
 
 	#[wasm_bindgen_test]
-	pub async fn fetch_response() {
-		for test_video in TEST_VIDEOS {
-			let query = Query::build(test_video.to_string());
-			query.send_query().await.unwrap();
+	pub async fn round_trip_recorded_responses() {
+		let transport = recorded_transport();
+
+		for (video, _) in RECORDED_RESPONSES {
+			let response = video_id_query(video).fetch(&transport).await.unwrap().unwrap();
+			let serialised = serde_json::to_value(&response).unwrap();
+			let reparsed: Response = serde_json::from_value(serialised.clone()).unwrap();
+
+			assert_eq!(serde_json::to_value(&reparsed).unwrap(), serialised, "{}", video);
 		}
 	}
+
+	#[wasm_bindgen_test]
+	pub async fn missing_segments_are_not_an_error() {
+		let query = video_id_query("wsmHCfSZM70");
+		let transport = MockTransport::new().with_status(query.url(), 404, "Not Found");
+
+		assert!(query.fetch(&transport).await.unwrap().is_none());
+	}
+
+	#[wasm_bindgen_test]
+	pub async fn build_query_url() {
+		let query = Query::build("wsmHCfSZM70".to_string()).set_lookup(Lookup::VideoId);
+
+		assert_eq!(query.url(), format!("{}/skipSegments?videoID=wsmHCfSZM70", SPONSORBLOCK_API));
+
+		let query = Query::build("wsmHCfSZM70".to_string())
+			.set_lookup(Lookup::VideoId)
+			.set_categories(Some(vec![Category::Sponsor, Category::OffTopicMusic]))
+			.set_actions(Some(vec![Action::Skip, Action::Mute]));
+
+		assert_eq!(
+			query.url(),
+			format!(
+				"{}/skipSegments?videoID=wsmHCfSZM70\
+				 &categories=[\"sponsor\",\"music_offtopic\"]&actionTypes=[\"skip\",\"mute\"]",
+				SPONSORBLOCK_API
+			)
+		);
+	}
+
+	#[wasm_bindgen_test]
+	pub async fn build_hash_prefix_query_url() {
+		let prefix = hash_prefix("wsmHCfSZM70");
+
+		assert_eq!(prefix.len(), HASH_PREFIX_LENGTH);
+		assert!(prefix.chars().all(|char| char.is_ascii_hexdigit()));
+		assert_eq!(hash_prefix("abc"), "ba78");
+
+		let query = Query::build("wsmHCfSZM70".to_string());
+
+		assert_eq!(query.url(), format!("{}/skipSegments/{}", SPONSORBLOCK_API, prefix));
+		assert!(!query.url().contains("wsmHCfSZM70"));
+
+		let query = query.clone().set_categories(Some(vec![Category::Sponsor]));
+
+		assert_eq!(
+			query.url(),
+			format!("{}/skipSegments/{}?categories=[\"sponsor\"]", SPONSORBLOCK_API, prefix)
+		);
+	}
+
+	#[wasm_bindgen_test]
+	pub async fn filter_hash_prefix_responses() {
+		let (video, body) = RECORDED_RESPONSES[0];
+		let segments: serde_json::Value = serde_json::from_str(body).unwrap();
+		let body = serde_json::json!([
+			{ "videoID": "someOtherId", "hash": "", "segments": [] },
+			{ "videoID": video, "hash": "", "segments": segments },
+		])
+		.to_string();
+		let query = Query::build(video.to_string());
+		let transport = MockTransport::new().with_response(query.url(), body);
+
+		let response = query.fetch(&transport).await.unwrap().unwrap();
+		let expected = video_id_query(video).fetch(&recorded_transport()).await.unwrap().unwrap();
+
+		assert_eq!(response.segments.len(), expected.segments.len());
+		assert_eq!(transport.requests(), vec![query.url()]);
+		assert!(!transport.requests()[0].contains(video));
+
+		let other_video = Query::build("notInReply".to_string());
+		let transport = MockTransport::new().with_response(other_video.url(), "[]");
+		assert!(other_video.fetch(&transport).await.unwrap().is_none());
+
+		let transport = MockTransport::new().with_status(query.url(), 404, "Not Found");
+		assert!(query.fetch(&transport).await.unwrap().is_none());
+	}
 }